@@ -25,6 +25,9 @@ pub trait AssetMining<Balance> {
     /// Issues reward to the reward pot of an Asset.
     fn reward(_asset_id: AssetId, _reward_value: Balance);
 
+    /// Called when a new era starts.
+    fn on_new_era() {}
+
     /// Returns the mining power of all mining assets.
     fn total_asset_mining_power() -> MiningPower {
         Self::asset_mining_power()
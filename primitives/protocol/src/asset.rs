@@ -52,6 +52,20 @@ pub const X_DOT: AssetId = 354;
 /// Reserved since this symbol had been used in legacy ChainX 1.0.
 pub const S_DOT: AssetId = 0xa0000000 | X_DOT;
 
-const EXTEND: AssetId = 0x01000000;
+/// The beginning of the asset ids decided by ChainX, which are not in SLIP44.
+pub const EXTEND: AssetId = 0x01000000;
 /// USDT asset in ChainX.
 pub const USDT: AssetId = EXTEND | 0x01;
+
+/// The first asset id allocated by the permissionless registration, the ids
+/// between `EXTEND` and this one are reserved for the assets decided by ChainX.
+pub const EXTEND_PERMISSIONLESS: AssetId = EXTEND | 0x00010000;
+/// The end (exclusive) of the `EXTEND` range.
+pub const EXTEND_END: AssetId = 0x02000000;
+
+/// The prefix of the virtual asset ids standing for the liquidity mining of the spot
+/// trading pairs, i.e., `LIQUIDITY_MINING | trading_pair_id`.
+pub const LIQUIDITY_MINING: AssetId = 0xf0000000;
+
+/// The maximum decimals of a permissionless registered asset.
+pub const PERMISSIONLESS_ASSET_MAX_DECIMALS: Decimals = 18;
@@ -20,5 +20,11 @@ pub const ASSET_TOKEN_NAME_MAX_LEN: usize = 48;
 /// The maximum length of asset description
 pub const ASSET_DESC_MAX_LEN: usize = 128;
 
+/// The maximum length of asset metadata URI, e.g., the logo URI and the homepage.
+pub const ASSET_URI_MAX_LEN: usize = 256;
+
+/// The maximum length of the origin chain identifier of an asset.
+pub const ASSET_ORIGIN_ID_MAX_LEN: usize = 64;
+
 /// The maximum length of memo
 pub const MEMO_MAX_LEN: usize = 80;
@@ -47,7 +47,9 @@ use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 
 use xpallet_dex_spot::{Depth, FullPairInfo, RpcOrder, TradingPairId};
 use xpallet_mining_asset::{MinerLedger, MiningAssetInfo, MiningDividendInfo};
-use xpallet_mining_staking::{NominatorInfo, NominatorLedger, ValidatorInfo};
+use xpallet_mining_staking::{
+    EraIndex, NominatorInfo, NominatorLedger, UnappliedSlash, ValidatorEraRecord, ValidatorInfo,
+};
 use xpallet_support::traits::MultisigAddressFor;
 
 // A few exports that help ease life for downstream crates.
@@ -75,7 +77,8 @@ pub use xp_runtime::Memo;
 
 // xpallet re-exports
 pub use xpallet_assets::{
-    AssetInfo, AssetRestrictions, AssetType, Chain, TotalAssetInfo, WithdrawalLimit,
+    AssetInfo, AssetRestrictions, AssetType, Chain, SnapshotId, TotalAssetInfo, VestingSchedule,
+    WithdrawalLimit,
 };
 #[cfg(feature = "std")]
 pub use xpallet_gateway_bitcoin::h256_rev;
@@ -97,17 +100,17 @@ pub mod constants;
 pub mod impls;
 
 use self::constants::{currency::*, fee::WeightToFee, time::*};
-use self::impls::{ChargeExtraFee, DealWithFees, SlowAdjustingFeeUpdate};
+use self::impls::{ChargeAssetTxPayment, ChargeExtraFee, DealWithFees, SlowAdjustingFeeUpdate};
 
 /// This runtime version.
 pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("chainx"),
     impl_name: create_runtime_str!("chainx-net"),
     authoring_version: 1,
-    spec_version: 10,
+    spec_version: 12,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 3,
 };
 
 /// The version information used to identify this runtime when compiled natively.
@@ -392,7 +395,21 @@ impl pallet_transaction_payment::Trait for Runtime {
     type FeeMultiplierUpdate = SlowAdjustingFeeUpdate<Self>;
 }
 
-impl xpallet_transaction_fee::Trait for Runtime {}
+parameter_types! {
+    // One storage item; key size is 32; value is size 16+16 bytes without the call filters.
+    pub const SponsorshipDeposit: Balance = deposit(1, 64);
+    // A pallet name and a call name of at most 32 bytes each.
+    pub const CallFilterDeposit: Balance = deposit(0, 64);
+}
+
+impl xpallet_transaction_fee::Trait for Runtime {
+    type Event = Event;
+    type PriceFeed = TimeWeightedPriceFeed;
+    type FeeReceivers = DealWithFees;
+    type SponsorshipDeposit = SponsorshipDeposit;
+    type CallFilterDeposit = CallFilterDeposit;
+    type WeightInfo = xpallet_transaction_fee::weights::SubstrateWeight<Runtime>;
+}
 
 parameter_types! {
     pub const SessionDuration: BlockNumber = EPOCH_DURATION_IN_BLOCKS;
@@ -450,7 +467,7 @@ where
             frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
             frame_system::CheckNonce::<Runtime>::from(nonce),
             frame_system::CheckWeight::<Runtime>::new(),
-            pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+            ChargeAssetTxPayment::new(tip, None, None),
             BaseFilter,
             ChargeExtraFee,
         );
@@ -890,12 +907,16 @@ impl xpallet_system::Trait for Runtime {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = xp_protocol::PCX;
+    pub const AssetRegistrationDeposit: Balance = 10_000 * DOLLARS;
+    pub const MaxVestingDelay: BlockNumber = 365 * DAYS;
 }
 
 impl xpallet_assets_registrar::Trait for Runtime {
     type Event = Event;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = XMiningAsset;
+    type Currency = Balances;
+    type RegistrationDeposit = AssetRegistrationDeposit;
     type WeightInfo = xpallet_assets_registrar::weights::SubstrateWeight<Runtime>;
 }
 
@@ -906,6 +927,7 @@ impl xpallet_assets::Trait for Runtime {
     type TreasuryAccount = SimpleTreasuryAccount;
     type OnCreatedAccount = frame_system::CallOnCreatedAccount<Runtime>;
     type OnAssetChanged = XMiningAsset;
+    type MaxVestingDelay = MaxVestingDelay;
     type WeightInfo = xpallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
@@ -930,6 +952,10 @@ impl xpallet_gateway_common::Trait for Runtime {
     type WeightInfo = xpallet_gateway_common::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const MaxConcurrentProposals: u32 = 8;
+}
+
 impl xpallet_gateway_bitcoin::Trait for Runtime {
     type Event = Event;
     type UnixTime = Timestamp;
@@ -938,12 +964,14 @@ impl xpallet_gateway_bitcoin::Trait for Runtime {
     type TrusteeOrigin = EnsureSignedBy<trustees::bitcoin::BtcTrusteeMultisig<Runtime>, AccountId>;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type MaxConcurrentProposals = MaxConcurrentProposals;
     type WeightInfo = xpallet_gateway_bitcoin::weights::SubstrateWeight<Runtime>;
 }
 
 impl xpallet_dex_spot::Trait for Runtime {
     type Event = Event;
     type Price = Balance;
+    type OnOrderChanged = XMiningAsset;
     type WeightInfo = xpallet_dex_spot::weights::SubstrateWeight<Runtime>;
 }
 
@@ -974,9 +1002,17 @@ impl xpallet_mining_staking::Trait for Runtime {
     type AssetMining = XMiningAsset;
     type DetermineRewardPotAccount =
         xpallet_mining_staking::SimpleValidatorRewardPotAccountDeterminer<Runtime>;
+    type ElectionAuthorityId = ImOnlineId;
+    type ElectionAuthorities = Self;
     type WeightInfo = xpallet_mining_staking::weights::SubstrateWeight<Runtime>;
 }
 
+impl xpallet_mining_staking::ElectionAuthorities<ImOnlineId> for Runtime {
+    fn authorities() -> Vec<ImOnlineId> {
+        ImOnline::keys()
+    }
+}
+
 pub struct ReferralGetter;
 impl xpallet_mining_asset::GatewayInterface<AccountId> for ReferralGetter {
     fn referral_of(who: &AccountId, asset_id: AssetId) -> Option<AccountId> {
@@ -985,6 +1021,20 @@ impl xpallet_mining_asset::GatewayInterface<AccountId> for ReferralGetter {
     }
 }
 
+pub struct SpotPriceFeed;
+impl xpallet_mining_asset::PriceFeed for SpotPriceFeed {
+    fn native_price_of(asset_id: AssetId) -> Option<u128> {
+        XSpot::quote_value_in_base(xp_protocol::PCX, asset_id)
+    }
+}
+
+pub struct TimeWeightedPriceFeed;
+impl xpallet_transaction_fee::PriceFeed for TimeWeightedPriceFeed {
+    fn native_price_of(asset_id: AssetId) -> Option<u128> {
+        XMiningAsset::average_price_of(asset_id)
+    }
+}
+
 impl xpallet_mining_asset::Trait for Runtime {
     type Event = Event;
     type StakingInterface = Self;
@@ -992,6 +1042,8 @@ impl xpallet_mining_asset::Trait for Runtime {
     type TreasuryAccount = SimpleTreasuryAccount;
     type DetermineRewardPotAccount =
         xpallet_mining_asset::SimpleAssetRewardPotAccountDeterminer<Runtime>;
+    type PriceFeed = SpotPriceFeed;
+    type SpotInterface = Self;
     type WeightInfo = xpallet_mining_asset::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1044,7 +1096,7 @@ construct_runtime!(
         XAssets: xpallet_assets::{Module, Call, Storage, Event<T>, Config<T>} = 26,
 
         // Mining, must be after XAssets.
-        XStaking: xpallet_mining_staking::{Module, Call, Storage, Event<T>, Config<T>} = 27,
+        XStaking: xpallet_mining_staking::{Module, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 27,
         XMiningAsset: xpallet_mining_asset::{Module, Call, Storage, Event<T>, Config<T>} = 28,
 
         // Crypto gateway stuff.
@@ -1064,7 +1116,7 @@ construct_runtime!(
 
         // It might be possible to merge this module into pallet_transaction_payment in future, thus
         // we put it at the end for keeping the extrinsic ordering.
-        XTransactionFee: xpallet_transaction_fee::{Module, Event<T>} = 35,
+        XTransactionFee: xpallet_transaction_fee::{Module, Call, Storage, Event<T>} = 35,
 
         Proxy: pallet_proxy::{Module, Call, Storage, Event<T>} = 37,
     }
@@ -1088,7 +1140,7 @@ pub type SignedExtra = (
     frame_system::CheckEra<Runtime>,
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
-    pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    ChargeAssetTxPayment,
     BaseFilter,
     ChargeExtraFee,
 );
@@ -1290,21 +1342,11 @@ impl_runtime_apis! {
             uxt: <Block as BlockT>::Extrinsic,
             len: u32,
         ) -> xpallet_transaction_fee::FeeDetails<Balance> {
-            if let Some(extra_fee) = ChargeExtraFee::has_extra_fee(&uxt.function) {
-                let details = XTransactionFee::query_fee_details(uxt, len);
-                xpallet_transaction_fee::FeeDetails {
-                    extra_fee,
-                    final_fee: details.final_fee + extra_fee,
-                    ..details
-                }
-            } else {
-                XTransactionFee::query_fee_details(uxt, len)
-            }
-
+            impls::query_fee_details(uxt, len)
         }
     }
 
-    impl xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance> for Runtime {
+    impl xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance, BlockNumber> for Runtime {
         fn assets_for_account(who: AccountId) -> BTreeMap<AssetId, BTreeMap<AssetType, Balance>> {
             XAssets::valid_assets_of(&who)
         }
@@ -1312,6 +1354,18 @@ impl_runtime_apis! {
         fn assets() -> BTreeMap<AssetId, TotalAssetInfo<Balance>> {
             XAssets::total_asset_infos()
         }
+
+        fn vesting_schedules(who: AccountId) -> BTreeMap<AssetId, VestingSchedule<Balance, BlockNumber>> {
+            XAssets::vesting_schedules_of(&who)
+        }
+
+        fn allowances(owner: AccountId) -> BTreeMap<AssetId, BTreeMap<AccountId, Balance>> {
+            XAssets::allowances_of(&owner)
+        }
+
+        fn balance_at(snapshot_id: SnapshotId, who: AccountId) -> Option<Balance> {
+            XAssets::balance_at(snapshot_id, &who)
+        }
     }
 
     impl xpallet_mining_staking_rpc_runtime_api::XStakingApi<Block, AccountId, Balance, VoteWeight, BlockNumber> for Runtime {
@@ -1330,6 +1384,18 @@ impl_runtime_apis! {
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber> {
             XStaking::nominator_info_of(who)
         }
+
+        fn emission_projection(sessions: u32) -> Vec<(SessionIndex, Balance)> {
+            XStaking::emission_projection(sessions)
+        }
+
+        fn unapplied_slashes() -> Vec<(EraIndex, Vec<UnappliedSlash<AccountId, Balance>>)> {
+            XStaking::unapplied_slashes()
+        }
+
+        fn validator_history(who: AccountId, eras: u32) -> Vec<(EraIndex, ValidatorEraRecord<Balance>)> {
+            XStaking::validator_history(who, eras)
+        }
     }
 
     impl xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance> for Runtime {
@@ -1370,6 +1436,16 @@ impl_runtime_apis! {
         }
     }
 
+    impl xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, Balance> for Runtime {
+        fn custody_info() -> xpallet_gateway_bitcoin_rpc_runtime_api::BtcCustodyInfo<Balance> {
+            XGatewayBitcoin::custody_info()
+        }
+
+        fn proof_of_reserves() -> xpallet_gateway_bitcoin_rpc_runtime_api::BtcProofOfReserves<Balance> {
+            XGatewayBitcoin::proof_of_reserves()
+        }
+    }
+
     impl xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance> for Runtime {
         fn bound_addrs(who: AccountId) -> BTreeMap<Chain, Vec<ChainAddress>> {
             XGatewayCommon::bound_addrs(&who)
@@ -1441,6 +1517,7 @@ impl_runtime_apis! {
             add_benchmark!(params, batches, xpallet_gateway_common, XGatewayCommon);
             add_benchmark!(params, batches, xpallet_gateway_bitcoin, XGatewayBitcoin);
             add_benchmark!(params, batches, xpallet_dex_spot, XSpot);
+            add_benchmark!(params, batches, xpallet_transaction_fee, XTransactionFee);
 
             if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
             Ok(batches)
@@ -5,7 +5,7 @@
 use codec::{Decode, Encode};
 
 use sp_runtime::{
-    traits::{DispatchInfoOf, SignedExtension},
+    traits::{DispatchInfoOf, SignedExtension, StaticLookup},
     transaction_validity::{
         InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
     },
@@ -21,10 +21,11 @@ use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
 
 use xpallet_gateway_common::Call as XGatewayCommonCall;
 use xpallet_mining_staking::Call as XStakingCall;
+use xpallet_transaction_fee::{FeeDetails, FeeReceivers};
 
 use chainx_primitives::{AccountId, Balance};
 
-use crate::{Authorship, Balances, Call, Runtime};
+use crate::{Authorship, Balances, Call, Indices, Runtime, UncheckedExtrinsic, XTransactionFee};
 
 type NegativeImbalance = <Balances as Currency<AccountId>>::NegativeImbalance;
 
@@ -36,6 +37,15 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 }
 
 pub struct DealWithFees;
+
+impl FeeReceivers<AccountId> for DealWithFees {
+    fn author_and_reward_pot() -> (AccountId, AccountId) {
+        let author = <pallet_authorship::Module<Runtime>>::author();
+        let reward_pot = <xpallet_mining_staking::Module<Runtime>>::reward_pot_for(&author);
+        (author, reward_pot)
+    }
+}
+
 impl OnUnbalanced<NegativeImbalance> for DealWithFees {
     fn on_nonzero_unbalanced(fees: NegativeImbalance) {
         // for fees, 90% to the reward pot of author, 10% to author
@@ -44,8 +54,7 @@ impl OnUnbalanced<NegativeImbalance> for DealWithFees {
         let to_author_numeric_amount = to_author.peek();
         let to_reward_pot_numeric_amount = to_reward_pot.peek();
 
-        let author = <pallet_authorship::Module<Runtime>>::author();
-        let reward_pot = <xpallet_mining_staking::Module<Runtime>>::reward_pot_for(&author);
+        let (author, reward_pot) = Self::author_and_reward_pot();
 
         <pallet_balances::Module<Runtime>>::resolve_creating(&author, to_author);
         <pallet_balances::Module<Runtime>>::resolve_creating(&reward_pot, to_reward_pot);
@@ -138,3 +147,41 @@ impl SignedExtension for ChargeExtraFee {
         Ok(ValidTransaction::default())
     }
 }
+
+/// The transaction fee extension charging the fee in PCX or in a whitelisted asset.
+pub type ChargeAssetTxPayment = xpallet_transaction_fee::ChargeAssetTxPayment<Runtime>;
+
+/// Returns the details of the fee of `uxt`, including the extra fee, the fee measured by
+/// the fee asset and the sponsor paying the fee.
+pub fn query_fee_details(uxt: UncheckedExtrinsic, len: u32) -> FeeDetails<Balance> {
+    let signed = uxt
+        .signature
+        .as_ref()
+        .map(|(address, _, extra)| (address.clone(), extra.6.clone()));
+    let call = uxt.function.clone();
+
+    let details = if let Some(extra_fee) = ChargeExtraFee::has_extra_fee(&call) {
+        let details = XTransactionFee::query_fee_details(uxt, len);
+        FeeDetails {
+            extra_fee,
+            final_fee: details.final_fee + extra_fee,
+            ..details
+        }
+    } else {
+        XTransactionFee::query_fee_details(uxt, len)
+    };
+
+    match signed {
+        Some((address, charge)) => {
+            let sponsor = Indices::lookup(address).ok().and_then(|who| {
+                charge.sponsor_of(&who, &call, details.final_fee - details.extra_fee)
+            });
+            let details = FeeDetails { sponsor, ..details };
+            match charge.fee_asset() {
+                Some(asset_id) => XTransactionFee::with_asset_fee(details, asset_id),
+                None => details,
+            }
+        }
+        None => details,
+    }
+}
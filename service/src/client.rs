@@ -19,8 +19,9 @@ pub trait RuntimeApiCollection:
     + sp_offchain::OffchainWorkerApi<Block>
     + sp_session::SessionKeys<Block>
     + sp_authority_discovery::AuthorityDiscoveryApi<Block>
-    + xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance>
+    + xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance, BlockNumber>
     + xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance>
+    + xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, Balance>
     + xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance>
     + xpallet_gateway_records_rpc_runtime_api::XGatewayRecordsApi<
         Block,
@@ -58,8 +59,9 @@ where
         + sp_offchain::OffchainWorkerApi<Block>
         + sp_session::SessionKeys<Block>
         + sp_authority_discovery::AuthorityDiscoveryApi<Block>
-        + xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance>
+        + xpallet_assets_rpc_runtime_api::XAssetsApi<Block, AccountId, Balance, BlockNumber>
         + xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance>
+        + xpallet_gateway_bitcoin_rpc_runtime_api::XGatewayBitcoinApi<Block, Balance>
         + xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance>
         + xpallet_gateway_records_rpc_runtime_api::XGatewayRecordsApi<
             Block,
@@ -1,6 +1,6 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
 
 use super::*;
@@ -18,6 +18,28 @@ fn b_asset_info_test_data<T: Trait>() -> AssetInfo {
     .unwrap()
 }
 
+fn b_permissionless_asset_info_test_data<T: Trait>() -> AssetInfo {
+    AssetInfo::new::<T>(
+        b"token".to_vec(),
+        b"token_name".to_vec(),
+        Chain::ChainX,
+        18,
+        b"token_desc".to_vec(),
+    )
+    .unwrap()
+}
+
+fn b_register_permissionless<T: Trait>() -> Result<AssetId, &'static str> {
+    let caller: T::AccountId = whitelisted_caller();
+    T::Currency::make_free_balance_be(&caller, T::RegistrationDeposit::get() * 10u32.into());
+    let asset_id = Module::<T>::next_permissionless_asset_id();
+    Module::<T>::register_permissionless(
+        RawOrigin::Signed(caller).into(),
+        b_permissionless_asset_info_test_data::<T>(),
+    )?;
+    Ok(asset_id)
+}
+
 benchmarks! {
     _{
         // User account seed
@@ -65,6 +87,59 @@ benchmarks! {
         new_asset_info.set_desc(b"new_desc".to_vec());
         assert_eq!(AssetInfoOf::get(ASSET_ID).unwrap(), new_asset_info);
     }
+
+    set_asset_roles {
+        let asset_info = b_asset_info_test_data::<T>();
+        Module::<T>::register(RawOrigin::Root.into(), ASSET_ID, asset_info, true, true)?;
+        let admin: T::AccountId = account("admin", 0, 0);
+        let roles = AssetRoles {
+            admin: admin.clone(),
+            issuer: admin.clone(),
+            freezer: admin,
+        };
+    }: _(RawOrigin::Root, ASSET_ID, Some(roles.clone()))
+    verify {
+        assert_eq!(AssetRolesOf::<T>::get(ASSET_ID), Some(roles));
+    }
+
+    register_permissionless {
+        let caller: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&caller, T::RegistrationDeposit::get() * 10u32.into());
+        let asset_info = b_permissionless_asset_info_test_data::<T>();
+        let asset_id = Module::<T>::next_permissionless_asset_id();
+    }: _(RawOrigin::Signed(caller.clone()), asset_info.clone())
+    verify {
+        assert_eq!(AssetInfoOf::get(asset_id), Some(asset_info));
+        assert_eq!(T::Currency::reserved_balance(&caller), T::RegistrationDeposit::get());
+    }
+
+    promote {
+        let asset_id = b_register_permissionless::<T>()?;
+    }: _(RawOrigin::Root, asset_id)
+    verify {
+        assert!(AssetDepositOf::<T>::get(asset_id).is_none());
+    }
+
+    slash_deposit {
+        let asset_id = b_register_permissionless::<T>()?;
+    }: _(RawOrigin::Root, asset_id)
+    verify {
+        assert!(AssetDepositOf::<T>::get(asset_id).is_none());
+        assert!(!AssetOnline::get(asset_id));
+    }
+
+    update_asset_metadata {
+        let asset_info = b_asset_info_test_data::<T>();
+        Module::<T>::register(RawOrigin::Root.into(), ASSET_ID, asset_info, true, true)?;
+        let metadata = AssetMetadata {
+            logo_uri: vec![b'a'; xp_protocol::ASSET_URI_MAX_LEN],
+            homepage: vec![b'a'; xp_protocol::ASSET_URI_MAX_LEN],
+            origin: Some(AssetOrigin::Other(vec![b'a'; xp_protocol::ASSET_ORIGIN_ID_MAX_LEN])),
+        };
+    }: _(RawOrigin::Root, ASSET_ID, metadata.clone())
+    verify {
+        assert_eq!(AssetMetadataOf::get(ASSET_ID), metadata);
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +155,11 @@ mod tests {
             assert_ok!(test_benchmark_deregister::<Test>());
             assert_ok!(test_benchmark_recover::<Test>());
             assert_ok!(test_benchmark_update_asset_info::<Test>());
+            assert_ok!(test_benchmark_set_asset_roles::<Test>());
+            assert_ok!(test_benchmark_register_permissionless::<Test>());
+            assert_ok!(test_benchmark_promote::<Test>());
+            assert_ok!(test_benchmark_slash_deposit::<Test>());
+            assert_ok!(test_benchmark_update_asset_metadata::<Test>());
         });
     }
 }
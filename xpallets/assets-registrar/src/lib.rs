@@ -10,6 +10,7 @@
 
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarking;
+mod migration;
 #[cfg(test)]
 mod tests;
 mod types;
@@ -22,18 +23,24 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    traits::Get,
+    traits::{Currency, Get, ReservableCurrency},
+    weights::Weight,
     IterableStorageMap,
 };
-use frame_system::ensure_root;
+use frame_system::{ensure_root, ensure_signed};
 
 use chainx_primitives::{AssetId, Desc, Token};
 use xp_logging::info;
+use xp_protocol::{EXTEND_END, EXTEND_PERMISSIONLESS, PERMISSIONLESS_ASSET_MAX_DECIMALS};
 
-pub use self::types::AssetInfo;
+use self::types::Releases;
+pub use self::types::{AssetDeposit, AssetInfo, AssetMetadata, AssetOrigin, AssetRoles};
 pub use self::weights::WeightInfo;
 pub use xp_assets_registrar::{Chain, RegistrarHandler};
 
+pub type BalanceOf<T> =
+    <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+
 /// The module's config trait.
 ///
 /// `frame_system::Trait` should always be included in our implied traits.
@@ -47,6 +54,12 @@ pub trait Trait: frame_system::Trait {
     /// Handler for doing stuff after the asset is registered/deregistered.
     type RegistrarHandler: RegistrarHandler;
 
+    /// The currency used for the deposit of the permissionless registration.
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// The deposit reserved for registering an asset permissionlessly.
+    type RegistrationDeposit: Get<BalanceOf<Self>>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -60,6 +73,14 @@ decl_event!(
         Recovered(AssetId, bool),
         /// An asset was deregistered. [asset_id]
         Deregistered(AssetId),
+        /// The roles of an asset were set or cleared. [asset_id]
+        RolesUpdated(AssetId),
+        /// A permissionless registered asset was promoted and its deposit was returned. [asset_id]
+        Promoted(AssetId),
+        /// The deposit of a permissionless registered asset was slashed. [asset_id]
+        DepositSlashed(AssetId),
+        /// The metadata of an asset was updated. [asset_id]
+        MetadataUpdated(AssetId),
     }
 );
 
@@ -84,6 +105,26 @@ decl_error! {
         AssetAlreadyValid,
         /// The asset is invalid (not online).
         AssetIsInvalid,
+        /// The sender is not the admin of the asset.
+        RequireAdmin,
+        /// The sender is not the issuer of the asset.
+        RequireIssuer,
+        /// The sender is not the freezer of the asset.
+        RequireFreezer,
+        /// Only the asset of Chain::ChainX can be registered permissionlessly.
+        RequireChainXAsset,
+        /// The decimals of a permissionless registered asset is too large.
+        InvalidAssetDecimals,
+        /// No available asset id for the permissionless registration.
+        AssetIdExhausted,
+        /// The asset has no deposit, i.e., not registered permissionlessly or already handled.
+        NoDeposit,
+        /// URI of the asset metadata is too long
+        InvalidAssetUriLength,
+        /// The origin chain identifier of the asset is invalid
+        InvalidAssetOrigin,
+        /// The token or token name is already used by another asset.
+        AssetTokenAlreadyUsed,
     }
 }
 
@@ -100,6 +141,27 @@ decl_storage! {
 
         /// The map of asset to the block number at which the asset was registered.
         pub RegisteredAt get(fn registered_at): map hasher(twox_64_concat) AssetId => T::BlockNumber;
+
+        /// The optional admin/issuer/freezer roles of each asset.
+        pub AssetRolesOf get(fn asset_roles_of):
+            map hasher(twox_64_concat) AssetId => Option<AssetRoles<T::AccountId>>;
+
+        /// The next asset id to try for the permissionless registration.
+        pub NextPermissionlessAssetId get(fn next_permissionless_asset_id): AssetId = EXTEND_PERMISSIONLESS;
+
+        /// The asset which uses the lowercased token or token name.
+        pub UsedTokens get(fn used_tokens): map hasher(blake2_128_concat) Token => Option<AssetId>;
+
+        /// The deposit of each permissionless registered asset which is not promoted yet.
+        pub AssetDepositOf get(fn asset_deposit_of):
+            map hasher(twox_64_concat) AssetId => Option<AssetDeposit<T::AccountId, BalanceOf<T>>>;
+
+        /// The extensible metadata of each asset.
+        pub AssetMetadataOf get(fn asset_metadata_of):
+            map hasher(twox_64_concat) AssetId => AssetMetadata;
+
+        /// Storage version of the pallet.
+        StorageVersion build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
     }
     add_extra_genesis {
         config(assets): Vec<(AssetId, AssetInfo, bool, bool)>;
@@ -124,6 +186,10 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            migration::on_runtime_upgrade::<T>()
+        }
+
         /// Register a new foreign asset.
         ///
         /// This is a root-only operation.
@@ -146,6 +212,9 @@ decl_module! {
             );
 
             Self::apply_register(asset_id, asset)?;
+            if asset_id == Self::next_permissionless_asset_id() {
+                Self::advance_permissionless_asset_id(asset_id);
+            }
 
             Self::deposit_event(Event::Registered(asset_id, has_mining_rights));
             T::RegistrarHandler::on_register(&asset_id, has_mining_rights)?;
@@ -209,6 +278,7 @@ decl_module! {
             ensure_root(origin)?;
 
             let mut info = Self::asset_info_of(&id).ok_or(Error::<T>::AssetDoesNotExist)?;
+            Self::remove_used_tokens(id, &info);
             if let Some(t) = token {
                 info.set_token(t)
             }
@@ -218,9 +288,134 @@ decl_module! {
             if let Some(desc) = desc {
                 info.set_desc(desc);
             }
+            Self::insert_used_tokens(id, &info);
             AssetInfoOf::insert(id, info);
             Ok(())
         }
+
+        /// Set or clear the admin/issuer/freezer roles of an asset.
+        ///
+        /// This could be done by root or the current admin of the asset.
+        #[weight = T::WeightInfo::set_asset_roles()]
+        pub fn set_asset_roles(
+            origin,
+            #[compact] id: AssetId,
+            roles: Option<AssetRoles<T::AccountId>>
+        ) -> DispatchResult {
+            if ensure_root(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                Self::ensure_admin(&id, &who)?;
+            }
+            Self::ensure_asset_exists(&id)?;
+
+            info!("[set_asset_roles] id:{}, roles:{:?}", id, roles);
+            match roles {
+                Some(roles) => AssetRolesOf::<T>::insert(id, roles),
+                None => AssetRolesOf::<T>::remove(id),
+            }
+
+            Self::deposit_event(Event::RolesUpdated(id));
+            Ok(())
+        }
+
+        /// Register a new asset of `Chain::ChainX` without governance.
+        ///
+        /// `RegistrationDeposit` is reserved from the sender, who also becomes the
+        /// admin, issuer and freezer of the asset. The asset id is allocated in the
+        /// `EXTEND` range and the asset has no mining rights.
+        #[weight = T::WeightInfo::register_permissionless()]
+        pub fn register_permissionless(origin, asset: AssetInfo) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(asset.chain() == Chain::ChainX, Error::<T>::RequireChainXAsset);
+            ensure!(
+                asset.decimals() <= PERMISSIONLESS_ASSET_MAX_DECIMALS,
+                Error::<T>::InvalidAssetDecimals
+            );
+            asset.is_valid::<T>()?;
+            Self::ensure_unused_token(&asset)?;
+
+            let asset_id = Self::allocate_permissionless_asset_id()?;
+            let amount = T::RegistrationDeposit::get();
+            T::Currency::reserve(&who, amount)?;
+
+            info!(
+                "[register_permissionless] id:{}, info:{:?}, who:{:?}, deposit:{:?}",
+                asset_id, asset, who, amount
+            );
+
+            Self::apply_register(asset_id, asset)?;
+            Self::advance_permissionless_asset_id(asset_id);
+            AssetDepositOf::<T>::insert(asset_id, AssetDeposit { depositor: who.clone(), amount });
+            AssetRolesOf::<T>::insert(
+                asset_id,
+                AssetRoles { admin: who.clone(), issuer: who.clone(), freezer: who },
+            );
+
+            Self::deposit_event(Event::Registered(asset_id, false));
+            T::RegistrarHandler::on_register(&asset_id, false)?;
+
+            Ok(())
+        }
+
+        /// Promote a permissionless registered asset, the deposit is returned to
+        /// the depositor.
+        ///
+        /// This is a root-only operation.
+        #[weight = T::WeightInfo::promote()]
+        pub fn promote(origin, #[compact] id: AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let deposit = Self::asset_deposit_of(&id).ok_or(Error::<T>::NoDeposit)?;
+            T::Currency::unreserve(&deposit.depositor, deposit.amount);
+            AssetDepositOf::<T>::remove(id);
+
+            Self::deposit_event(Event::Promoted(id));
+            Ok(())
+        }
+
+        /// Slash the deposit of an abusive permissionless registered asset, the
+        /// asset is deregistered as well if it's still valid.
+        ///
+        /// This is a root-only operation.
+        #[weight = T::WeightInfo::slash_deposit()]
+        pub fn slash_deposit(origin, #[compact] id: AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let deposit = Self::asset_deposit_of(&id).ok_or(Error::<T>::NoDeposit)?;
+            let _ = T::Currency::slash_reserved(&deposit.depositor, deposit.amount);
+            AssetDepositOf::<T>::remove(id);
+            Self::deposit_event(Event::DepositSlashed(id));
+
+            if Self::is_valid(&id) {
+                Self::deregister(frame_system::RawOrigin::Root.into(), id)?;
+            }
+            Ok(())
+        }
+
+        /// Update the metadata of an asset, e.g., the logo URI, homepage and the
+        /// identifier on its origin chain.
+        ///
+        /// This could be done by root or the admin of the asset.
+        #[weight = T::WeightInfo::update_asset_metadata()]
+        pub fn update_asset_metadata(
+            origin,
+            #[compact] id: AssetId,
+            metadata: AssetMetadata
+        ) -> DispatchResult {
+            if ensure_root(origin.clone()).is_err() {
+                let who = ensure_signed(origin)?;
+                Self::ensure_admin(&id, &who)?;
+            }
+            Self::ensure_asset_exists(&id)?;
+            metadata.is_valid::<T>()?;
+
+            info!("[update_asset_metadata] id:{}, metadata:{:?}", id, metadata);
+            AssetMetadataOf::insert(id, metadata);
+
+            Self::deposit_event(Event::MetadataUpdated(id));
+            Ok(())
+        }
     }
 }
 
@@ -296,6 +491,76 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Helper function for checking whether `who` is the admin of the asset.
+    pub fn ensure_admin(id: &AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(
+            Self::asset_roles_of(id).map_or(false, |roles| roles.admin == *who),
+            Error::<T>::RequireAdmin
+        );
+        Ok(())
+    }
+
+    /// Helper function for checking whether `who` is the issuer of the asset.
+    pub fn ensure_issuer(id: &AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(
+            Self::asset_roles_of(id).map_or(false, |roles| roles.issuer == *who),
+            Error::<T>::RequireIssuer
+        );
+        Ok(())
+    }
+
+    /// Helper function for checking whether `who` is the freezer of the asset.
+    pub fn ensure_freezer(id: &AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(
+            Self::asset_roles_of(id).map_or(false, |roles| roles.freezer == *who),
+            Error::<T>::RequireFreezer
+        );
+        Ok(())
+    }
+
+    /// Ensures neither the token nor the token name of `asset` is used by the registered
+    /// assets, ignoring the ASCII case, so that the existing assets can't be impersonated.
+    fn ensure_unused_token(asset: &AssetInfo) -> DispatchResult {
+        let is_used = UsedTokens::contains_key(asset.token().to_ascii_lowercase())
+            || UsedTokens::contains_key(asset.token_name().to_ascii_lowercase());
+        ensure!(!is_used, Error::<T>::AssetTokenAlreadyUsed);
+        Ok(())
+    }
+
+    /// Marks the token and token name of asset `id` as used.
+    pub(crate) fn insert_used_tokens(id: AssetId, asset: &AssetInfo) {
+        UsedTokens::insert(asset.token().to_ascii_lowercase(), id);
+        UsedTokens::insert(asset.token_name().to_ascii_lowercase(), id);
+    }
+
+    /// Releases the token and token name of asset `id`, unless they are used by another asset.
+    fn remove_used_tokens(id: AssetId, asset: &AssetInfo) {
+        for token in &[asset.token(), asset.token_name()] {
+            let key = token.to_ascii_lowercase();
+            if UsedTokens::get(&key) == Some(id) {
+                UsedTokens::remove(key);
+            }
+        }
+    }
+
+    /// Returns the next asset id for the permissionless registration.
+    fn allocate_permissionless_asset_id() -> Result<AssetId, DispatchError> {
+        let id = Self::next_permissionless_asset_id();
+        ensure!(id < EXTEND_END, Error::<T>::AssetIdExhausted);
+        ensure!(!Self::exists(&id), Error::<T>::AssetAlreadyExists);
+        Ok(id)
+    }
+
+    /// Moves `NextPermissionlessAssetId` past `id` and the following ids registered by root,
+    /// each of which is skipped only once.
+    pub(crate) fn advance_permissionless_asset_id(id: AssetId) {
+        let mut next = id + 1;
+        while next < EXTEND_END && Self::exists(&next) {
+            next += 1;
+        }
+        NextPermissionlessAssetId::put(next);
+    }
+
     /// Actually register an asset.
     fn apply_register(id: AssetId, asset: AssetInfo) -> DispatchResult {
         let chain = asset.chain();
@@ -305,6 +570,7 @@ impl<T: Trait> Module<T> {
             }
         });
 
+        Self::insert_used_tokens(id, &asset);
         AssetInfoOf::insert(&id, asset);
         AssetOnline::insert(&id, true);
 
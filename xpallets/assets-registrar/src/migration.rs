@@ -0,0 +1,57 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{traits::Get, weights::Weight, IterableStorageMap, StorageMap, StorageValue};
+
+use chainx_primitives::AssetId;
+use xp_logging::info;
+use xp_protocol::{X_BTC, X_DOT, X_ETH};
+
+use crate::types::{AssetMetadata, AssetOrigin, Releases};
+use crate::{
+    AssetInfoOf, AssetMetadataOf, Module, NextPermissionlessAssetId, StorageVersion, Trait,
+};
+
+/// The base tokens which are native on their origin chains, see `xp_protocol::asset`.
+const NATIVE_ASSETS: [AssetId; 3] = [X_BTC, X_ETH, X_DOT];
+
+/// Apply all of the migrations due to `StorageVersion`.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    match StorageVersion::get() {
+        Releases::V2_0_0 => 0,
+        Releases::V1_0_0 => migrate_to_v2::<T>(),
+    }
+}
+
+/// Initialize the metadata and the used tokens of the existing assets.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+    let mut count: Weight = 0;
+    for (id, info) in AssetInfoOf::iter() {
+        count += 1;
+        Module::<T>::insert_used_tokens(id, &info);
+        if AssetMetadataOf::contains_key(id) {
+            continue;
+        }
+        let origin = if NATIVE_ASSETS.contains(&id) {
+            Some(AssetOrigin::Native)
+        } else {
+            None
+        };
+        AssetMetadataOf::insert(
+            id,
+            AssetMetadata {
+                origin,
+                ..Default::default()
+            },
+        );
+    }
+    let next = NextPermissionlessAssetId::get();
+    if Module::<T>::exists(&next) {
+        Module::<T>::advance_permissionless_asset_id(next);
+    }
+    StorageVersion::put(Releases::V2_0_0);
+    info!(
+        "[migrate_to_v2] initialized the metadata of {} assets",
+        count
+    );
+    T::DbWeight::get().reads_writes(count * 2 + 2, count * 3 + 2)
+}
@@ -2,7 +2,7 @@
 
 use frame_support::{
     assert_noop, assert_ok, impl_outer_event, impl_outer_origin, parameter_types, sp_io,
-    weights::Weight,
+    traits::OnRuntimeUpgrade, weights::Weight, StorageMap, StorageValue,
 };
 use sp_core::H256;
 use sp_runtime::{
@@ -18,6 +18,7 @@ use crate::*;
 
 /// The AccountId alias in this test module.
 pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
 
 impl_outer_origin! {
     pub enum Origin for Test {}
@@ -71,20 +72,37 @@ impl frame_system::Trait for Test {
     type AvailableBlockRatio = AvailableBlockRatio;
     type Version = ();
     type PalletInfo = ();
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
 }
 
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const RegistrationDeposit: Balance = 100;
 }
 
 impl Trait for Test {
     type Event = MetaEvent;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
     type WeightInfo = ();
 }
 
@@ -115,6 +133,11 @@ impl ExtBuilder {
             .build_storage::<Test>()
             .unwrap();
 
+        let _ = pallet_balances::GenesisConfig::<Test> {
+            balances: vec![(1, 1000), (2, 1000), (3, 50)],
+        }
+        .assimilate_storage(&mut storage);
+
         let _ = GenesisConfig { assets }.assimilate_storage::<Test>(&mut storage);
 
         let ext = sp_io::TestExternalities::new(storage);
@@ -131,6 +154,7 @@ impl ExtBuilder {
 }
 
 pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
 pub type XAssetsRegistrar = Module<Test>;
 pub type Err = Error<Test>;
 
@@ -185,3 +209,370 @@ fn test_register() {
         );
     })
 }
+
+#[test]
+fn test_set_asset_roles() {
+    ExtBuilder::default().build_and_execute(|| {
+        let roles = AssetRoles {
+            admin: 1,
+            issuer: 2,
+            freezer: 3,
+        };
+        assert_noop!(
+            XAssetsRegistrar::set_asset_roles(Origin::signed(1), X_BTC, Some(roles.clone())),
+            Err::RequireAdmin
+        );
+        assert_noop!(
+            XAssetsRegistrar::set_asset_roles(Origin::root(), 10000, Some(roles.clone())),
+            Err::AssetDoesNotExist
+        );
+        assert_ok!(XAssetsRegistrar::set_asset_roles(
+            Origin::root(),
+            X_BTC,
+            Some(roles.clone())
+        ));
+        assert_eq!(XAssetsRegistrar::asset_roles_of(X_BTC), Some(roles));
+        assert_ok!(XAssetsRegistrar::ensure_issuer(&X_BTC, &2));
+        assert_ok!(XAssetsRegistrar::ensure_freezer(&X_BTC, &3));
+        assert_noop!(
+            XAssetsRegistrar::ensure_issuer(&X_BTC, &1),
+            Err::RequireIssuer
+        );
+
+        // the admin could transfer the roles
+        let new_roles = AssetRoles {
+            admin: 4,
+            issuer: 4,
+            freezer: 4,
+        };
+        assert_noop!(
+            XAssetsRegistrar::set_asset_roles(Origin::signed(2), X_BTC, Some(new_roles.clone())),
+            Err::RequireAdmin
+        );
+        assert_ok!(XAssetsRegistrar::set_asset_roles(
+            Origin::signed(1),
+            X_BTC,
+            Some(new_roles.clone())
+        ));
+        assert_eq!(XAssetsRegistrar::asset_roles_of(X_BTC), Some(new_roles));
+
+        assert_ok!(XAssetsRegistrar::set_asset_roles(
+            Origin::signed(4),
+            X_BTC,
+            None
+        ));
+        assert_eq!(XAssetsRegistrar::asset_roles_of(X_BTC), None);
+        assert_noop!(
+            XAssetsRegistrar::ensure_freezer(&X_BTC, &4),
+            Err::RequireFreezer
+        );
+    })
+}
+
+fn permissionless_asset(token: &[u8], decimals: u8) -> AssetInfo {
+    AssetInfo::new::<Test>(
+        token.to_vec(),
+        [token, &b" Token"[..]].concat(),
+        Chain::ChainX,
+        decimals,
+        b"abc".to_vec(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_register_permissionless() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssetsRegistrar::register_permissionless(Origin::signed(1), btc().1),
+            Err::RequireChainXAsset
+        );
+        assert_noop!(
+            XAssetsRegistrar::register_permissionless(
+                Origin::signed(1),
+                permissionless_asset(b"ABC", 19)
+            ),
+            Err::InvalidAssetDecimals
+        );
+        assert_noop!(
+            XAssetsRegistrar::register_permissionless(
+                Origin::signed(3),
+                permissionless_asset(b"ABC", 18)
+            ),
+            pallet_balances::Error::<Test, _>::InsufficientBalance
+        );
+
+        let id = xp_protocol::EXTEND_PERMISSIONLESS;
+        assert_ok!(XAssetsRegistrar::register_permissionless(
+            Origin::signed(1),
+            permissionless_asset(b"ABC", 18)
+        ));
+        assert_eq!(XAssetsRegistrar::asset_ids_of(Chain::ChainX), vec![id]);
+        assert!(XAssetsRegistrar::is_valid(&id));
+        assert_eq!(Balances::reserved_balance(1), 100);
+        assert_eq!(
+            XAssetsRegistrar::asset_deposit_of(id),
+            Some(AssetDeposit {
+                depositor: 1,
+                amount: 100
+            })
+        );
+        assert_ok!(XAssetsRegistrar::ensure_issuer(&id, &1));
+
+        // the id registered by root is skipped
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            id + 1,
+            permissionless_asset(b"ABC", 8),
+            true,
+            false
+        ));
+        assert_ok!(XAssetsRegistrar::register_permissionless(
+            Origin::signed(2),
+            permissionless_asset(b"DEF", 8)
+        ));
+        assert!(XAssetsRegistrar::asset_deposit_of(id + 2).is_some());
+        assert_eq!(XAssetsRegistrar::next_permissionless_asset_id(), id + 3);
+
+        // the next id is advanced as soon as root registers it
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            id + 4,
+            permissionless_asset(b"GHI", 8),
+            true,
+            false
+        ));
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            id + 3,
+            permissionless_asset(b"JKL", 8),
+            true,
+            false
+        ));
+        assert_eq!(XAssetsRegistrar::next_permissionless_asset_id(), id + 5);
+    })
+}
+
+#[test]
+fn test_register_permissionless_with_used_token() {
+    ExtBuilder::default().build_and_execute(|| {
+        let impersonate = |token: &[u8], token_name: &[u8]| {
+            let asset = AssetInfo::new::<Test>(
+                token.to_vec(),
+                token_name.to_vec(),
+                Chain::ChainX,
+                8,
+                b"fake".to_vec(),
+            )
+            .unwrap();
+            XAssetsRegistrar::register_permissionless(Origin::signed(1), asset)
+        };
+
+        let btc = btc().1;
+        assert_noop!(
+            impersonate(btc.token(), b"Fake Token"),
+            Err::AssetTokenAlreadyUsed
+        );
+        assert_noop!(
+            impersonate(&btc.token().to_ascii_lowercase(), b"Fake Token"),
+            Err::AssetTokenAlreadyUsed
+        );
+        assert_noop!(
+            impersonate(b"FAKE", btc.token_name()),
+            Err::AssetTokenAlreadyUsed
+        );
+
+        assert_ok!(XAssetsRegistrar::register_permissionless(
+            Origin::signed(1),
+            permissionless_asset(b"ABC", 8)
+        ));
+        assert_noop!(
+            XAssetsRegistrar::register_permissionless(
+                Origin::signed(2),
+                permissionless_asset(b"ABC", 8)
+            ),
+            Err::AssetTokenAlreadyUsed
+        );
+
+        // the renamed token is released
+        assert_eq!(
+            XAssetsRegistrar::used_tokens(b"abc".to_vec()),
+            Some(xp_protocol::EXTEND_PERMISSIONLESS)
+        );
+        assert_ok!(XAssetsRegistrar::update_asset_info(
+            Origin::root(),
+            xp_protocol::EXTEND_PERMISSIONLESS,
+            Some(b"XYZ".to_vec()),
+            Some(b"XYZ Token".to_vec()),
+            None
+        ));
+        assert_eq!(XAssetsRegistrar::used_tokens(b"abc".to_vec()), None);
+        assert_noop!(
+            impersonate(b"xyz", b"Fake Token"),
+            Err::AssetTokenAlreadyUsed
+        );
+        assert_ok!(XAssetsRegistrar::register_permissionless(
+            Origin::signed(2),
+            permissionless_asset(b"ABC", 8)
+        ));
+    })
+}
+
+#[test]
+fn test_promote_and_slash_deposit() {
+    ExtBuilder::default().build_and_execute(|| {
+        let id = xp_protocol::EXTEND_PERMISSIONLESS;
+        assert_ok!(XAssetsRegistrar::register_permissionless(
+            Origin::signed(1),
+            permissionless_asset(b"GHI", 8)
+        ));
+        assert_ok!(XAssetsRegistrar::register_permissionless(
+            Origin::signed(2),
+            permissionless_asset(b"ABC", 8)
+        ));
+
+        assert_noop!(
+            XAssetsRegistrar::promote(Origin::signed(1), id),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XAssetsRegistrar::promote(Origin::root(), X_BTC),
+            Err::NoDeposit
+        );
+
+        assert_ok!(XAssetsRegistrar::promote(Origin::root(), id));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::free_balance(1), 1000);
+        assert!(XAssetsRegistrar::is_valid(&id));
+        assert_noop!(
+            XAssetsRegistrar::slash_deposit(Origin::root(), id),
+            Err::NoDeposit
+        );
+
+        assert_ok!(XAssetsRegistrar::slash_deposit(Origin::root(), id + 1));
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::free_balance(2), 900);
+        assert!(!XAssetsRegistrar::is_valid(&(id + 1)));
+        assert!(XAssetsRegistrar::asset_deposit_of(id + 1).is_none());
+    })
+}
+
+#[test]
+fn test_update_asset_metadata() {
+    ExtBuilder::default().build_and_execute(|| {
+        let metadata = AssetMetadata {
+            logo_uri: b"https://chainx.org/logo/xbtc.svg".to_vec(),
+            homepage: b"https://chainx.org".to_vec(),
+            origin: Some(AssetOrigin::Native),
+        };
+        assert_noop!(
+            XAssetsRegistrar::update_asset_metadata(Origin::signed(1), X_BTC, metadata.clone()),
+            Err::RequireAdmin
+        );
+        assert_noop!(
+            XAssetsRegistrar::update_asset_metadata(Origin::root(), 10000, metadata.clone()),
+            Err::AssetDoesNotExist
+        );
+        assert_ok!(XAssetsRegistrar::update_asset_metadata(
+            Origin::root(),
+            X_BTC,
+            metadata.clone()
+        ));
+        assert_eq!(XAssetsRegistrar::asset_metadata_of(X_BTC), metadata);
+
+        let invalid = |metadata: AssetMetadata| {
+            XAssetsRegistrar::update_asset_metadata(Origin::root(), X_BTC, metadata)
+        };
+        assert_noop!(
+            invalid(AssetMetadata {
+                logo_uri: vec![b'a'; xp_protocol::ASSET_URI_MAX_LEN + 1],
+                ..Default::default()
+            }),
+            Err::InvalidAssetUriLength
+        );
+        assert_noop!(
+            invalid(AssetMetadata {
+                homepage: b"https://chainx.org/\n".to_vec(),
+                ..Default::default()
+            }),
+            Err::InvalidAscii
+        );
+        assert_noop!(
+            invalid(AssetMetadata {
+                origin: Some(AssetOrigin::Erc20(vec![0; 19])),
+                ..Default::default()
+            }),
+            Err::InvalidAssetOrigin
+        );
+        assert_noop!(
+            invalid(AssetMetadata {
+                origin: Some(AssetOrigin::Other(vec![])),
+                ..Default::default()
+            }),
+            Err::InvalidAssetOrigin
+        );
+
+        // the admin of the asset could update the metadata as well
+        assert_ok!(XAssetsRegistrar::set_asset_roles(
+            Origin::root(),
+            X_BTC,
+            Some(AssetRoles {
+                admin: 1,
+                issuer: 1,
+                freezer: 1,
+            })
+        ));
+        assert_ok!(XAssetsRegistrar::update_asset_metadata(
+            Origin::signed(1),
+            X_BTC,
+            AssetMetadata {
+                origin: Some(AssetOrigin::Erc20(vec![0; 20])),
+                ..Default::default()
+            }
+        ));
+    })
+}
+
+#[test]
+fn test_migrate_to_v2() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+        let usdt = xp_protocol::USDT;
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            usdt,
+            AssetInfo::new::<Test>(
+                b"USDT".to_vec(),
+                b"Tether USD".to_vec(),
+                Chain::Ethereum,
+                6,
+                b"USDT".to_vec(),
+            )
+            .unwrap(),
+            true,
+            false
+        ));
+
+        // pretend the metadata does not exist before the upgrade.
+        AssetMetadataOf::remove(X_BTC);
+        AssetMetadataOf::remove(usdt);
+        UsedTokens::remove(b"usdt".to_vec());
+        UsedTokens::remove(b"tether usd".to_vec());
+        StorageVersion::put(Releases::V1_0_0);
+
+        XAssetsRegistrar::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+        assert!(AssetMetadataOf::contains_key(X_BTC));
+        assert_eq!(
+            XAssetsRegistrar::asset_metadata_of(X_BTC).origin,
+            Some(AssetOrigin::Native)
+        );
+        assert!(AssetMetadataOf::contains_key(usdt));
+        assert_eq!(XAssetsRegistrar::asset_metadata_of(usdt).origin, None);
+        assert_eq!(XAssetsRegistrar::used_tokens(b"usdt".to_vec()), Some(usdt));
+        assert_eq!(
+            XAssetsRegistrar::used_tokens(b"tether usd".to_vec()),
+            Some(usdt)
+        );
+    })
+}
@@ -5,13 +5,14 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use frame_support::dispatch::{DispatchError, DispatchResult};
-use sp_std::fmt;
+use sp_runtime::RuntimeDebug;
+use sp_std::{fmt, prelude::*};
 
 use chainx_primitives::{Decimals, Desc, Token};
 use xp_assets_registrar::Chain;
 
 use crate::verifier::*;
-use crate::Trait;
+use crate::{Error, Trait};
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -101,3 +102,83 @@ impl AssetInfo {
         self.token_name = token_name
     }
 }
+
+/// The optional privileged roles of an asset.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetRoles<AccountId> {
+    /// Account which is able to change the roles of the asset.
+    pub admin: AccountId,
+    /// Account which is able to mint and burn the asset.
+    pub issuer: AccountId,
+    /// Account which is able to freeze and thaw the asset of an account.
+    pub freezer: AccountId,
+}
+
+/// The deposit reserved for a permissionless registered asset.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetDeposit<AccountId, Balance> {
+    /// Account which registered the asset.
+    pub depositor: AccountId,
+    /// The reserved deposit.
+    pub amount: Balance,
+}
+
+/// The identifier of an asset on its origin chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum AssetOrigin {
+    /// The native token of the origin chain, e.g., BTC of Bitcoin.
+    Native,
+    /// An ERC20 token, identified by its 20 bytes contract address.
+    Erc20(#[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_hex"))] Vec<u8>),
+    /// An asset of the Polkadot ecosystem, identified by its asset id.
+    PolkadotAsset(u32),
+    /// Any other identifier on the origin chain.
+    Other(#[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))] Vec<u8>),
+}
+
+/// The extensible metadata of an asset, mainly for the wallets.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetMetadata {
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
+    pub logo_uri: Vec<u8>,
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
+    pub homepage: Vec<u8>,
+    pub origin: Option<AssetOrigin>,
+}
+
+impl AssetMetadata {
+    pub fn is_valid<T: Trait>(&self) -> DispatchResult {
+        is_valid_uri::<T>(&self.logo_uri)?;
+        is_valid_uri::<T>(&self.homepage)?;
+        match &self.origin {
+            Some(AssetOrigin::Erc20(address)) if address.len() != 20 => {
+                Err(Error::<T>::InvalidAssetOrigin.into())
+            }
+            Some(AssetOrigin::Other(id)) => is_valid_origin_id::<T>(id),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The storage version of this pallet.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    /// `AssetInfo` only.
+    V1_0_0,
+    /// `AssetMetadata` is added.
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
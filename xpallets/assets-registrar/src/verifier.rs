@@ -1,6 +1,9 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use xp_protocol::{ASSET_DESC_MAX_LEN, ASSET_TOKEN_NAME_MAX_LEN, ASSET_TOKEN_SYMBOL_MAX_LEN};
+use xp_protocol::{
+    ASSET_DESC_MAX_LEN, ASSET_ORIGIN_ID_MAX_LEN, ASSET_TOKEN_NAME_MAX_LEN,
+    ASSET_TOKEN_SYMBOL_MAX_LEN, ASSET_URI_MAX_LEN,
+};
 
 use super::*;
 
@@ -46,6 +49,33 @@ pub fn is_valid_desc<T: Trait>(desc: &[u8]) -> DispatchResult {
     Ok(())
 }
 
+/// A valid URI should be visible ASCII chars only and not too long, empty means unset.
+pub fn is_valid_uri<T: Trait>(uri: &[u8]) -> DispatchResult {
+    if uri.len() > ASSET_URI_MAX_LEN {
+        return Err(Error::<T>::InvalidAssetUriLength.into());
+    }
+    xp_runtime::xss_check(uri)?;
+    for c in uri {
+        if !is_ascii_visible(c) {
+            return Err(Error::<T>::InvalidAscii.into());
+        }
+    }
+    Ok(())
+}
+
+/// A valid origin chain identifier should be visible ASCII chars only and not too long.
+pub fn is_valid_origin_id<T: Trait>(id: &[u8]) -> DispatchResult {
+    if id.len() > ASSET_ORIGIN_ID_MAX_LEN || id.is_empty() {
+        return Err(Error::<T>::InvalidAssetOrigin.into());
+    }
+    for c in id {
+        if !is_ascii_visible(c) {
+            return Err(Error::<T>::InvalidAscii.into());
+        }
+    }
+    Ok(())
+}
+
 /// Visible ASCII char [0x20, 0x7E]
 #[inline]
 fn is_ascii_visible(c: &u8) -> bool {
@@ -8,16 +8,26 @@ use sp_std::collections::btree_map::BTreeMap;
 use codec::Codec;
 
 pub use chainx_primitives::AssetId;
-pub use xpallet_assets::{AssetType, TotalAssetInfo};
+pub use xpallet_assets::{AssetType, SnapshotId, TotalAssetInfo, VestingSchedule};
 
 sp_api::decl_runtime_apis! {
-    pub trait XAssetsApi<AccountId, Balance>
+    /// Version 2: `metadata` is added to `TotalAssetInfo`, `BlockNumber` is added for
+    /// `vesting_schedules`, and `allowances` and `balance_at` are added.
+    #[api_version(2)]
+    pub trait XAssetsApi<AccountId, Balance, BlockNumber>
     where
         AccountId: Codec,
         Balance: Codec,
+        BlockNumber: Codec,
     {
         fn assets_for_account(who: AccountId) -> BTreeMap<AssetId, BTreeMap<AssetType, Balance>>;
 
         fn assets() -> BTreeMap<AssetId, TotalAssetInfo<Balance>>;
+
+        fn vesting_schedules(who: AccountId) -> BTreeMap<AssetId, VestingSchedule<Balance, BlockNumber>>;
+
+        fn allowances(owner: AccountId) -> BTreeMap<AssetId, BTreeMap<AccountId, Balance>>;
+
+        fn balance_at(snapshot_id: SnapshotId, who: AccountId) -> Option<Balance>;
     }
 }
@@ -8,17 +8,18 @@ use std::sync::Arc;
 use codec::Codec;
 use jsonrpc_derive::rpc;
 
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{
     generic::BlockId,
     traits::{Block as BlockT, Zero},
 };
 
-use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance};
+use xp_rpc::{runtime_error_into_rpc_err, Error, ErrorCode, Result, RpcBalance, RUNTIME_ERROR};
 
 use xpallet_assets_rpc_runtime_api::{
-    AssetId, AssetType, TotalAssetInfo, XAssetsApi as XAssetsRuntimeApi,
+    AssetId, AssetType, SnapshotId, TotalAssetInfo, VestingSchedule,
+    XAssetsApi as XAssetsRuntimeApi,
 };
 
 pub struct Assets<C, B> {
@@ -37,7 +38,7 @@ impl<C, B> Assets<C, B> {
 }
 
 #[rpc]
-pub trait XAssetsApi<BlockHash, AccountId, Balance>
+pub trait XAssetsApi<BlockHash, AccountId, Balance, BlockNumber>
 where
     Balance: Display + FromStr,
 {
@@ -49,22 +50,48 @@ where
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<AssetId, BTreeMap<AssetType, RpcBalance<Balance>>>>;
 
-    /// Return all valid assets balance with AssetTypes. (exclude native token(PCX))
+    /// Return all valid assets balance with AssetTypes and the asset metadata. (exclude native token(PCX))
     #[rpc(name = "xassets_getAssets")]
     fn assets(
         &self,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<AssetId, TotalAssetInfo<RpcBalance<Balance>>>>;
+
+    /// Return all the vesting schedules of an account.
+    #[rpc(name = "xassets_getVestingSchedules")]
+    fn vesting_schedules(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<AssetId, VestingSchedule<RpcBalance<Balance>, BlockNumber>>>;
+
+    /// Return all the allowances approved by an account, asset id => (spender => allowance).
+    #[rpc(name = "xassets_getAllowances")]
+    fn allowances(
+        &self,
+        owner: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<AssetId, BTreeMap<AccountId, RpcBalance<Balance>>>>;
+
+    /// Return the total balance of an account at the given snapshot, null if the snapshot does not exist.
+    #[rpc(name = "xassets_getBalanceAt")]
+    fn balance_at(
+        &self,
+        snapshot_id: SnapshotId,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcBalance<Balance>>>;
 }
 
-impl<C, Block, AccountId, Balance> XAssetsApi<<Block as BlockT>::Hash, AccountId, Balance>
-    for Assets<C, Block>
+impl<C, Block, AccountId, Balance, BlockNumber>
+    XAssetsApi<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber> for Assets<C, Block>
 where
     C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-    C::Api: XAssetsRuntimeApi<Block, AccountId, Balance>,
+    C::Api: XAssetsRuntimeApi<Block, AccountId, Balance, BlockNumber>,
     Block: BlockT,
-    AccountId: Clone + Display + Codec,
+    AccountId: Clone + Display + Codec + Ord,
     Balance: Clone + Copy + Display + FromStr + Codec + Zero,
+    BlockNumber: Clone + Display + Codec,
 {
     fn assets_by_account(
         &self,
@@ -96,6 +123,19 @@ where
     ) -> Result<BTreeMap<AssetId, TotalAssetInfo<RpcBalance<Balance>>>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        // `TotalAssetInfo` of the other versions can not be decoded.
+        let supported = api
+            .has_api::<dyn XAssetsRuntimeApi<Block, AccountId, Balance, BlockNumber>>(&at)
+            .map_err(runtime_error_into_rpc_err)?;
+        if !supported {
+            return Err(Error {
+                code: ErrorCode::ServerError(RUNTIME_ERROR),
+                message: "Unsupported version of the assets runtime api.".into(),
+                data: None,
+            });
+        }
+
         api.assets(&at)
             .map(|map| {
                 map.into_iter()
@@ -119,6 +159,7 @@ where
                                 balance,
                                 is_online: info.is_online,
                                 restrictions: info.restrictions,
+                                metadata: info.metadata,
                             },
                         )
                     })
@@ -126,4 +167,62 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn vesting_schedules(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AssetId, VestingSchedule<RpcBalance<Balance>, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.vesting_schedules(&at, who)
+            .map(|map| {
+                map.into_iter()
+                    .map(|(id, schedule)| {
+                        let schedule = VestingSchedule {
+                            locked: schedule.locked.into(),
+                            per_block: schedule.per_block.into(),
+                            starting_block: schedule.starting_block,
+                        };
+                        (id, schedule)
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn allowances(
+        &self,
+        owner: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<AssetId, BTreeMap<AccountId, RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.allowances(&at, owner)
+            .map(|map| {
+                map.into_iter()
+                    .map(|(id, allowances)| {
+                        let allowances = allowances
+                            .into_iter()
+                            .map(|(spender, allowance)| (spender, allowance.into()))
+                            .collect();
+                        (id, allowances)
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn balance_at(
+        &self,
+        snapshot_id: SnapshotId,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.balance_at(&at, snapshot_id, who)
+            .map(|balance| balance.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
@@ -0,0 +1,99 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_runtime::traits::{CheckedAdd, CheckedSub, Zero};
+use sp_std::collections::btree_map::BTreeMap;
+
+use frame_support::{dispatch::DispatchResult, IterableStorageDoubleMap};
+
+use chainx_primitives::AssetId;
+use xp_logging::debug;
+
+use crate::{Allowances, BalanceOf, Error, Event, Module, Trait};
+
+impl<T: Trait> Module<T> {
+    /// Returns all the allowances approved by `owner`, asset id => (spender => allowance).
+    pub fn allowances_of(
+        owner: &T::AccountId,
+    ) -> BTreeMap<AssetId, BTreeMap<T::AccountId, BalanceOf<T>>> {
+        let mut allowances = BTreeMap::<AssetId, BTreeMap<T::AccountId, BalanceOf<T>>>::new();
+        for ((spender, id), allowance) in Allowances::<T>::iter_prefix(owner) {
+            allowances.entry(id).or_default().insert(spender, allowance);
+        }
+        allowances
+    }
+
+    pub(crate) fn approve_impl(
+        id: &AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::ensure_not_native_asset(id)?;
+        xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(id)?;
+        Self::set_allowance(id, owner, spender, value);
+        Ok(())
+    }
+
+    pub(crate) fn increase_allowance_impl(
+        id: &AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        let new_allowance = Self::allowance(owner, (spender, id))
+            .checked_add(&value)
+            .ok_or(Error::<T>::Overflow)?;
+        Self::approve_impl(id, owner, spender, new_allowance)
+    }
+
+    pub(crate) fn decrease_allowance_impl(
+        id: &AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        let new_allowance = Self::allowance(owner, (spender, id))
+            .checked_sub(&value)
+            .ok_or(Error::<T>::InsufficientAllowance)?;
+        Self::approve_impl(id, owner, spender, new_allowance)
+    }
+
+    pub(crate) fn transfer_from_impl(
+        id: &AssetId,
+        spender: &T::AccountId,
+        owner: &T::AccountId,
+        dest: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        let new_allowance = Self::allowance(owner, (spender, id))
+            .checked_sub(&value)
+            .ok_or(Error::<T>::InsufficientAllowance)?;
+
+        Self::move_usable_balance(id, owner, dest, value).map_err::<Error<T>, _>(Into::into)?;
+        Self::set_allowance(id, owner, spender, new_allowance);
+        Ok(())
+    }
+
+    fn set_allowance(
+        id: &AssetId,
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        allowance: BalanceOf<T>,
+    ) {
+        debug!(
+            "[set_allowance] id:{}, owner:{:?}, spender:{:?}, allowance:{:?}",
+            id, owner, spender, allowance
+        );
+        if allowance.is_zero() {
+            Allowances::<T>::remove(owner, (spender, id));
+        } else {
+            Allowances::<T>::insert(owner, (spender, id), allowance);
+        }
+        Self::deposit_event(Event::<T>::Approval(
+            *id,
+            owner.clone(),
+            spender.clone(),
+            allowance,
+        ));
+    }
+}
@@ -3,6 +3,7 @@
 use super::*;
 
 use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::StorageMap;
 use frame_system::RawOrigin;
 
 use crate::Module as XAssets;
@@ -10,6 +11,19 @@ use crate::Module as XAssets;
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
 const SEED: u32 = 0;
 
+fn set_roles<T: Trait>(who: &T::AccountId) -> DispatchResult {
+    let roles = xpallet_assets_registrar::AssetRoles {
+        admin: who.clone(),
+        issuer: who.clone(),
+        freezer: who.clone(),
+    };
+    xpallet_assets_registrar::Module::<T>::set_asset_roles(
+        RawOrigin::Root.into(),
+        ASSET_ID,
+        Some(roles),
+    )
+}
+
 benchmarks! {
     _{ }
 
@@ -62,6 +76,152 @@ benchmarks! {
     verify {
         assert_eq!(XAssets::<T>::asset_restrictions_of(&ASSET_ID), res);
     }
+
+    vested_transfer {
+        let caller = whitelisted_caller();
+        let transfer_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &caller, transfer_amount).unwrap();
+        MinVestedTransferOf::<T>::insert(ASSET_ID, BalanceOf::<T>::from(100000_u32));
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+        let schedule = VestingSchedule {
+            locked: transfer_amount,
+            per_block: 100000000.into(),
+            starting_block: 10.into(),
+        };
+    }: _(RawOrigin::Signed(caller.clone()), recipient_lookup, ASSET_ID, schedule)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), Zero::zero());
+        assert_eq!(XAssets::<T>::locked_balance(&recipient, &ASSET_ID), transfer_amount);
+        assert_eq!(XAssets::<T>::vesting(&recipient, &ASSET_ID), Some(schedule));
+    }
+
+    vest {
+        let caller = whitelisted_caller();
+        let transfer_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &caller, transfer_amount).unwrap();
+        MinVestedTransferOf::<T>::insert(ASSET_ID, BalanceOf::<T>::from(100000_u32));
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let schedule = VestingSchedule {
+            locked: transfer_amount,
+            per_block: 100000000.into(),
+            starting_block: 10.into(),
+        };
+        XAssets::<T>::vested_transfer_impl(&ASSET_ID, &caller, &recipient, schedule)?;
+        frame_system::Module::<T>::set_block_number(15.into());
+    }: _(RawOrigin::Signed(recipient.clone()), ASSET_ID)
+    verify {
+        let unvested: BalanceOf<T> = (100000000 * 5_u32).into();
+        assert_eq!(XAssets::<T>::locked_balance(&recipient, &ASSET_ID), unvested);
+    }
+
+    approve {
+        let caller = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let spender_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(spender.clone());
+        let allowance: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+    }: _(RawOrigin::Signed(caller.clone()), spender_lookup, ASSET_ID, allowance)
+    verify {
+        assert_eq!(XAssets::<T>::allowance(&caller, (&spender, &ASSET_ID)), allowance);
+    }
+
+    increase_allowance {
+        let caller = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let spender_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(spender.clone());
+        let allowance: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::approve_impl(&ASSET_ID, &caller, &spender, allowance)?;
+    }: _(RawOrigin::Signed(caller.clone()), spender_lookup, ASSET_ID, allowance)
+    verify {
+        assert_eq!(XAssets::<T>::allowance(&caller, (&spender, &ASSET_ID)), allowance + allowance);
+    }
+
+    decrease_allowance {
+        let caller = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let spender_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(spender.clone());
+        let allowance: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::approve_impl(&ASSET_ID, &caller, &spender, allowance + allowance)?;
+    }: _(RawOrigin::Signed(caller.clone()), spender_lookup, ASSET_ID, allowance)
+    verify {
+        assert_eq!(XAssets::<T>::allowance(&caller, (&spender, &ASSET_ID)), allowance);
+    }
+
+    transfer_from {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let owner_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(owner.clone());
+        let transfer_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &owner, transfer_amount).unwrap();
+
+        let caller = whitelisted_caller();
+        XAssets::<T>::approve_impl(&ASSET_ID, &owner, &caller, transfer_amount)?;
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+    }: _(RawOrigin::Signed(caller.clone()), owner_lookup, recipient_lookup, ASSET_ID, transfer_amount)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&owner, &ASSET_ID), Zero::zero());
+        assert_eq!(XAssets::<T>::usable_balance(&recipient, &ASSET_ID), transfer_amount);
+        assert_eq!(XAssets::<T>::allowance(&owner, (&caller, &ASSET_ID)), Zero::zero());
+    }
+
+    mint {
+        let caller = whitelisted_caller();
+        set_roles::<T>(&caller)?;
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+        let mint_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+    }: _(RawOrigin::Signed(caller), recipient_lookup, ASSET_ID, mint_amount)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&recipient, &ASSET_ID), mint_amount);
+    }
+
+    burn {
+        let caller = whitelisted_caller();
+        set_roles::<T>(&caller)?;
+
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+        let burn_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &user, burn_amount)?;
+    }: _(RawOrigin::Signed(caller), user_lookup, ASSET_ID, burn_amount)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&user, &ASSET_ID), Zero::zero());
+    }
+
+    freeze_account {
+        let caller = whitelisted_caller();
+        set_roles::<T>(&caller)?;
+
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+    }: _(RawOrigin::Signed(caller), user_lookup, ASSET_ID)
+    verify {
+        assert!(XAssets::<T>::is_frozen(&user, &ASSET_ID));
+    }
+
+    thaw_account {
+        let caller: T::AccountId = whitelisted_caller();
+        set_roles::<T>(&caller)?;
+
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+        XAssets::<T>::freeze_account(RawOrigin::Signed(caller.clone()).into(), user_lookup.clone(), ASSET_ID)?;
+    }: _(RawOrigin::Signed(caller), user_lookup, ASSET_ID)
+    verify {
+        assert!(!XAssets::<T>::is_frozen(&user, &ASSET_ID));
+    }
+
+    take_snapshot {
+        let name = vec![b'a'; SNAPSHOT_NAME_MAX_LEN];
+        let snapshot_id = XAssets::<T>::next_snapshot_id();
+    }: _(RawOrigin::Root, ASSET_ID, name)
+    verify {
+        assert_eq!(XAssets::<T>::latest_snapshot_of(ASSET_ID), Some(snapshot_id));
+    }
 }
 
 #[cfg(test)]
@@ -77,6 +237,17 @@ mod tests {
             assert_ok!(test_benchmark_force_transfer::<Test>());
             assert_ok!(test_benchmark_set_balance::<Test>());
             assert_ok!(test_benchmark_set_asset_limit::<Test>());
+            assert_ok!(test_benchmark_vested_transfer::<Test>());
+            assert_ok!(test_benchmark_vest::<Test>());
+            assert_ok!(test_benchmark_approve::<Test>());
+            assert_ok!(test_benchmark_increase_allowance::<Test>());
+            assert_ok!(test_benchmark_decrease_allowance::<Test>());
+            assert_ok!(test_benchmark_transfer_from::<Test>());
+            assert_ok!(test_benchmark_mint::<Test>());
+            assert_ok!(test_benchmark_burn::<Test>());
+            assert_ok!(test_benchmark_freeze_account::<Test>());
+            assert_ok!(test_benchmark_thaw_account::<Test>());
+            assert_ok!(test_benchmark_take_snapshot::<Test>());
         });
     }
 }
@@ -6,6 +6,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::type_complexity, clippy::transmute_ptr_to_ptr)]
 
+mod allowance;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 #[cfg(test)]
@@ -13,12 +14,22 @@ mod mock;
 #[cfg(test)]
 mod tests;
 #[cfg(test)]
+mod tests_allowance;
+#[cfg(test)]
 mod tests_multicurrency;
+#[cfg(test)]
+mod tests_roles;
+#[cfg(test)]
+mod tests_snapshot;
+#[cfg(test)]
+mod tests_vesting;
 
 mod multicurrency;
+mod snapshot;
 pub mod traits;
 mod trigger;
 pub mod types;
+mod vesting;
 pub mod weights;
 
 use sp_std::{
@@ -42,14 +53,17 @@ use sp_runtime::traits::{
 
 use chainx_primitives::AssetId;
 use xp_logging::{debug, error, info};
-pub use xpallet_assets_registrar::{AssetInfo, Chain};
+pub use xpallet_assets_registrar::{AssetInfo, AssetMetadata, Chain};
 use xpallet_support::traits::TreasuryAccount;
 
+pub use self::snapshot::SNAPSHOT_NAME_MAX_LEN;
 pub use self::traits::{ChainT, OnAssetChanged};
 use self::trigger::AssetChangedTrigger;
 pub use self::types::{
-    AssetErr, AssetRestrictions, AssetType, BalanceLock, TotalAssetInfo, WithdrawalLimit,
+    AssetErr, AssetRestrictions, AssetSnapshot, AssetType, BalanceLock, SnapshotId, TotalAssetInfo,
+    VestingSchedule, WithdrawalLimit,
 };
+pub use self::vesting::VESTING_ID;
 pub use self::weights::WeightInfo;
 
 pub type BalanceOf<T> =
@@ -82,6 +96,9 @@ pub trait Trait: xpallet_assets_registrar::Trait {
 
     type OnAssetChanged: OnAssetChanged<Self::AccountId, BalanceOf<Self>>;
 
+    /// The maximum number of blocks a vesting schedule can start after the transfer.
+    type MaxVestingDelay: Get<Self::BlockNumber>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -107,6 +124,22 @@ decl_error! {
         DenyNativeAsset,
         /// Action is not allowed.
         ActionNotAllowed,
+        /// The account given is not vesting the asset.
+        NotVesting,
+        /// An existing vesting schedule already exists for this account and asset.
+        ExistingVestingSchedule,
+        /// The vesting schedule locks nothing, never unlocks or starts too late.
+        InvalidVestingSchedule,
+        /// The amount transferred with the vesting schedule is below the minimum.
+        VestedTransferAmountLow,
+        /// The minimum vested transfer of the asset is not set yet.
+        NoMinVestedTransfer,
+        /// The allowance is too low to transfer or decrease.
+        InsufficientAllowance,
+        /// The asset of the account is frozen.
+        AccountFrozen,
+        /// The snapshot name is empty or too long.
+        InvalidSnapshotName,
     }
 }
 
@@ -124,6 +157,20 @@ decl_event!(
         Destroyed(AssetId, AccountId, Balance),
         /// Set asset balance of an account by root. [asset_id, who, asset_type, amount]
         BalanceSet(AssetId, AccountId, AssetType, Balance),
+        /// Some balances of an asset were transferred with a vesting schedule. [asset_id, from, to, locked]
+        VestedTransfer(AssetId, AccountId, AccountId, Balance),
+        /// The vesting lock of an account was updated. [asset_id, who, unvested]
+        VestingUpdated(AssetId, AccountId, Balance),
+        /// An account has become fully vested. [asset_id, who]
+        VestingCompleted(AssetId, AccountId),
+        /// The allowance of a spender for an owner was set. [asset_id, owner, spender, allowance]
+        Approval(AssetId, AccountId, AccountId, Balance),
+        /// The asset of an account was frozen by the freezer. [asset_id, who]
+        Frozen(AssetId, AccountId),
+        /// The asset of an account was thawed by the freezer. [asset_id, who]
+        Thawed(AssetId, AccountId),
+        /// A snapshot of the balances of an asset was taken. [snapshot_id, asset_id]
+        SnapshotTaken(SnapshotId, AssetId),
     }
 );
 
@@ -149,6 +196,44 @@ decl_storage! {
         /// asset balance for an asset_id, use btree_map to accept different asset type
         pub TotalAssetBalance get(fn total_asset_balance):
             map hasher(twox_64_concat) AssetId => BTreeMap<AssetType, BalanceOf<T>>;
+
+        /// Vesting schedule of an asset under an account.
+        pub Vesting get(fn vesting):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) AssetId
+            => Option<VestingSchedule<BalanceOf<T>, T::BlockNumber>>;
+
+        /// The minimum amount of an asset transferred with a vesting schedule, the vested
+        /// transfer of an asset is not allowed until its minimum is set.
+        pub MinVestedTransferOf get(fn min_vested_transfer_of):
+            map hasher(twox_64_concat) AssetId => Option<BalanceOf<T>>;
+
+        /// The balance of an asset `spender` is allowed to transfer on behalf of `owner`,
+        /// owner => (spender, asset_id) => allowance.
+        pub Allowances get(fn allowance):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) (T::AccountId, AssetId)
+            => BalanceOf<T>;
+
+        /// The accounts whose asset is frozen by the freezer of the asset, the frozen
+        /// balance can not be moved out.
+        pub FrozenAccounts get(fn is_frozen):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) AssetId
+            => bool;
+
+        /// The id of the next balance snapshot.
+        pub NextSnapshotId get(fn next_snapshot_id): SnapshotId;
+
+        /// The balance snapshots taken so far.
+        pub Snapshots get(fn snapshots):
+            map hasher(twox_64_concat) SnapshotId => Option<AssetSnapshot<BalanceOf<T>, T::BlockNumber>>;
+
+        /// The latest snapshot of each asset.
+        pub LatestSnapshotOf get(fn latest_snapshot_of): map hasher(twox_64_concat) AssetId => Option<SnapshotId>;
+
+        /// The balances of an account recorded on the first modification after each
+        /// snapshot, sorted by the snapshot id.
+        pub AccountSnapshots get(fn account_snapshots):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) AssetId
+            => Vec<(SnapshotId, BalanceOf<T>)>;
     }
     add_extra_genesis {
         config(assets_restrictions): Vec<(AssetId, AssetRestrictions)>;
@@ -174,6 +259,9 @@ decl_storage! {
 
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        /// The maximum number of blocks a vesting schedule can start after the transfer.
+        const MaxVestingDelay: T::BlockNumber = T::MaxVestingDelay::get();
+
         type Error = Error<T>;
 
         fn deposit_event() = default;
@@ -236,6 +324,185 @@ decl_module! {
             ensure_root(origin)?;
             Self::set_asset_restrictions(id, restrictions)
         }
+
+        /// Transfer `schedule.locked` to `dest`, which is locked and then released
+        /// linearly according to the vesting schedule.
+        #[weight = <T as Trait>::WeightInfo::vested_transfer()]
+        pub fn vested_transfer(
+            origin,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            schedule: VestingSchedule<BalanceOf<T>, T::BlockNumber>
+        ) -> DispatchResult {
+            let transactor = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+            debug!("[vested_transfer] from:{:?}, to:{:?}, id:{}, schedule:{:?}", transactor, dest, id, schedule);
+            Self::can_transfer(&id)?;
+            Self::vested_transfer_impl(&id, &transactor, &dest, schedule)
+        }
+
+        /// Set the minimum amount of an asset transferred with a vesting schedule.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_asset_limit()]
+        pub fn set_min_vested_transfer(
+            origin,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_exists(&id)?;
+            MinVestedTransferOf::<T>::insert(id, value);
+            Ok(())
+        }
+
+        /// Unlock the vested balance of an asset for the sender.
+        #[weight = <T as Trait>::WeightInfo::vest()]
+        pub fn vest(origin, #[compact] id: AssetId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::vest_impl(&id, &who)
+        }
+
+        /// Allow `spender` to transfer at most `value` of the sender's asset, the
+        /// previous allowance is overwritten.
+        #[weight = <T as Trait>::WeightInfo::approve()]
+        pub fn approve(
+            origin,
+            spender: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            Self::approve_impl(&id, &owner, &spender, value)
+        }
+
+        /// Increase the allowance of `spender` by `value`.
+        #[weight = <T as Trait>::WeightInfo::increase_allowance()]
+        pub fn increase_allowance(
+            origin,
+            spender: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            Self::increase_allowance_impl(&id, &owner, &spender, value)
+        }
+
+        /// Decrease the allowance of `spender` by `value`.
+        #[weight = <T as Trait>::WeightInfo::decrease_allowance()]
+        pub fn decrease_allowance(
+            origin,
+            spender: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            Self::decrease_allowance_impl(&id, &owner, &spender, value)
+        }
+
+        /// Transfer `value` of `owner`'s asset to `dest` within the allowance approved
+        /// to the sender.
+        #[weight = <T as Trait>::WeightInfo::transfer_from()]
+        pub fn transfer_from(
+            origin,
+            owner: <T::Lookup as StaticLookup>::Source,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let spender = ensure_signed(origin)?;
+            let owner = T::Lookup::lookup(owner)?;
+            let dest = T::Lookup::lookup(dest)?;
+            debug!("[transfer_from] spender:{:?}, from:{:?}, to:{:?}, id:{}, value:{:?}", spender, owner, dest, id, value);
+            Self::can_transfer(&id)?;
+            Self::transfer_from_impl(&id, &spender, &owner, &dest, value)
+        }
+
+        /// Mint `value` of the asset to `beneficiary`.
+        ///
+        /// This is only allowed for the issuer of the asset.
+        #[weight = <T as Trait>::WeightInfo::mint()]
+        pub fn mint(
+            origin,
+            beneficiary: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_issuer(&id, &issuer)?;
+            let beneficiary = T::Lookup::lookup(beneficiary)?;
+            info!("[mint] issuer:{:?}, beneficiary:{:?}, id:{}, value:{:?}", issuer, beneficiary, id, value);
+            Self::issue(&id, &beneficiary, value)
+        }
+
+        /// Burn `value` of the usable asset of `who`.
+        ///
+        /// This is only allowed for the issuer of the asset.
+        #[weight = <T as Trait>::WeightInfo::burn()]
+        pub fn burn(
+            origin,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let issuer = ensure_signed(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_issuer(&id, &issuer)?;
+            let who = T::Lookup::lookup(who)?;
+            info!("[burn] issuer:{:?}, who:{:?}, id:{}, value:{:?}", issuer, who, id, value);
+            Self::ensure_not_native_asset(&id)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(&id)?;
+            Self::inner_destroy(&id, &who, AssetType::Usable, value)
+        }
+
+        /// Freeze the asset of `who`, which can not be moved out until it's thawed.
+        ///
+        /// This is only allowed for the freezer of the asset.
+        #[weight = <T as Trait>::WeightInfo::freeze_account()]
+        pub fn freeze_account(
+            origin,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId
+        ) -> DispatchResult {
+            let freezer = ensure_signed(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_exists(&id)?;
+            xpallet_assets_registrar::Module::<T>::ensure_freezer(&id, &freezer)?;
+            let who = T::Lookup::lookup(who)?;
+            info!("[freeze_account] freezer:{:?}, who:{:?}, id:{}", freezer, who, id);
+            FrozenAccounts::<T>::insert(&who, id, true);
+            Self::deposit_event(Event::<T>::Frozen(id, who));
+            Ok(())
+        }
+
+        /// Thaw the frozen asset of `who`.
+        ///
+        /// This is only allowed for the freezer of the asset.
+        #[weight = <T as Trait>::WeightInfo::thaw_account()]
+        pub fn thaw_account(
+            origin,
+            who: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId
+        ) -> DispatchResult {
+            let freezer = ensure_signed(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_freezer(&id, &freezer)?;
+            let who = T::Lookup::lookup(who)?;
+            info!("[thaw_account] freezer:{:?}, who:{:?}, id:{}", freezer, who, id);
+            FrozenAccounts::<T>::remove(&who, id);
+            Self::deposit_event(Event::<T>::Thawed(id, who));
+            Ok(())
+        }
+
+        /// Take a named snapshot of the balances of an asset, the balance of any
+        /// account at this moment could be queried later by `balance_at`.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::take_snapshot()]
+        pub fn take_snapshot(origin, #[compact] id: AssetId, name: Vec<u8>) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::take_snapshot_impl(id, name)
+        }
     }
 }
 
@@ -277,6 +544,7 @@ impl<T: Trait> Module<T> {
                             balance: Self::total_asset_balance(id),
                             is_online: xpallet_assets_registrar::Module::<T>::is_online(&id),
                             restrictions: Self::asset_restrictions_of(id),
+                            metadata: xpallet_assets_registrar::Module::<T>::asset_metadata_of(id),
                         },
                     );
                     Some(data)
@@ -419,6 +687,18 @@ impl<T: Trait> Module<T> {
         xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(id)
             .map_err(|_| AssetErr::InvalidAsset)?;
         Self::can_move(id).map_err(|_| AssetErr::NotAllow)?;
+        // The frozen balance can neither leave the account nor be spent from
+        // `Usable`, but it could still be released back to `Usable`. The balance
+        // reserved by the existing spot orders is delivered as usual, otherwise
+        // the orders of the frozen account would block the trading pair.
+        let is_spot_delivery =
+            from_type == AssetType::ReservedDexSpot && to_type == AssetType::Usable;
+        if Self::is_frozen(from, id)
+            && !is_spot_delivery
+            && (from != to || from_type == AssetType::Usable)
+        {
+            return Err(AssetErr::Frozen);
+        }
 
         if value == Zero::zero() {
             // value is zero, do not read storage, no event
@@ -516,6 +796,8 @@ impl<T: Trait> Module<T> {
         type_: AssetType,
         new_balance: BalanceOf<T>,
     ) {
+        Self::record_snapshot_balance(who, id);
+
         let mut original: BalanceOf<T> = Zero::zero();
         // todo change to try_mutate when update to rc5
         let existed = AssetBalance::<T>::contains_key(who, id);
@@ -608,49 +890,52 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
-    fn update_locks(currency_id: AssetId, who: &T::AccountId, locks: &[BalanceLock<BalanceOf<T>>]) {
-        // update locked balance
-        if let Some(max_locked) = locks.iter().map(|lock| lock.amount).max() {
-            use sp_std::cmp::Ordering;
-            let current_locked = Self::asset_balance_of(who, &currency_id, AssetType::Locked);
-
-            let result = match max_locked.cmp(&current_locked) {
-                Ordering::Greater => {
-                    // new lock more than current locked, move usable to locked
-                    Self::move_balance(
-                        &currency_id,
-                        who,
-                        AssetType::Usable,
-                        who,
-                        AssetType::Locked,
-                        max_locked - current_locked,
-                    )
-                }
-                Ordering::Less => {
-                    // new lock less then current locked, release locked to usable
-                    Self::move_balance(
-                        &currency_id,
-                        who,
-                        AssetType::Locked,
-                        who,
-                        AssetType::Usable,
-                        current_locked - max_locked,
-                    )
-                }
-                Ordering::Equal => {
-                    // if max_locked == locked, need do nothing
-                    Ok(())
-                }
-            };
-            if let Err(err) = result {
-                // should not fail, for set lock need to check free_balance, free_balance = usable + free
-                error!(
-                    "[update_locks] Should not be failed when move asset (usable <=> locked), \
-                    who:{:?}, asset:[id:{}, max_locked:{:?}, current_locked:{:?}], err:{:?}",
-                    who, currency_id, max_locked, current_locked, err
-                );
+    /// Moves the balance between `Usable` and `Locked` according to `locks`, the locks
+    /// are only updated if the balance has been moved successfully.
+    pub(crate) fn update_locks(
+        currency_id: AssetId,
+        who: &T::AccountId,
+        locks: &[BalanceLock<BalanceOf<T>>],
+    ) -> DispatchResult {
+        use sp_std::cmp::Ordering;
+
+        // update locked balance, release all the locked balance if there is no lock any more
+        let max_locked = locks
+            .iter()
+            .map(|lock| lock.amount)
+            .max()
+            .unwrap_or_else(Zero::zero);
+        let current_locked = Self::asset_balance_of(who, &currency_id, AssetType::Locked);
+
+        match max_locked.cmp(&current_locked) {
+            Ordering::Greater => {
+                // new lock more than current locked, move usable to locked
+                Self::move_balance(
+                    &currency_id,
+                    who,
+                    AssetType::Usable,
+                    who,
+                    AssetType::Locked,
+                    max_locked - current_locked,
+                )
+            }
+            Ordering::Less => {
+                // new lock less then current locked, release locked to usable
+                Self::move_balance(
+                    &currency_id,
+                    who,
+                    AssetType::Locked,
+                    who,
+                    AssetType::Usable,
+                    current_locked - max_locked,
+                )
+            }
+            Ordering::Equal => {
+                // if max_locked == locked, need do nothing
+                Ok(())
             }
         }
+        .map_err::<Error<T>, _>(Into::into)?;
 
         // update locks
         if locks.is_empty() {
@@ -658,5 +943,6 @@ impl<T: Trait> Module<T> {
         } else {
             <Locks<T>>::insert(who, currency_id, locks);
         }
+        Ok(())
     }
 }
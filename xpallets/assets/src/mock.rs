@@ -9,7 +9,9 @@ use sp_runtime::{
     Perbill,
 };
 
-use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, sp_io, weights::Weight};
+use frame_support::{
+    impl_outer_event, impl_outer_origin, parameter_types, sp_io, weights::Weight, StorageMap,
+};
 
 use chainx_primitives::AssetId;
 pub use xp_protocol::X_BTC;
@@ -97,12 +99,16 @@ impl pallet_balances::Trait for Test {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const RegistrationDeposit: Balance = 100;
+    pub const MaxVestingDelay: u64 = 100;
 }
 
 impl xpallet_assets_registrar::Trait for Test {
     type Event = MetaEvent;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
     type WeightInfo = ();
 }
 
@@ -113,6 +119,7 @@ impl Trait for Test {
     type TreasuryAccount = ();
     type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
     type OnAssetChanged = ();
+    type MaxVestingDelay = MaxVestingDelay;
     type WeightInfo = ();
 }
 
@@ -181,7 +188,10 @@ impl ExtBuilder {
     }
     pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
         let mut ext = self.build_default();
-        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            MinVestedTransferOf::<Test>::insert(X_BTC, 10);
+        });
         ext.execute_with(test);
     }
 
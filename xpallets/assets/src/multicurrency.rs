@@ -319,17 +319,17 @@ impl<T: Trait> MultiReservableCurrency<T::AccountId> for Module<T> {
     }
 }
 
-impl<T: Trait> MultiLockableCurrency<T::AccountId> for Module<T> {
-    type Moment = T::BlockNumber;
-
-    fn set_lock(
+impl<T: Trait> Module<T> {
+    /// Sets the lock `lock_id` of `who`, the same as `MultiLockableCurrency::set_lock`
+    /// except that the error of moving the balance to `Locked` is returned.
+    pub fn try_set_lock(
         lock_id: LockIdentifier,
-        currency_id: Self::CurrencyId,
+        currency_id: AssetId,
         who: &T::AccountId,
-        amount: Self::Balance,
-    ) {
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
         if amount.is_zero() {
-            return;
+            return Ok(());
         }
         let mut new_lock = Some(BalanceLock {
             id: lock_id,
@@ -348,17 +348,19 @@ impl<T: Trait> MultiLockableCurrency<T::AccountId> for Module<T> {
         if let Some(lock) = new_lock {
             locks.push(lock)
         }
-        Self::update_locks(currency_id, who, &locks[..]);
+        Self::update_locks(currency_id, who, &locks[..])
     }
 
-    fn extend_lock(
+    /// Extends the lock `lock_id` of `who`, the same as `MultiLockableCurrency::extend_lock`
+    /// except that the error of moving the balance to `Locked` is returned.
+    pub fn try_extend_lock(
         lock_id: LockIdentifier,
-        currency_id: Self::CurrencyId,
+        currency_id: AssetId,
         who: &T::AccountId,
-        amount: Self::Balance,
-    ) {
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
         if amount.is_zero() {
-            return;
+            return Ok(());
         }
         let mut new_lock = Some(BalanceLock {
             id: lock_id,
@@ -380,12 +382,59 @@ impl<T: Trait> MultiLockableCurrency<T::AccountId> for Module<T> {
         if let Some(lock) = new_lock {
             locks.push(lock)
         }
-        Self::update_locks(currency_id, who, &locks[..]);
+        Self::update_locks(currency_id, who, &locks[..])
     }
 
-    fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) {
+    /// Removes the lock `lock_id` of `who`, the same as `MultiLockableCurrency::remove_lock`
+    /// except that the error of moving the balance back to `Usable` is returned.
+    pub fn try_remove_lock(
+        lock_id: LockIdentifier,
+        currency_id: AssetId,
+        who: &T::AccountId,
+    ) -> DispatchResult {
         let mut locks = Self::locks(who, currency_id);
         locks.retain(|lock| lock.id != lock_id);
-        Self::update_locks(currency_id, who, &locks[..]);
+        Self::update_locks(currency_id, who, &locks[..])
+    }
+}
+
+impl<T: Trait> MultiLockableCurrency<T::AccountId> for Module<T> {
+    type Moment = T::BlockNumber;
+
+    fn set_lock(
+        lock_id: LockIdentifier,
+        currency_id: Self::CurrencyId,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) {
+        if let Err(err) = Self::try_set_lock(lock_id, currency_id, who, amount) {
+            error!(
+                "[set_lock] Failed to lock the asset, who:{:?}, id:{}, amount:{:?}, err:{:?}",
+                who, currency_id, amount, err
+            );
+        }
+    }
+
+    fn extend_lock(
+        lock_id: LockIdentifier,
+        currency_id: Self::CurrencyId,
+        who: &T::AccountId,
+        amount: Self::Balance,
+    ) {
+        if let Err(err) = Self::try_extend_lock(lock_id, currency_id, who, amount) {
+            error!(
+                "[extend_lock] Failed to lock the asset, who:{:?}, id:{}, amount:{:?}, err:{:?}",
+                who, currency_id, amount, err
+            );
+        }
+    }
+
+    fn remove_lock(lock_id: LockIdentifier, currency_id: Self::CurrencyId, who: &T::AccountId) {
+        if let Err(err) = Self::try_remove_lock(lock_id, currency_id, who) {
+            error!(
+                "[remove_lock] Failed to unlock the asset, who:{:?}, id:{}, err:{:?}",
+                who, currency_id, err
+            );
+        }
     }
 }
@@ -0,0 +1,83 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_std::prelude::*;
+
+use frame_support::{dispatch::DispatchResult, ensure, StorageDoubleMap, StorageMap, StorageValue};
+
+use chainx_primitives::AssetId;
+use xp_logging::info;
+
+use crate::types::{AssetSnapshot, SnapshotId};
+use crate::{
+    AccountSnapshots, BalanceOf, Error, Event, LatestSnapshotOf, Module, NextSnapshotId, Snapshots,
+    Trait,
+};
+
+/// The maximum length of the name of a snapshot.
+pub const SNAPSHOT_NAME_MAX_LEN: usize = 32;
+
+impl<T: Trait> Module<T> {
+    /// Returns the total balance of `who` for the asset of `snapshot_id` at the
+    /// time the snapshot was taken, None if the snapshot does not exist.
+    ///
+    /// The balance is only recorded on the first modification after a snapshot,
+    /// so the balance at a snapshot is the one recorded at the earliest snapshot
+    /// since then, or the current balance if there is no modification at all.
+    pub fn balance_at(snapshot_id: SnapshotId, who: &T::AccountId) -> Option<BalanceOf<T>> {
+        let snapshot = Self::snapshots(snapshot_id)?;
+        let records = Self::account_snapshots(who, snapshot.asset_id);
+        let balance = match records.binary_search_by_key(&snapshot_id, |(id, _)| *id) {
+            Ok(index) => records[index].1,
+            Err(index) => match records.get(index) {
+                Some((_, balance)) => *balance,
+                None => Self::all_type_asset_balance(who, &snapshot.asset_id),
+            },
+        };
+        Some(balance)
+    }
+
+    pub(crate) fn take_snapshot_impl(id: AssetId, name: Vec<u8>) -> DispatchResult {
+        Self::ensure_not_native_asset(&id)?;
+        xpallet_assets_registrar::Module::<T>::ensure_asset_exists(&id)?;
+        ensure!(
+            !name.is_empty() && name.len() <= SNAPSHOT_NAME_MAX_LEN,
+            Error::<T>::InvalidSnapshotName
+        );
+
+        let snapshot_id = Self::next_snapshot_id();
+        let next_id = snapshot_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+
+        let snapshot = AssetSnapshot {
+            asset_id: id,
+            name,
+            block: frame_system::Module::<T>::block_number(),
+            total_issuance: Self::total_issuance(&id),
+        };
+        info!(
+            "[take_snapshot] snapshot_id:{}, snapshot:{:?}",
+            snapshot_id, snapshot
+        );
+
+        Snapshots::<T>::insert(snapshot_id, snapshot);
+        LatestSnapshotOf::insert(id, snapshot_id);
+        NextSnapshotId::put(next_id);
+
+        Self::deposit_event(Event::<T>::SnapshotTaken(snapshot_id, id));
+        Ok(())
+    }
+
+    /// Records the balance of `who` before it's modified for the first time
+    /// since the latest snapshot of the asset.
+    pub(crate) fn record_snapshot_balance(who: &T::AccountId, id: &AssetId) {
+        if let Some(snapshot_id) = Self::latest_snapshot_of(id) {
+            let mut records = Self::account_snapshots(who, id);
+            if records
+                .last()
+                .map_or(true, |(last_id, _)| *last_id < snapshot_id)
+            {
+                records.push((snapshot_id, Self::all_type_asset_balance(who, id)));
+                AccountSnapshots::<T>::insert(who, id, records);
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use crate::mock::*;
+use crate::*;
+
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn approve_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssets::approve(Origin::signed(ALICE), BOB, X_BTC, 50));
+        assert_eq!(XAssets::allowance(ALICE, (BOB, X_BTC)), 50);
+
+        let approval_event = MetaEvent::assets(RawEvent::Approval(X_BTC, ALICE, BOB, 50));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == approval_event));
+
+        // the previous allowance is overwritten
+        assert_ok!(XAssets::approve(Origin::signed(ALICE), BOB, X_BTC, 30));
+        assert_eq!(XAssets::allowance(ALICE, (BOB, X_BTC)), 30);
+
+        assert_ok!(XAssets::increase_allowance(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC,
+            20
+        ));
+        assert_eq!(XAssets::allowance(ALICE, (BOB, X_BTC)), 50);
+
+        assert_ok!(XAssets::decrease_allowance(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC,
+            10
+        ));
+        assert_eq!(XAssets::allowance(ALICE, (BOB, X_BTC)), 40);
+        assert_noop!(
+            XAssets::decrease_allowance(Origin::signed(ALICE), BOB, X_BTC, 41),
+            XAssetsErr::InsufficientAllowance
+        );
+
+        assert_ok!(XAssets::approve(Origin::signed(ALICE), CHARLIE, X_BTC, 10));
+        let mut expected = BTreeMap::new();
+        expected.insert(BOB, 40);
+        expected.insert(CHARLIE, 10);
+        assert_eq!(XAssets::allowances_of(&ALICE).get(&X_BTC), Some(&expected));
+
+        // the zero allowance is removed
+        assert_ok!(XAssets::decrease_allowance(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC,
+            40
+        ));
+        assert!(!Allowances::<Test>::contains_key(ALICE, (BOB, X_BTC)));
+        assert_eq!(XAssets::allowances_of(&ALICE).get(&X_BTC).unwrap().len(), 1);
+    });
+}
+
+#[test]
+fn approve_should_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssets::approve(Origin::signed(ALICE), BOB, 0, 50),
+            XAssetsErr::DenyNativeAsset
+        );
+        assert_noop!(
+            XAssets::approve(Origin::signed(ALICE), BOB, 100, 50),
+            xpallet_assets_registrar::Error::<Test>::AssetIsInvalid
+        );
+        assert_ok!(XAssets::approve(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC,
+            Balance::max_value()
+        ));
+        assert_noop!(
+            XAssets::increase_allowance(Origin::signed(ALICE), BOB, X_BTC, 1),
+            XAssetsErr::Overflow
+        );
+    });
+}
+
+#[test]
+fn transfer_from_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssets::approve(Origin::signed(ALICE), BOB, X_BTC, 60));
+        assert_ok!(XAssets::transfer_from(
+            Origin::signed(BOB),
+            ALICE,
+            CHARLIE,
+            X_BTC,
+            40
+        ));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 60);
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 200);
+        assert_eq!(XAssets::usable_balance(&CHARLIE, &X_BTC), 340);
+        assert_eq!(XAssets::allowance(ALICE, (BOB, X_BTC)), 20);
+
+        let approval_event = MetaEvent::assets(RawEvent::Approval(X_BTC, ALICE, BOB, 20));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == approval_event));
+
+        assert_noop!(
+            XAssets::transfer_from(Origin::signed(BOB), ALICE, CHARLIE, X_BTC, 21),
+            XAssetsErr::InsufficientAllowance
+        );
+        // only the spender could use the allowance
+        assert_noop!(
+            XAssets::transfer_from(Origin::signed(CHARLIE), ALICE, CHARLIE, X_BTC, 10),
+            XAssetsErr::InsufficientAllowance
+        );
+
+        assert_ok!(XAssets::transfer_from(
+            Origin::signed(BOB),
+            ALICE,
+            CHARLIE,
+            X_BTC,
+            20
+        ));
+        assert!(XAssets::allowances_of(&ALICE).is_empty());
+    });
+}
+
+#[test]
+fn transfer_from_should_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        // the allowance could exceed the balance of the owner
+        assert_ok!(XAssets::approve(Origin::signed(ALICE), BOB, X_BTC, 200));
+        assert_noop!(
+            XAssets::transfer_from(Origin::signed(BOB), ALICE, CHARLIE, X_BTC, 150),
+            XAssetsErr::InsufficientBalance
+        );
+
+        assert_ok!(XAssets::set_asset_limit(
+            Origin::root(),
+            X_BTC,
+            AssetRestrictions::TRANSFER
+        ));
+        assert_noop!(
+            XAssets::transfer_from(Origin::signed(BOB), ALICE, CHARLIE, X_BTC, 50),
+            XAssetsErr::ActionNotAllowed
+        );
+    });
+}
@@ -51,6 +51,11 @@ fn remove_lock_should_work() {
         assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 2);
         XAssets::remove_lock(ID_2, X_BTC, &ALICE);
         assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 1);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 10);
+        XAssets::remove_lock(ID_1, X_BTC, &ALICE);
+        assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 0);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 0);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100);
     });
 }
 
@@ -0,0 +1,253 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use crate::mock::*;
+use crate::*;
+
+use frame_support::{assert_noop, assert_ok};
+
+use xpallet_assets_registrar::AssetRoles;
+
+type XAssetsRegistrar = xpallet_assets_registrar::Module<Test>;
+type XAssetsRegistrarErr = xpallet_assets_registrar::Error<Test>;
+
+fn set_roles() {
+    assert_ok!(XAssetsRegistrar::set_asset_roles(
+        Origin::root(),
+        X_BTC,
+        Some(AssetRoles {
+            admin: ALICE,
+            issuer: BOB,
+            freezer: CHARLIE,
+        })
+    ));
+}
+
+#[test]
+fn mint_and_burn_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssets::mint(Origin::signed(BOB), DAVE, X_BTC, 100),
+            XAssetsRegistrarErr::RequireIssuer
+        );
+        set_roles();
+        assert_noop!(
+            XAssets::mint(Origin::signed(ALICE), DAVE, X_BTC, 100),
+            XAssetsRegistrarErr::RequireIssuer
+        );
+
+        assert_ok!(XAssets::mint(Origin::signed(BOB), DAVE, X_BTC, 100));
+        assert_eq!(XAssets::usable_balance(&DAVE, &X_BTC), 500);
+        assert_eq!(XAssets::total_issuance(&X_BTC), 1100);
+
+        assert_ok!(XAssets::burn(Origin::signed(BOB), DAVE, X_BTC, 200));
+        assert_eq!(XAssets::usable_balance(&DAVE, &X_BTC), 300);
+        assert_eq!(XAssets::total_issuance(&X_BTC), 900);
+        assert_noop!(
+            XAssets::burn(Origin::signed(BOB), DAVE, X_BTC, 301),
+            XAssetsErr::InsufficientBalance
+        );
+        assert_noop!(
+            XAssets::burn(Origin::signed(CHARLIE), DAVE, X_BTC, 100),
+            XAssetsRegistrarErr::RequireIssuer
+        );
+    });
+}
+
+#[test]
+fn freeze_and_thaw_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        set_roles();
+        assert_noop!(
+            XAssets::freeze_account(Origin::signed(BOB), ALICE, X_BTC),
+            XAssetsRegistrarErr::RequireFreezer
+        );
+        assert_ok!(XAssets::freeze_account(
+            Origin::signed(CHARLIE),
+            ALICE,
+            X_BTC
+        ));
+        assert!(XAssets::is_frozen(ALICE, X_BTC));
+
+        let frozen_event = MetaEvent::assets(RawEvent::Frozen(X_BTC, ALICE));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == frozen_event));
+
+        // the frozen balance can not be moved out
+        assert_noop!(
+            XAssets::transfer(Origin::signed(ALICE), BOB, X_BTC.into(), 10),
+            XAssetsErr::AccountFrozen
+        );
+        assert_eq!(
+            XAssets::move_balance(
+                &X_BTC,
+                &ALICE,
+                AssetType::Usable,
+                &ALICE,
+                AssetType::ReservedWithdrawal,
+                10
+            ),
+            Err(AssetErr::Frozen)
+        );
+        // but could still be received
+        assert_ok!(XAssets::transfer(
+            Origin::signed(BOB),
+            ALICE,
+            X_BTC.into(),
+            10
+        ));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 110);
+        // and burned by the issuer
+        assert_ok!(XAssets::burn(Origin::signed(BOB), ALICE, X_BTC, 10));
+
+        assert_ok!(XAssets::thaw_account(Origin::signed(CHARLIE), ALICE, X_BTC));
+        assert!(!XAssets::is_frozen(ALICE, X_BTC));
+        assert_ok!(XAssets::transfer(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC.into(),
+            10
+        ));
+    });
+}
+
+#[test]
+fn frozen_reserved_balance_could_be_released() {
+    ExtBuilder::default().build_and_execute(|| {
+        set_roles();
+        assert_ok!(XAssets::move_balance(
+            &X_BTC,
+            &ALICE,
+            AssetType::Usable,
+            &ALICE,
+            AssetType::ReservedWithdrawal,
+            50
+        ));
+        assert_ok!(XAssets::freeze_account(
+            Origin::signed(CHARLIE),
+            ALICE,
+            X_BTC
+        ));
+
+        assert_eq!(
+            XAssets::move_balance(
+                &X_BTC,
+                &ALICE,
+                AssetType::ReservedWithdrawal,
+                &BOB,
+                AssetType::Usable,
+                50
+            ),
+            Err(AssetErr::Frozen)
+        );
+        assert_ok!(XAssets::move_balance(
+            &X_BTC,
+            &ALICE,
+            AssetType::ReservedWithdrawal,
+            &ALICE,
+            AssetType::Usable,
+            50
+        ));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100);
+    });
+}
+
+#[test]
+fn freeze_nonexistent_asset_should_not_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        set_roles();
+        assert_noop!(
+            XAssets::freeze_account(Origin::signed(CHARLIE), ALICE, 9999),
+            XAssetsRegistrarErr::AssetDoesNotExist
+        );
+    });
+}
+
+#[test]
+fn frozen_spot_reserved_balance_could_be_delivered() {
+    ExtBuilder::default().build_and_execute(|| {
+        set_roles();
+        assert_ok!(XAssets::move_balance(
+            &X_BTC,
+            &ALICE,
+            AssetType::Usable,
+            &ALICE,
+            AssetType::ReservedDexSpot,
+            50
+        ));
+        assert_ok!(XAssets::freeze_account(
+            Origin::signed(CHARLIE),
+            ALICE,
+            X_BTC
+        ));
+
+        // the order of the frozen account can still be settled
+        assert_ok!(XAssets::move_balance(
+            &X_BTC,
+            &ALICE,
+            AssetType::ReservedDexSpot,
+            &BOB,
+            AssetType::Usable,
+            20
+        ));
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 220);
+        // but not moved to the other types of another account
+        assert_eq!(
+            XAssets::move_balance(
+                &X_BTC,
+                &ALICE,
+                AssetType::ReservedDexSpot,
+                &BOB,
+                AssetType::ReservedDexSpot,
+                20
+            ),
+            Err(AssetErr::Frozen)
+        );
+    });
+}
+
+#[test]
+fn lock_on_frozen_balance_should_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        set_roles();
+        assert_ok!(XAssets::freeze_account(
+            Origin::signed(CHARLIE),
+            ALICE,
+            X_BTC
+        ));
+
+        assert_noop!(
+            XAssets::try_set_lock(*b"test_id1", X_BTC, &ALICE, 10),
+            XAssetsErr::AccountFrozen
+        );
+        // the locks stay the same as the locked balance
+        assert!(XAssets::locks(ALICE, X_BTC).is_empty());
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 0);
+    });
+}
+
+#[test]
+fn vested_transfer_to_frozen_account_should_not_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        set_roles();
+        assert_ok!(XAssets::freeze_account(
+            Origin::signed(CHARLIE),
+            ALICE,
+            X_BTC
+        ));
+
+        assert_noop!(
+            XAssets::vested_transfer(
+                Origin::signed(BOB),
+                ALICE,
+                X_BTC,
+                VestingSchedule {
+                    locked: 100,
+                    per_block: 10,
+                    starting_block: 10,
+                }
+            ),
+            XAssetsErr::AccountFrozen
+        );
+    });
+}
@@ -0,0 +1,120 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use crate::mock::*;
+use crate::*;
+
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn take_snapshot_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssets::take_snapshot(Origin::signed(ALICE), X_BTC, b"airdrop".to_vec()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XAssets::take_snapshot(Origin::root(), X_BTC, vec![]),
+            XAssetsErr::InvalidSnapshotName
+        );
+        assert_noop!(
+            XAssets::take_snapshot(Origin::root(), X_BTC, vec![b'a'; SNAPSHOT_NAME_MAX_LEN + 1]),
+            XAssetsErr::InvalidSnapshotName
+        );
+
+        assert_ok!(XAssets::take_snapshot(
+            Origin::root(),
+            X_BTC,
+            b"airdrop".to_vec()
+        ));
+        assert_eq!(
+            XAssets::snapshots(0),
+            Some(AssetSnapshot {
+                asset_id: X_BTC,
+                name: b"airdrop".to_vec(),
+                block: 1,
+                total_issuance: 1000,
+            })
+        );
+        assert_eq!(XAssets::latest_snapshot_of(X_BTC), Some(0));
+        assert_eq!(XAssets::next_snapshot_id(), 1);
+
+        let snapshot_event = MetaEvent::assets(RawEvent::SnapshotTaken(0, X_BTC));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == snapshot_event));
+
+        assert_eq!(XAssets::balance_at(1, &ALICE), None);
+    });
+}
+
+#[test]
+fn balance_at_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssets::take_snapshot(
+            Origin::root(),
+            X_BTC,
+            b"s0".to_vec()
+        ));
+        // nothing changed since the snapshot
+        assert_eq!(XAssets::balance_at(0, &ALICE), Some(100));
+        assert!(XAssets::account_snapshots(ALICE, X_BTC).is_empty());
+
+        assert_ok!(XAssets::transfer(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC.into(),
+            10
+        ));
+        // the balance is recorded only once, on the first modification.
+        assert_ok!(XAssets::transfer(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC.into(),
+            10
+        ));
+        assert_eq!(XAssets::account_snapshots(ALICE, X_BTC), vec![(0, 100)]);
+        assert_eq!(XAssets::balance_at(0, &ALICE), Some(100));
+        assert_eq!(XAssets::balance_at(0, &BOB), Some(200));
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 220);
+
+        System::set_block_number(2);
+        assert_ok!(XAssets::take_snapshot(
+            Origin::root(),
+            X_BTC,
+            b"s1".to_vec()
+        ));
+        assert_eq!(XAssets::balance_at(1, &ALICE), Some(80));
+
+        // CHARLIE is only modified after s1, whose balance at s0 is the same as s1.
+        assert_ok!(XAssets::transfer(
+            Origin::signed(CHARLIE),
+            ALICE,
+            X_BTC.into(),
+            100
+        ));
+        assert_eq!(XAssets::balance_at(0, &CHARLIE), Some(300));
+        assert_eq!(XAssets::balance_at(1, &CHARLIE), Some(300));
+        assert_eq!(XAssets::balance_at(0, &ALICE), Some(100));
+        assert_eq!(XAssets::balance_at(1, &ALICE), Some(80));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 180);
+
+        // the reserved balance is counted as well
+        assert_ok!(XAssets::take_snapshot(
+            Origin::root(),
+            X_BTC,
+            b"s2".to_vec()
+        ));
+        assert_ok!(XAssets::move_balance(
+            &X_BTC,
+            &DAVE,
+            AssetType::Usable,
+            &DAVE,
+            AssetType::ReservedWithdrawal,
+            100
+        ));
+        assert_ok!(XAssets::destroy_reserved_withdrawal(&X_BTC, &DAVE, 100));
+        assert_eq!(XAssets::balance_at(2, &DAVE), Some(400));
+        assert_eq!(XAssets::balance_at(0, &DAVE), Some(400));
+        assert_eq!(XAssets::all_type_asset_balance(&DAVE, &X_BTC), 300);
+    });
+}
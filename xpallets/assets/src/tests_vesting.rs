@@ -0,0 +1,201 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use crate::mock::*;
+use crate::*;
+
+use frame_support::{assert_noop, assert_ok, traits::LockIdentifier, StorageMap};
+
+use orml_traits::currency::MultiLockableCurrency;
+
+pub const ID_1: LockIdentifier = *b"1       ";
+
+fn schedule(
+    locked: Balance,
+    per_block: Balance,
+    starting_block: u64,
+) -> VestingSchedule<Balance, u64> {
+    VestingSchedule {
+        locked,
+        per_block,
+        starting_block,
+    }
+}
+
+#[test]
+fn vested_transfer_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssets::vested_transfer(
+            Origin::signed(BOB),
+            ALICE,
+            X_BTC,
+            schedule(100, 10, 10)
+        ));
+        assert_eq!(XAssets::vesting(ALICE, X_BTC), Some(schedule(100, 10, 10)));
+        assert_eq!(XAssets::vesting_schedules_of(&ALICE).len(), 1);
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 100);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 100);
+        assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 1);
+
+        let vested_event = MetaEvent::assets(RawEvent::VestedTransfer(X_BTC, BOB, ALICE, 100));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == vested_event));
+
+        // the locked balance can not be transferred
+        assert_noop!(
+            XAssets::transfer(Origin::signed(ALICE), BOB, X_BTC.into(), 150),
+            XAssetsErr::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn vest_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssets::vested_transfer(
+            Origin::signed(BOB),
+            ALICE,
+            X_BTC,
+            schedule(100, 10, 10)
+        ));
+
+        // not started yet
+        System::set_block_number(5);
+        assert_ok!(XAssets::vest(Origin::signed(ALICE), X_BTC));
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 100);
+
+        System::set_block_number(15);
+        assert_ok!(XAssets::vest(Origin::signed(ALICE), X_BTC));
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 50);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 150);
+        assert_ok!(XAssets::transfer(
+            Origin::signed(ALICE),
+            BOB,
+            X_BTC.into(),
+            150
+        ));
+
+        System::set_block_number(20);
+        assert_ok!(XAssets::vest(Origin::signed(ALICE), X_BTC));
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 0);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 50);
+        assert_eq!(XAssets::vesting(ALICE, X_BTC), None);
+        assert!(XAssets::locks(ALICE, X_BTC).is_empty());
+
+        let completed_event = MetaEvent::assets(RawEvent::VestingCompleted(X_BTC, ALICE));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == completed_event));
+
+        assert_noop!(
+            XAssets::vest(Origin::signed(ALICE), X_BTC),
+            XAssetsErr::NotVesting
+        );
+    });
+}
+
+#[test]
+fn vested_transfer_should_fail() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(100, 0, 10)),
+            XAssetsErr::InvalidVestingSchedule
+        );
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(0, 10, 10)),
+            XAssetsErr::InvalidVestingSchedule
+        );
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(300, 10, 10)),
+            XAssetsErr::InsufficientBalance
+        );
+
+        assert_ok!(XAssets::vested_transfer(
+            Origin::signed(BOB),
+            ALICE,
+            X_BTC,
+            schedule(100, 10, 10)
+        ));
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(50, 10, 10)),
+            XAssetsErr::ExistingVestingSchedule
+        );
+    });
+}
+
+#[test]
+fn vested_transfer_should_respect_the_limits() {
+    ExtBuilder::default().build_and_execute(|| {
+        // the dust can not be transferred with a vesting schedule
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(9, 1, 10)),
+            XAssetsErr::VestedTransferAmountLow
+        );
+        // the schedule can not start later than `MaxVestingDelay` blocks from now
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(100, 10, 102)),
+            XAssetsErr::InvalidVestingSchedule
+        );
+
+        assert_ok!(XAssets::vested_transfer(
+            Origin::signed(BOB),
+            ALICE,
+            X_BTC,
+            schedule(10, 1, 101)
+        ));
+        assert_eq!(XAssets::vesting(ALICE, X_BTC), Some(schedule(10, 1, 101)));
+    });
+}
+
+#[test]
+fn min_vested_transfer_should_be_set_per_asset() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XAssets::set_min_vested_transfer(Origin::signed(ALICE), X_BTC, 50),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XAssets::set_min_vested_transfer(Origin::root(), X_BTC, 50));
+        assert_eq!(XAssets::min_vested_transfer_of(X_BTC), Some(50));
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(49, 1, 10)),
+            XAssetsErr::VestedTransferAmountLow
+        );
+
+        // the vested transfer is not allowed until the minimum is set
+        MinVestedTransferOf::<Test>::remove(X_BTC);
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(BOB), ALICE, X_BTC, schedule(100, 10, 10)),
+            XAssetsErr::NoMinVestedTransfer
+        );
+    });
+}
+
+#[test]
+fn vesting_lock_should_overlap_with_other_locks() {
+    ExtBuilder::default().build_and_execute(|| {
+        XAssets::set_lock(ID_1, X_BTC, &ALICE, 80);
+        assert_ok!(XAssets::vested_transfer(
+            Origin::signed(BOB),
+            ALICE,
+            X_BTC,
+            schedule(100, 10, 10)
+        ));
+        assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 2);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 100);
+
+        System::set_block_number(15);
+        assert_ok!(XAssets::vest(Origin::signed(ALICE), X_BTC));
+        // the other lock is still in effect
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 80);
+
+        System::set_block_number(20);
+        assert_ok!(XAssets::vest(Origin::signed(ALICE), X_BTC));
+        assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 1);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 80);
+
+        XAssets::remove_lock(ID_1, X_BTC, &ALICE);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 0);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 200);
+    });
+}
@@ -6,12 +6,16 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 // Substrate
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, CheckedMul, Saturating, Zero},
+    RuntimeDebug, SaturatedConversion,
+};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*, slice::Iter};
 
 // ChainX
+use chainx_primitives::AssetId;
 pub use chainx_primitives::{Decimals, Desc, Token};
-use xpallet_assets_registrar::AssetInfo;
+use xpallet_assets_registrar::{AssetInfo, AssetMetadata};
 
 use frame_support::traits::LockIdentifier;
 
@@ -75,6 +79,7 @@ pub struct TotalAssetInfo<Balance> {
     pub balance: BTreeMap<AssetType, Balance>,
     pub is_online: bool,
     pub restrictions: AssetRestrictions,
+    pub metadata: AssetMetadata,
 }
 
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
@@ -86,6 +91,7 @@ pub enum AssetErr {
     TotalAssetOverFlow,
     InvalidAsset,
     NotAllow,
+    Frozen,
 }
 
 impl<T: Trait> From<AssetErr> for Error<T> {
@@ -97,6 +103,7 @@ impl<T: Trait> From<AssetErr> for Error<T> {
             AssetErr::TotalAssetOverFlow => Error::<T>::TotalAssetOverflow,
             AssetErr::InvalidAsset => Error::<T>::InvalidAsset,
             AssetErr::NotAllow => Error::<T>::ActionNotAllowed,
+            AssetErr::Frozen => Error::<T>::AccountFrozen,
         }
     }
 }
@@ -120,3 +127,54 @@ pub struct WithdrawalLimit<Balance> {
     pub minimal_withdrawal: Balance,
     pub fee: Balance,
 }
+
+/// A vesting schedule of an asset, the `locked` balance is released linearly
+/// by `per_block` since `starting_block`.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct VestingSchedule<Balance, BlockNumber> {
+    /// Locked amount at genesis of the schedule.
+    pub locked: Balance,
+    /// Amount that gets unlocked every block after `starting_block`.
+    pub per_block: Balance,
+    /// Starting block for unlocking(vesting).
+    pub starting_block: BlockNumber,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+    Balance: AtLeast32BitUnsigned + Copy,
+    BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+    /// Amount still locked at block `n`.
+    pub fn locked_at(&self, n: BlockNumber) -> Balance {
+        // Number of blocks that count toward vesting, saturating to zero if the
+        // schedule has not started yet.
+        let vested_block_count: Balance = n
+            .saturating_sub(self.starting_block)
+            .saturated_into::<u32>()
+            .into();
+        match vested_block_count.checked_mul(&self.per_block) {
+            Some(vested) => self.locked.saturating_sub(vested),
+            None => Zero::zero(),
+        }
+    }
+}
+
+/// Identifier of the balance snapshots.
+pub type SnapshotId = u32;
+
+/// A named snapshot of the balances of an asset.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetSnapshot<Balance, BlockNumber> {
+    pub asset_id: AssetId,
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
+    pub name: Vec<u8>,
+    /// The block at which the snapshot was taken.
+    pub block: BlockNumber,
+    /// Total issuance of the asset when the snapshot was taken.
+    pub total_issuance: Balance,
+}
@@ -0,0 +1,98 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_runtime::traits::{Saturating, Zero};
+use sp_std::collections::btree_map::BTreeMap;
+
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{Get, LockIdentifier},
+    IterableStorageDoubleMap,
+};
+
+use chainx_primitives::AssetId;
+use xp_logging::debug;
+
+use crate::types::VestingSchedule;
+use crate::{BalanceOf, Error, Event, Module, Trait, Vesting};
+
+/// The lock identifier of the vesting balances, which overlaps with the other
+/// locks set by `MultiLockableCurrency`.
+pub const VESTING_ID: LockIdentifier = *b"xvesting";
+
+impl<T: Trait> Module<T> {
+    /// Returns all the vesting schedules of `who`.
+    pub fn vesting_schedules_of(
+        who: &T::AccountId,
+    ) -> BTreeMap<AssetId, VestingSchedule<BalanceOf<T>, T::BlockNumber>> {
+        Vesting::<T>::iter_prefix(who).collect()
+    }
+
+    pub(crate) fn vested_transfer_impl(
+        id: &AssetId,
+        from: &T::AccountId,
+        to: &T::AccountId,
+        schedule: VestingSchedule<BalanceOf<T>, T::BlockNumber>,
+    ) -> DispatchResult {
+        ensure!(
+            !schedule.locked.is_zero() && !schedule.per_block.is_zero(),
+            Error::<T>::InvalidVestingSchedule
+        );
+        let now = frame_system::Module::<T>::block_number();
+        ensure!(
+            schedule.starting_block <= now.saturating_add(T::MaxVestingDelay::get()),
+            Error::<T>::InvalidVestingSchedule
+        );
+        let min_vested_transfer =
+            Self::min_vested_transfer_of(id).ok_or(Error::<T>::NoMinVestedTransfer)?;
+        ensure!(
+            schedule.locked >= min_vested_transfer,
+            Error::<T>::VestedTransferAmountLow
+        );
+        ensure!(
+            !Vesting::<T>::contains_key(to, id),
+            Error::<T>::ExistingVestingSchedule
+        );
+        // The vesting lock can not be set on the frozen balance.
+        ensure!(!Self::is_frozen(to, id), Error::<T>::AccountFrozen);
+
+        Self::move_usable_balance(id, from, to, schedule.locked)
+            .map_err::<Error<T>, _>(Into::into)?;
+        Vesting::<T>::insert(to, id, schedule);
+        Self::deposit_event(Event::<T>::VestedTransfer(
+            *id,
+            from.clone(),
+            to.clone(),
+            schedule.locked,
+        ));
+
+        Self::update_vesting_lock(id, to)
+    }
+
+    pub(crate) fn vest_impl(id: &AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(Vesting::<T>::contains_key(who, id), Error::<T>::NotVesting);
+        Self::update_vesting_lock(id, who)
+    }
+
+    /// Sets the vesting lock to the balance still locked by the schedule at the
+    /// current block, the schedule is removed once it's fully vested.
+    fn update_vesting_lock(id: &AssetId, who: &T::AccountId) -> DispatchResult {
+        let schedule = Self::vesting(who, id).ok_or(Error::<T>::NotVesting)?;
+        let now = frame_system::Module::<T>::block_number();
+        let unvested = schedule.locked_at(now);
+        debug!(
+            "[update_vesting_lock] who:{:?}, id:{}, schedule:{:?}, now:{:?}, unvested:{:?}",
+            who, id, schedule, now, unvested
+        );
+
+        if unvested.is_zero() {
+            Self::try_remove_lock(VESTING_ID, *id, who)?;
+            Vesting::<T>::remove(who, id);
+            Self::deposit_event(Event::<T>::VestingCompleted(*id, who.clone()));
+        } else {
+            Self::try_set_lock(VESTING_ID, *id, who, unvested)?;
+            Self::deposit_event(Event::<T>::VestingUpdated(*id, who.clone(), unvested));
+        }
+        Ok(())
+    }
+}
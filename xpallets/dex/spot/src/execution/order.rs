@@ -68,6 +68,7 @@ impl<T: Trait> Module<T> {
 
         debug!("[inject_order] New order:{:?}", order);
         <OrderInfoOf<T>>::insert(&who, order_id, &order);
+        <OpenOrdersOf<T>>::mutate(&who, pair_id, |orders| orders.push(order_id));
 
         // The order count of user should be increased after a new order is created.
         <OrderCountOf<T>>::insert(&who, order_id + 1);
@@ -123,7 +124,14 @@ impl<T: Trait> Module<T> {
         #[cfg(feature = "std")]
         let now = std::time::Instant::now();
 
-        Self::apply_match_order(order, pair, handicap);
+        let mut executed_makers = Vec::new();
+        Self::apply_match_order(order, pair, handicap, &mut executed_makers);
+
+        // Only the first `MAX_BACKLOG_ORDER` makers are notified, which is the most
+        // `put_order` pays for.
+        for maker in executed_makers.iter().take(MAX_BACKLOG_ORDER) {
+            T::OnOrderChanged::on_order_changed(maker, pair.id);
+        }
 
         #[cfg(feature = "std")]
         debug!("Took {:?}ms to match this order", now.elapsed().as_millis());
@@ -133,6 +141,7 @@ impl<T: Trait> Module<T> {
         if order.is_fulfilled() {
             order.status = OrderStatus::Filled;
             <OrderInfoOf<T>>::remove(order.submitter(), order.id());
+            Self::remove_open_order(&order.submitter(), order.pair_id(), order.id());
         } else {
             <QuotationsOf<T>>::mutate(order.pair_id(), order.price(), |quotations| {
                 quotations.push((order.submitter(), order.id()))
@@ -155,6 +164,7 @@ impl<T: Trait> Module<T> {
         pair: &TradingPairProfile,
         counterparty_price: T::Price,
         counterparty_side: Side,
+        executed_makers: &mut Vec<T::AccountId>,
     ) {
         let quotations = <QuotationsOf<T>>::get(pair.id, counterparty_price);
         let mut fulfilled_orders = Vec::new();
@@ -186,6 +196,10 @@ impl<T: Trait> Module<T> {
 
                 assert!(execution_result.is_ok(), "Match order execution paniced");
 
+                if !executed_makers.contains(who) {
+                    executed_makers.push(who.clone());
+                }
+
                 // Remove maker_order if it has been full filled.
                 if maker_order.is_fulfilled() {
                     fulfilled_orders.push((maker_order.submitter(), maker_order.id()));
@@ -206,6 +220,7 @@ impl<T: Trait> Module<T> {
         taker_order: &mut OrderInfo<T>,
         pair: &TradingPairProfile,
         lowest_ask: T::Price,
+        executed_makers: &mut Vec<T::AccountId>,
     ) {
         let tick = pair.tick();
         let my_quote = taker_order.price();
@@ -224,6 +239,7 @@ impl<T: Trait> Module<T> {
                 pair,
                 counterparty_price,
                 counterparty_side,
+                executed_makers,
             );
             counterparty_price = Self::tick_up(counterparty_price, tick);
         }
@@ -233,6 +249,7 @@ impl<T: Trait> Module<T> {
         taker_order: &mut OrderInfo<T>,
         pair: &TradingPairProfile,
         highest_bid: T::Price,
+        executed_makers: &mut Vec<T::AccountId>,
     ) {
         let tick = pair.tick();
         let my_quote = taker_order.price();
@@ -251,6 +268,7 @@ impl<T: Trait> Module<T> {
                 pair,
                 counterparty_price,
                 counterparty_side,
+                executed_makers,
             );
             counterparty_price = Self::tick_down(counterparty_price, tick);
         }
@@ -263,14 +281,19 @@ impl<T: Trait> Module<T> {
         taker_order: &mut OrderInfo<T>,
         pair: &TradingPairProfile,
         handicap: &HandicapInfo<T>,
+        executed_makers: &mut Vec<T::AccountId>,
     ) {
         let (lowest_ask, highest_bid) = (handicap.lowest_ask, handicap.highest_bid);
 
         //  Buy: [ lowest_ask  , my_quote ]
         // Sell: [ my_quote , highest_bid ]
         match taker_order.side() {
-            Side::Buy => Self::match_taker_order_buy(taker_order, pair, lowest_ask),
-            Side::Sell => Self::match_taker_order_sell(taker_order, pair, highest_bid),
+            Side::Buy => {
+                Self::match_taker_order_buy(taker_order, pair, lowest_ask, executed_makers)
+            }
+            Side::Sell => {
+                Self::match_taker_order_sell(taker_order, pair, highest_bid, executed_makers)
+            }
         }
     }
 
@@ -284,6 +307,7 @@ impl<T: Trait> Module<T> {
         order_side: Side,
     ) {
         <OrderInfoOf<T>>::remove(&who, order_index);
+        Self::remove_open_order(&who, pair_id, order_index);
 
         let order_key = (who, order_index);
         Self::remove_quotation(pair_id, price, order_key);
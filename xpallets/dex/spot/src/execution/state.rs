@@ -142,6 +142,7 @@ impl<T: Trait> Module<T> {
         );
         for (who, order_idx) in fulfilled_orders.iter() {
             <OrderInfoOf<T>>::remove(who, order_idx);
+            Self::remove_open_order(who, pair_id, *order_idx);
         }
 
         <QuotationsOf<T>>::mutate(pair_id, price, |quotations| {
@@ -149,6 +150,15 @@ impl<T: Trait> Module<T> {
         });
     }
 
+    /// Removes the order from the open orders of `who` in `pair_id`.
+    ///
+    /// This happens when the order is fulfilled or killed.
+    pub(crate) fn remove_open_order(who: &T::AccountId, pair_id: TradingPairId, order_id: OrderId) {
+        <OpenOrdersOf<T>>::mutate(who, pair_id, |orders| {
+            orders.retain(|id| *id != order_id);
+        });
+    }
+
     /// Removes the quotation only.
     ///
     /// This happens when the order is killed.
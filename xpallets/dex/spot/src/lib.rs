@@ -6,6 +6,7 @@
 #![allow(clippy::type_complexity)]
 
 mod execution;
+mod migration;
 mod rpc;
 mod types;
 pub mod weights;
@@ -19,9 +20,12 @@ mod tests;
 
 use codec::Codec;
 
-use sp_runtime::traits::{
-    AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, SaturatedConversion, StaticLookup,
-    Zero,
+use sp_runtime::{
+    traits::{
+        AtLeast32BitUnsigned, MaybeSerializeDeserialize, Member, SaturatedConversion, Saturating,
+        StaticLookup, Zero,
+    },
+    Perbill,
 };
 use sp_std::prelude::*;
 use sp_std::{cmp, fmt::Debug};
@@ -30,7 +34,9 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
+    storage::IterableStorageDoubleMap,
     traits::{Currency, Get, ReservableCurrency},
+    weights::Weight,
     Parameter,
 };
 use frame_system::{ensure_root, ensure_signed};
@@ -58,6 +64,9 @@ const MAX_BACKLOG_ORDER: usize = 1000;
 /// more time than the Block time to finish.
 const DEFAULT_FLUCTUATION: u32 = 100;
 
+/// Maximum of open orders of an account in a trading pair.
+pub const MAX_OPEN_ORDERS_PER_PAIR: usize = 50;
+
 pub type BalanceOf<T> = <<T as xpallet_assets::Trait>::Currency as Currency<
     <T as frame_system::Trait>::AccountId,
 >>::Balance;
@@ -86,9 +95,32 @@ pub trait Trait: xpallet_assets::Trait {
         + MaybeSerializeDeserialize
         + Debug;
 
+    /// Handler for the changes of the orders.
+    type OnOrderChanged: OnOrderChanged<Self::AccountId>;
+
     type WeightInfo: WeightInfo;
 }
 
+pub trait OnOrderChanged<AccountId> {
+    /// Called after the orders of `who` in `pair_id` have been put, executed or canceled.
+    ///
+    /// NOTE: This is called once for each maker matched by a taker order, up to
+    /// `MAX_BACKLOG_ORDER` makers, hence it must be cheap and not depend on the
+    /// order history of `who`.
+    fn on_order_changed(who: &AccountId, pair_id: TradingPairId);
+
+    /// The weight of a single `on_order_changed` call.
+    fn on_order_changed_weight() -> Weight;
+}
+
+impl<AccountId> OnOrderChanged<AccountId> for () {
+    fn on_order_changed(_: &AccountId, _: TradingPairId) {}
+
+    fn on_order_changed_weight() -> Weight {
+        0
+    }
+}
+
 decl_storage! {
     trait Store for Module<T: Trait> as XSpot {
         /// How many trading pairs so far.
@@ -119,6 +151,11 @@ decl_storage! {
             double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) OrderId
             => Option<OrderInfo<T>>;
 
+        /// The ids of the open orders given the account ID and trading pair ID.
+        pub OpenOrdersOf get(fn open_orders_of):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) TradingPairId
+            => Vec<OrderId>;
+
         /// All the accounts and the order number given the trading pair ID and price.
         pub QuotationsOf get(fn quotations_of):
             double_map hasher(twox_64_concat) TradingPairId, hasher(twox_64_concat) T::Price
@@ -131,6 +168,9 @@ decl_storage! {
         /// The map of trading pair ID to the price fluctuation. Use with caution!
         pub PriceFluctuationOf get(fn price_fluctuation_of):
             map hasher(twox_64_concat) TradingPairId => PriceFluctuation = DEFAULT_FLUCTUATION;
+
+        /// Storage version of the pallet.
+        StorageVersion build(|_: &GenesisConfig<T>| Releases::V2_0_0): Releases;
     }
 
     add_extra_genesis {
@@ -207,6 +247,8 @@ decl_error! {
         TradingPairAlreadyExists,
         /// Too many orders for the same price.
         TooManyBacklogOrders,
+        /// Too many open orders of an account in the same trading pair.
+        TooManyOpenOrders,
         /// Can not retrieve the asset info given the trading pair.
         InvalidTradingPairAsset,
         /// Only the orders with ZeroFill or PartialFill can be canceled.
@@ -231,7 +273,20 @@ decl_module! {
 
         fn deposit_event() = default;
 
-        #[weight = <T as Trait>::WeightInfo::put_order()]
+        fn on_runtime_upgrade() -> Weight {
+            migration::on_runtime_upgrade::<T>()
+        }
+
+        /// Put an order, which is matched against the resting orders first.
+        ///
+        /// At most `MAX_BACKLOG_ORDER` matched makers are notified to `OnOrderChanged`
+        /// along with the taker.
+        #[weight = <T as Trait>::WeightInfo::put_order()
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(
+                T::OnOrderChanged::on_order_changed_weight()
+                    .saturating_mul(MAX_BACKLOG_ORDER as Weight + 1)
+            )]
         pub fn put_order(
             origin,
             #[compact] pair_id: TradingPairId,
@@ -253,6 +308,10 @@ decl_module! {
 
             Self::is_valid_quote(price, side, pair_id)?;
             Self::has_too_many_backlog_orders(pair_id, price, side)?;
+            ensure!(
+                Self::open_orders_of(&who, pair_id).len() < MAX_OPEN_ORDERS_PER_PAIR,
+                Error::<T>::TooManyOpenOrders
+            );
 
             // Reserve the token according to the order side.
             let (reserve_asset, reserve_amount) = match side {
@@ -263,7 +322,9 @@ decl_module! {
             Self::apply_put_order(who, pair_id, order_type, side, amount, price, reserve_amount)?;
         }
 
-        #[weight = <T as Trait>::WeightInfo::cancel_order()]
+        #[weight = <T as Trait>::WeightInfo::cancel_order()
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(T::OnOrderChanged::on_order_changed_weight())]
         pub fn cancel_order(
             origin,
             #[compact] pair_id: TradingPairId,
@@ -274,7 +335,9 @@ decl_module! {
         }
 
         /// Force cancel an order.
-        #[weight = <T as Trait>::WeightInfo::force_cancel_order()]
+        #[weight = <T as Trait>::WeightInfo::force_cancel_order()
+            .saturating_add(T::DbWeight::get().reads_writes(1, 1))
+            .saturating_add(T::OnOrderChanged::on_order_changed_weight())]
         fn force_cancel_order(
             origin,
             who: <T::Lookup as StaticLookup>::Source,
@@ -360,6 +423,73 @@ impl<T: Trait> Module<T> {
         None
     }
 
+    /// Returns the total remaining amount of `who`'s open orders of `pair_id` whose price
+    /// lies within `band` around the mid price, measured by the base currency.
+    ///
+    /// The mid price falls back to the latest price when either side of handicap is empty.
+    pub fn liquidity_in_band(
+        who: &T::AccountId,
+        pair_id: TradingPairId,
+        band: Perbill,
+    ) -> BalanceOf<T> {
+        let handicap = Self::handicap_of(pair_id);
+        let mid_price = if handicap.highest_bid.is_zero() || handicap.lowest_ask.is_zero() {
+            match Self::trading_pair_info_of(pair_id) {
+                Some(info) => info.latest_price,
+                None => return Zero::zero(),
+            }
+        } else {
+            (handicap.highest_bid + handicap.lowest_ask) / 2u32.saturated_into()
+        };
+        let spread = band * mid_price;
+        let (lower, upper) = (
+            mid_price.saturating_sub(spread),
+            mid_price.saturating_add(spread),
+        );
+
+        Self::open_orders_of(who, pair_id)
+            .into_iter()
+            .filter_map(|order_id| Self::order_info_of(who, order_id))
+            .filter(|order| order.price() >= lower && order.price() <= upper)
+            .fold(Zero::zero(), |total: BalanceOf<T>, order| {
+                // Only the reserved amount is at stake, which is measured by the quote
+                // currency for the buy orders and backs the unfilled amount of them.
+                let reserved_in_base = match order.side() {
+                    Side::Sell => order.remaining,
+                    Side::Buy if order.remaining.is_zero() => Zero::zero(),
+                    Side::Buy => order.remaining_in_base(),
+                };
+                total.saturating_add(reserved_in_base)
+            })
+    }
+
+    /// Returns how many smallest units of `base` one smallest unit of `quote` is worth,
+    /// given the latest price of the tradable trading pair `base`/`quote`.
+    ///
+    /// value = 10^(base.decimals + price.decimals - quote.decimals) / price
+    pub fn quote_value_in_base(base: AssetId, quote: AssetId) -> Option<u128> {
+        let pair = Self::get_trading_pair_by_currency_pair(&CurrencyPair::new(base, quote))
+            .filter(|pair| pair.tradable)?;
+        let latest_price = Self::trading_pair_info_of(pair.id)?
+            .latest_price
+            .saturated_into::<u128>();
+        if latest_price.is_zero() {
+            return None;
+        }
+
+        let decimals_of = |asset_id| {
+            <xpallet_assets_registrar::Module<T>>::asset_info_of(asset_id)
+                .map(|info| u32::from(info.decimals()))
+        };
+        let (base_p, quote_p) = (decimals_of(base)?, decimals_of(quote)?);
+
+        match (base_p + pair.pip_decimals).checked_sub(quote_p) {
+            Some(exp) => 10_u128.checked_pow(exp).map(|unit| unit / latest_price),
+            // One smallest unit of `quote` is worth less than one smallest unit of `base`.
+            None => Some(0),
+        }
+    }
+
     #[inline]
     fn trading_pair(pair_id: TradingPairId) -> Result<TradingPairProfile, Error<T>> {
         TradingPairOf::get(pair_id).ok_or(Error::<T>::InvalidTradingPair)
@@ -445,6 +575,8 @@ impl<T: Trait> Module<T> {
 
         Self::try_match_order(&pair, &mut order, pair_id, side, price);
 
+        T::OnOrderChanged::on_order_changed(&order.submitter(), pair_id);
+
         Ok(())
     }
 
@@ -491,6 +623,8 @@ impl<T: Trait> Module<T> {
             order.side(),
         );
 
+        T::OnOrderChanged::on_order_changed(who, pair_id);
+
         Ok(())
     }
 }
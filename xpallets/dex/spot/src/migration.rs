@@ -0,0 +1,39 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+};
+
+use xp_logging::info;
+
+use crate::types::{OrderStatus, Releases};
+use crate::{OpenOrdersOf, OrderInfoOf, StorageVersion, Trait};
+
+/// Apply all of the migrations due to `StorageVersion`.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    match StorageVersion::get() {
+        Releases::V2_0_0 => 0,
+        Releases::V1_0_0 => migrate_to_v2::<T>(),
+    }
+}
+
+/// Build the index of the open orders from the existing orders.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+    let mut count: Weight = 0;
+    let mut indexed: Weight = 0;
+    for (who, order_id, order) in OrderInfoOf::<T>::iter() {
+        count += 1;
+        if order.status == OrderStatus::Created || order.status == OrderStatus::PartialFill {
+            indexed += 1;
+            OpenOrdersOf::<T>::mutate(&who, order.pair_id(), |orders| orders.push(order_id));
+        }
+    }
+    StorageVersion::put(Releases::V2_0_0);
+    info!(
+        "[migrate_to_v2] indexed {} open orders out of {} orders",
+        indexed, count
+    );
+    T::DbWeight::get().reads_writes(count + indexed + 1, indexed + 1)
+}
@@ -91,17 +91,21 @@ impl pallet_balances::Trait for Test {
 impl Trait for Test {
     type Event = ();
     type Price = Price;
+    type OnOrderChanged = ();
     type WeightInfo = ();
 }
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const RegistrationDeposit: Balance = 100;
 }
 
 impl xpallet_assets_registrar::Trait for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = XSpot;
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
     type WeightInfo = ();
 }
 
@@ -112,6 +116,7 @@ impl xpallet_assets::Trait for Test {
     type TreasuryAccount = ();
     type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
     type OnAssetChanged = ();
+    type MaxVestingDelay = ();
     type WeightInfo = ();
 }
 
@@ -4,7 +4,9 @@
 
 use sp_std::collections::btree_map::BTreeMap;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok, traits::OnRuntimeUpgrade, StorageDoubleMap, StorageValue,
+};
 use xpallet_assets::AssetType;
 
 use super::mock::*;
@@ -147,6 +149,75 @@ fn convert_base_to_quote_should_work() {
     })
 }
 
+#[test]
+fn quote_value_in_base_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        use xp_protocol::{PCX, X_BTC};
+
+        // 1 BTC = 10_000 PCX given the price 0.000_100_000 of PCX/BTC.
+        assert_eq!(XSpot::quote_value_in_base(PCX, X_BTC), Some(10_000));
+        assert_eq!(XSpot::quote_value_in_base(X_BTC, PCX), None);
+
+        assert_ok!(XSpot::update_trading_pair(Origin::root(), 0, 2, false));
+        assert_eq!(XSpot::quote_value_in_base(PCX, X_BTC), None);
+    })
+}
+
+#[test]
+fn liquidity_in_band_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let pair_id = 0;
+        let who = 1;
+        let trading_pair = XSpot::trading_pair_of(pair_id).unwrap();
+
+        t_set_handicap(pair_id, 1_000_000, 1_100_000);
+
+        t_generic_issue(trading_pair.quote(), who, 10);
+        t_issue_pcx(who, 1000);
+        assert_ok!(t_put_order_buy(who, pair_id, 1000, 1_000_200));
+        assert_ok!(t_put_order_sell(who, pair_id, 100, 1_210_000));
+
+        // mid price: (1_000_200 + 1_100_000) / 2 = 1_050_100
+        assert_eq!(
+            XSpot::liquidity_in_band(&who, pair_id, Perbill::from_percent(10)),
+            1000
+        );
+        assert_eq!(
+            XSpot::liquidity_in_band(&who, pair_id, Perbill::from_percent(20)),
+            1100
+        );
+        assert_eq!(
+            XSpot::liquidity_in_band(&who, 1, Perbill::from_percent(20)),
+            0
+        );
+    })
+}
+
+#[test]
+fn liquidity_in_band_should_exclude_canceled_orders() {
+    ExtBuilder::default().build_and_execute(|| {
+        let pair_id = 0;
+        let who = 1;
+        let trading_pair = XSpot::trading_pair_of(pair_id).unwrap();
+
+        t_set_handicap(pair_id, 1_000_000, 1_100_000);
+
+        t_generic_issue(trading_pair.quote(), who, 10);
+        t_issue_pcx(who, 1000);
+        assert_ok!(t_put_order_buy(who, pair_id, 1000, 1_000_200));
+        assert_ok!(t_put_order_sell(who, pair_id, 100, 1_210_000));
+
+        let band = Perbill::from_percent(20);
+        assert_eq!(XSpot::liquidity_in_band(&who, pair_id, band), 1100);
+
+        assert_ok!(t_cancel_order(who, pair_id, 0));
+        assert_eq!(XSpot::liquidity_in_band(&who, pair_id, band), 100);
+
+        assert_ok!(t_cancel_order(who, pair_id, 1));
+        assert_eq!(XSpot::liquidity_in_band(&who, pair_id, band), 0);
+    })
+}
+
 #[test]
 fn put_order_reserve_should_work() {
     ExtBuilder::default().build_and_execute(|| {
@@ -315,10 +386,14 @@ fn match_order_should_work() {
         assert_eq!(order_1_1.already_filled, 500);
         assert_eq!(order_1_1.status, OrderStatus::PartialFill);
         assert_eq!(order_1_1.executed_indices, vec![0]);
+        assert_eq!(XSpot::open_orders_of(1, 0), vec![0, 1]);
+        assert!(XSpot::open_orders_of(2, 0).is_empty());
 
         assert_ok!(t_put_order_sell(2, 0, 700, 1_000_100));
 
         assert_eq!(XSpot::order_info_of(1, 1), None);
+        assert_eq!(XSpot::open_orders_of(1, 0), vec![0]);
+        assert_eq!(XSpot::open_orders_of(2, 0), vec![1]);
         let order_2_1 = XSpot::order_info_of(2, 1).unwrap();
         assert_eq!(order_2_1.status, OrderStatus::PartialFill);
         assert_eq!(order_2_1.already_filled, 500);
@@ -349,6 +424,46 @@ fn cancel_order_should_work() {
 
         assert_eq!(XSpot::quotations_of(0, 1_200_000), vec![]);
         assert_eq!(XSpot::order_info_of(1, 1), None);
+        assert_eq!(XSpot::open_orders_of(1, 0), vec![0]);
+    })
+}
+
+#[test]
+fn too_many_open_orders_should_not_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_set_handicap(0, 1_000_000, 1_100_000);
+        t_issue_pcx(1, 2000);
+
+        for _ in 0..MAX_OPEN_ORDERS_PER_PAIR {
+            assert_ok!(t_put_order_sell(1, 0, 10, 1_210_000));
+        }
+        assert_noop!(
+            t_put_order_sell(1, 0, 10, 1_210_000),
+            Error::<Test>::TooManyOpenOrders
+        );
+
+        assert_ok!(t_cancel_order(1, 0, 0));
+        assert_ok!(t_put_order_sell(1, 0, 10, 1_210_000));
+    })
+}
+
+#[test]
+fn migrate_to_v2_should_index_open_orders() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_set_handicap(0, 1_000_000, 1_100_000);
+        t_issue_pcx(1, 2000);
+
+        assert_ok!(t_put_order_sell(1, 0, 100, 1_210_000));
+        assert_ok!(t_put_order_sell(1, 0, 100, 1_220_000));
+
+        OpenOrdersOf::<Test>::remove(1, 0);
+        StorageVersion::put(Releases::V1_0_0);
+
+        XSpot::on_runtime_upgrade();
+        assert_eq!(StorageVersion::get(), Releases::V2_0_0);
+        let mut open_orders = XSpot::open_orders_of(1, 0);
+        open_orders.sort();
+        assert_eq!(open_orders, vec![0, 1]);
     })
 }
 
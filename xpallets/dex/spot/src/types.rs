@@ -430,3 +430,18 @@ impl<AccountId: Clone, Balance: Copy + Ord + BaseArithmetic, BlockNumber: Copy,
         }
     }
 }
+
+/// The storage version of this pallet.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    /// `OrderInfoOf` only.
+    V1_0_0,
+    /// `OpenOrdersOf` is added.
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
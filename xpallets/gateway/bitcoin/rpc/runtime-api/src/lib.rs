@@ -0,0 +1,32 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Runtime API definition required by ChainX RPC extensions.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+
+pub use xpallet_assets::AssetType;
+pub use xpallet_gateway_bitcoin::{
+    BtcCustodyInfo, BtcPendingDeposit, BtcPendingWithdrawal, BtcProofOfReserves,
+    BtcTrusteeAddrReserves,
+};
+pub use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalState};
+
+sp_api::decl_runtime_apis! {
+    /// The API to query the bitcoin gateway info.
+    ///
+    /// Version 2: `proof_of_reserves` is added.
+    #[api_version(2)]
+    pub trait XGatewayBitcoinApi<Balance>
+    where
+        Balance: Codec,
+    {
+        /// Get the BTC custodied by the trustee addresses and the total issuance of X-BTC.
+        fn custody_info() -> BtcCustodyInfo<Balance>;
+
+        /// Get the proof of reserves of X-BTC.
+        fn proof_of_reserves() -> BtcProofOfReserves<Balance>;
+    }
+}
@@ -0,0 +1,185 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! RPC interface for the bitcoin gateway module.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance};
+
+use xpallet_gateway_bitcoin_rpc_runtime_api::{
+    AssetType, BtcCustodyInfo, BtcPendingDeposit, BtcPendingWithdrawal, BtcProofOfReserves,
+    BtcTrusteeAddrReserves, WithdrawalRecordId, WithdrawalState,
+    XGatewayBitcoinApi as XGatewayBitcoinRuntimeApi,
+};
+
+/// XGatewayBitcoin RPC methods.
+#[rpc]
+pub trait XGatewayBitcoinApi<BlockHash, Balance>
+where
+    Balance: Display + FromStr,
+{
+    /// Get the BTC custodied by the trustee addresses and the total issuance of X-BTC.
+    #[rpc(name = "xgatewaybitcoin_custodyInfo")]
+    fn custody_info(&self, at: Option<BlockHash>) -> Result<BtcCustodyInfo<RpcBalance<Balance>>>;
+
+    /// Get the proof of reserves of X-BTC, which could be reconciled against bitcoin.
+    #[rpc(name = "xgatewaybitcoin_proofOfReserves")]
+    fn proof_of_reserves(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<RpcBtcProofOfReserves<RpcBalance<Balance>>>;
+}
+
+/// A struct that implements the [`XGatewayBitcoinApi`].
+pub struct XGatewayBitcoin<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> XGatewayBitcoin<C, B> {
+    /// Create new `XGatewayBitcoin` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, Balance> XGatewayBitcoinApi<<Block as BlockT>::Hash, Balance>
+    for XGatewayBitcoin<C, Block>
+where
+    Block: BlockT,
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: XGatewayBitcoinRuntimeApi<Block, Balance>,
+    Balance: Codec + Display + FromStr,
+{
+    fn custody_info(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BtcCustodyInfo<RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.custody_info(&at)
+            .map(|info| BtcCustodyInfo {
+                custodied: info.custodied,
+                utxo_count: info.utxo_count,
+                total_issuance: info.total_issuance.into(),
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn proof_of_reserves(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<RpcBtcProofOfReserves<RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.proof_of_reserves(&at)
+            .map(Into::into)
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBtcProofOfReserves<Balance> {
+    pub total_issuance: BTreeMap<AssetType, Balance>,
+    pub pending_withdrawals: BTreeMap<WithdrawalRecordId, RpcBtcPendingWithdrawal<Balance>>,
+    pub pending_deposits: Vec<RpcBtcPendingDeposit>,
+    pub trustee_addrs: Vec<RpcBtcTrusteeAddrReserves>,
+}
+
+impl<Balance: Display + FromStr> From<BtcProofOfReserves<Balance>>
+    for RpcBtcProofOfReserves<RpcBalance<Balance>>
+{
+    fn from(reserves: BtcProofOfReserves<Balance>) -> Self {
+        Self {
+            total_issuance: reserves
+                .total_issuance
+                .into_iter()
+                .map(|(ty, balance)| (ty, balance.into()))
+                .collect(),
+            pending_withdrawals: reserves
+                .pending_withdrawals
+                .into_iter()
+                .map(|(id, withdrawal)| (id, withdrawal.into()))
+                .collect(),
+            pending_deposits: reserves
+                .pending_deposits
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            trustee_addrs: reserves.trustee_addrs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBtcPendingWithdrawal<Balance> {
+    pub addr: String,
+    pub balance: Balance,
+    pub state: WithdrawalState,
+}
+
+impl<Balance: Display + FromStr> From<BtcPendingWithdrawal<Balance>>
+    for RpcBtcPendingWithdrawal<RpcBalance<Balance>>
+{
+    fn from(withdrawal: BtcPendingWithdrawal<Balance>) -> Self {
+        Self {
+            addr: String::from_utf8_lossy(&withdrawal.addr).into_owned(),
+            balance: withdrawal.balance.into(),
+            state: withdrawal.state,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBtcPendingDeposit {
+    pub addr: String,
+    pub txid: String,
+    pub balance: u64,
+}
+
+impl From<BtcPendingDeposit> for RpcBtcPendingDeposit {
+    fn from(deposit: BtcPendingDeposit) -> Self {
+        Self {
+            addr: String::from_utf8_lossy(&deposit.addr).into_owned(),
+            txid: format!("{:?}", deposit.txid),
+            balance: deposit.balance,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBtcTrusteeAddrReserves {
+    pub addr: String,
+    pub inflow: u64,
+    pub outflow: u64,
+    pub unspent: u64,
+}
+
+impl From<BtcTrusteeAddrReserves> for RpcBtcTrusteeAddrReserves {
+    fn from(reserves: BtcTrusteeAddrReserves) -> Self {
+        Self {
+            addr: String::from_utf8_lossy(&reserves.addr).into_owned(),
+            inflow: reserves.inflow,
+            outflow: reserves.outflow,
+            unspent: reserves.unspent,
+        }
+    }
+}
@@ -20,7 +20,8 @@ use light_bitcoin::{
 };
 
 use crate::{
-    types::*, Call, Module, PendingDeposits, Trait, TxState, Verifier, WithdrawalProposal,
+    trustee::insert_withdrawal_proposal, types::*, Call, Module, PendingDeposits, Trait,
+    TrusteeUtxos, TxState, Verifier, WithdrawalProposals,
 };
 
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
@@ -111,14 +112,26 @@ fn prepare_withdrawal<T: Trait>() -> Transaction {
         tx: old_withdraw.clone(),
         trustee_list: vec![(alice, true), (bob, true)],
     };
-    WithdrawalProposal::<T>::put(proposal);
+    insert_withdrawal_proposal::<T>(0, proposal);
 
     // replace tx
     let mut new_withdraw = old_withdraw;
     new_withdraw.inputs = tmp.inputs; // replace inputs
+    insert_trustee_utxos_of(&new_withdraw);
     new_withdraw
 }
 
+fn insert_trustee_utxos_of(tx: &Transaction) {
+    for input in tx.inputs.iter() {
+        let outpoint = &input.previous_output;
+        let utxo = BtcTrusteeUtxo {
+            addr: b"3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF".to_vec(),
+            balance: 100000,
+        };
+        TrusteeUtxos::insert((outpoint.txid, outpoint.index), utxo);
+    }
+}
+
 // block height: 577696
 // https://blockchain.info/rawtx/62c389f1974b8a44737d76f92da0f5cd7f6f48d065e7af6ba368298361141270?format=hex
 fn create_tx() -> Transaction {
@@ -180,11 +193,11 @@ benchmarks! {
             tx: tx.clone(),
             trustee_list: vec![],
         };
-        WithdrawalProposal::<T>::put(proposal);
+        insert_withdrawal_proposal::<T>(0, proposal);
 
     }: _(RawOrigin::Signed(caller), tx_raw, info, Some(prev_tx_raw))
     verify {
-        assert!(WithdrawalProposal::<T>::get().is_none());
+        assert!(WithdrawalProposals::<T>::get(0).is_none());
         assert_eq!(
             TxState::get(tx_hash),
             Some(BtcTxState {
@@ -214,10 +227,11 @@ benchmarks! {
         XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, second_withdraw, b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(), b"".to_vec().into()).unwrap();
 
         let tx = create_tx();
+        insert_trustee_utxos_of(&tx);
         let tx_raw: Vec<u8> = serialization::serialize(&tx).into();
     }: _(RawOrigin::Signed(caller), vec![0, 1], tx_raw)
     verify {
-        assert!(WithdrawalProposal::<T>::get().is_some());
+        assert!(WithdrawalProposals::<T>::get(0).is_some());
     }
 
     sign_withdraw_tx {
@@ -232,13 +246,13 @@ benchmarks! {
             tx: tx,
             trustee_list: vec![ (alice, true) ],
         };
-        WithdrawalProposal::<T>::put(proposal);
+        insert_withdrawal_proposal::<T>(0, proposal);
 
         let (signed_tx, _, _) = withdraw_tx();
         let tx_raw: Vec<u8> = serialization::serialize(&signed_tx).into();
-    }: _(RawOrigin::Signed(bob), Some(tx_raw))
+    }: _(RawOrigin::Signed(bob), 0, Some(tx_raw))
     verify {
-        assert_eq!(WithdrawalProposal::<T>::get().unwrap().sig_state, VoteResult::Finish);
+        assert_eq!(WithdrawalProposals::<T>::get(0).unwrap().sig_state, VoteResult::Finish);
     }
 
     set_best_index {
@@ -293,10 +307,10 @@ benchmarks! {
             tx: tx,
             trustee_list: vec![],
         };
-        WithdrawalProposal::<T>::put(proposal);
-    }: _(RawOrigin::Root)
+        insert_withdrawal_proposal::<T>(0, proposal);
+    }: _(RawOrigin::Root, 0)
     verify {
-        assert!(WithdrawalProposal::<T>::get().is_none());
+        assert!(WithdrawalProposals::<T>::get(0).is_none());
     }
 
     force_replace_proposal_tx {
@@ -305,9 +319,9 @@ benchmarks! {
         Verifier::put(BtcTxVerifier::Test);
         let tx = prepare_withdrawal::<T>();
         let raw = serialization::serialize(&tx);
-    }: _(RawOrigin::Root, raw.into())
+    }: _(RawOrigin::Root, 0, raw.into())
     verify {
-        assert_eq!(WithdrawalProposal::<T>::get().unwrap().tx, tx);
+        assert_eq!(WithdrawalProposals::<T>::get(0).unwrap().tx, tx);
     }
 
     set_btc_withdrawal_fee {
@@ -321,6 +335,22 @@ benchmarks! {
     }: _(RawOrigin::Root,  2000000)
     verify {
     }
+
+    force_add_trustee_utxos {
+        let n in 1 .. 100;
+        let utxos = (0..n)
+            .map(|i| {
+                let utxo = BtcTrusteeUtxo {
+                    addr: b"3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF".to_vec(),
+                    balance: 100000,
+                };
+                (H256::repeat_byte(i as u8), 0, utxo)
+            })
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Root, utxos)
+    verify {
+        assert!(TrusteeUtxos::contains_key((H256::repeat_byte((n - 1) as u8), 0)));
+    }
 }
 
 #[cfg(test)]
@@ -342,6 +372,7 @@ mod tests {
             assert_ok!(test_benchmark_force_replace_proposal_tx::<Test>());
             assert_ok!(test_benchmark_set_btc_withdrawal_fee::<Test>());
             assert_ok!(test_benchmark_set_btc_deposit_limit::<Test>());
+            assert_ok!(test_benchmark_force_add_trustee_utxos::<Test>());
         });
     }
 }
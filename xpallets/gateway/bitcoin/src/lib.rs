@@ -5,6 +5,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod header;
+mod migration;
+mod rpc;
 pub mod trustee;
 mod tx;
 mod types;
@@ -24,8 +26,8 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
     ensure,
-    traits::{EnsureOrigin, UnixTime},
-    weights::Pays,
+    traits::{EnsureOrigin, Get, UnixTime},
+    weights::{Pays, Weight},
 };
 use frame_system::{ensure_root, ensure_signed};
 use orml_utilities::with_transaction_result;
@@ -53,14 +55,23 @@ use xpallet_gateway_common::{
 };
 use xpallet_support::try_addr;
 
-pub use self::types::{BtcAddress, BtcParams, BtcTxVerifier, BtcWithdrawalProposal};
+pub use self::rpc::{
+    BtcCustodyInfo, BtcPendingDeposit, BtcPendingWithdrawal, BtcProofOfReserves,
+    BtcTrusteeAddrReserves,
+};
+pub use self::types::{
+    BtcAddress, BtcParams, BtcTrusteeAddrFlow, BtcTrusteeUtxo, BtcTxVerifier,
+    BtcWithdrawalProposal, WithdrawalProposalId,
+};
 pub use self::weights::WeightInfo;
 use self::{
-    trustee::{get_current_trustee_address_pair, get_last_trustee_address_pair},
-    tx::remove_pending_deposit,
+    trustee::{
+        get_current_trustee_address_pair, get_last_trustee_address_pair, remove_withdrawal_proposal,
+    },
+    tx::{insert_trustee_utxo, remove_pending_deposit},
     types::{
         BtcDepositCache, BtcHeaderIndex, BtcHeaderInfo, BtcRelayedTx, BtcRelayedTxInfo,
-        BtcTxResult, BtcTxState,
+        BtcTxResult, BtcTxState, Releases,
     },
 };
 
@@ -83,6 +94,8 @@ pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
     type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
     type ReferralBinding: ReferralBinding<Self::AccountId>;
     type AddressBinding: AddressBinding<Self::AccountId, BtcAddress>;
+    /// The maximum number of withdrawal proposals pending at the same time.
+    type MaxConcurrentProposals: Get<u32>;
     type WeightInfo: WeightInfo;
 }
 
@@ -157,12 +170,18 @@ decl_error! {
         NoProposal,
         /// invalid proposal
         InvalidProposal,
-        /// last proposal not finished yet
-        NotFinishProposal,
+        /// the proposal tx spends an input already spent by another pending proposal
+        ConflictedProposal,
         /// no withdrawal record for this id
         NoWithdrawalRecord,
         /// already vote for this withdrawal proposal
         DuplicateVote,
+        /// no more withdrawal proposal id available
+        ProposalIdOverflow,
+        /// too many withdrawal proposals are pending
+        TooManyProposals,
+        /// the proposal tx spends an output which is not a trustee utxo
+        NotTrusteeUtxo,
     }
 }
 
@@ -184,14 +203,14 @@ decl_event!(
         UnclaimedDeposit(H256, BtcAddress),
         /// A unclaimed deposit record was removed. [depositor, deposit_amount, tx_hash, btc_address]
         PendingDepositRemoved(AccountId, Balance, H256, BtcAddress),
-        /// A new withdrawal proposal was created. [proposer, withdrawal_ids]
-        WithdrawalProposalCreated(AccountId, Vec<u32>),
-        /// A trustee voted/vetoed a withdrawal proposal. [trustee, vote_status]
-        WithdrawalProposalVoted(AccountId, bool),
-        /// A withdrawal proposal was dropped. [reject_count, total_count, withdrawal_ids]
-        WithdrawalProposalDropped(u32, u32, Vec<u32>),
-        /// The proposal has been processed successfully and is waiting for broadcasting. [tx_hash]
-        WithdrawalProposalCompleted(H256),
+        /// A new withdrawal proposal was created. [proposal_id, proposer, withdrawal_ids]
+        WithdrawalProposalCreated(WithdrawalProposalId, AccountId, Vec<u32>),
+        /// A trustee voted/vetoed a withdrawal proposal. [proposal_id, trustee, vote_status]
+        WithdrawalProposalVoted(WithdrawalProposalId, AccountId, bool),
+        /// A withdrawal proposal was dropped. [proposal_id, reject_count, total_count, withdrawal_ids]
+        WithdrawalProposalDropped(WithdrawalProposalId, u32, u32, Vec<u32>),
+        /// The proposal has been processed successfully and is waiting for broadcasting. [proposal_id, tx_hash]
+        WithdrawalProposalCompleted(WithdrawalProposalId, H256),
         /// A fatal error happened during the withdrwal process. [tx_hash, proposal_hash]
         WithdrawalFatalErr(H256, H256),
     }
@@ -214,9 +233,21 @@ decl_storage! {
         pub TxState get(fn tx_state): map hasher(identity) H256 => Option<BtcTxState>;
         /// unclaimed deposit info, addr => tx_hash, btc value,
         pub PendingDeposits get(fn pending_deposits): map hasher(blake2_128_concat) BtcAddress => Vec<BtcDepositCache>;
-
-        /// withdrawal tx outs for account, tx_hash => outs ( out index => withdrawal account )
-        pub WithdrawalProposal get(fn withdrawal_proposal): Option<BtcWithdrawalProposal<T::AccountId>>;
+        /// unspent outputs owned by the trustee addresses, (txid, out index) => utxo
+        pub TrusteeUtxos get(fn trustee_utxos): map hasher(identity) (H256, u32) => Option<BtcTrusteeUtxo>;
+        /// processed inflows/outflows of the trustee addresses, addr => flow
+        pub TrusteeAddrFlows get(fn trustee_addr_flows): map hasher(blake2_128_concat) BtcAddress => BtcTrusteeAddrFlow;
+
+        /// the id of the next withdrawal proposal
+        pub NextWithdrawalProposalId get(fn next_proposal_id): WithdrawalProposalId = 0;
+        /// pending withdrawal proposals, proposal_id => proposal (withdrawal tx and trustee votes)
+        pub WithdrawalProposals get(fn withdrawal_proposal):
+            map hasher(twox_64_concat) WithdrawalProposalId => Option<BtcWithdrawalProposal<T::AccountId>>;
+        /// the number of pending withdrawal proposals
+        pub PendingProposalCount get(fn pending_proposal_count): u32;
+        /// the pending withdrawal proposal of a tx, tx_hash => proposal_id
+        pub ProposalIdByTxHash get(fn proposal_id_by_tx_hash):
+            map hasher(identity) H256 => Option<WithdrawalProposalId>;
 
         /// get GenesisInfo (header, height)
         pub GenesisInfo get(fn genesis_info) config(): (BtcHeader, u32);
@@ -234,6 +265,9 @@ decl_storage! {
         pub MaxWithdrawalCount get(fn max_withdrawal_count) config(): u32;
 
         Verifier get(fn verifier) config(): BtcTxVerifier;
+
+        /// Storage version of the pallet.
+        StorageVersion build(|_: &GenesisConfig<T>| Releases::V2_0_0): Releases;
     }
     add_extra_genesis {
         config(genesis_hash): H256;
@@ -265,9 +299,16 @@ decl_storage! {
 
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        /// The maximum number of withdrawal proposals pending at the same time.
+        const MaxConcurrentProposals: u32 = T::MaxConcurrentProposals::get();
+
         type Error = Error<T>;
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            migration::on_runtime_upgrade::<T>()
+        }
+
         /// if use `BtcHeader` struct would export in metadata, cause complex in front-end
         #[weight = <T as Trait>::WeightInfo::push_header()]
         pub fn push_header(origin, header: Vec<u8>) -> DispatchResultWithPostInfo {
@@ -307,6 +348,8 @@ decl_module! {
         /// Trustee create a proposal for a withdrawal list. `tx` is the proposal withdrawal transaction.
         /// The `tx` would have a sign for current creator or do not have sign. if creator do not sign
         /// for this transaction, he could do `sign_withdraw_tx` later.
+        /// Several proposals could be pending at the same time, as long as their inputs do not
+        /// conflict with each other.
         #[weight = <T as Trait>::WeightInfo::create_withdraw_tx()]
         pub fn create_withdraw_tx(origin, withdrawal_id_list: Vec<u32>, tx: Vec<u8>) -> DispatchResult {
             let from = ensure_signed(origin)?;
@@ -324,7 +367,11 @@ decl_module! {
         /// this proposal. If `tx` is Some(), the inner part must be a valid transaction with this
         /// trustee signature.
         #[weight = <T as Trait>::WeightInfo::sign_withdraw_tx()]
-        pub fn sign_withdraw_tx(origin, tx: Option<Vec<u8>>) -> DispatchResult {
+        pub fn sign_withdraw_tx(
+            origin,
+            #[compact] proposal_id: WithdrawalProposalId,
+            tx: Option<Vec<u8>>
+        ) -> DispatchResult {
             let from = ensure_signed(origin)?;
             Self::ensure_trustee(&from)?;
 
@@ -333,9 +380,9 @@ decl_module! {
             } else {
                 None
             };
-            native!(debug, "[sign_withdraw_tx] from:{:?}, vote_tx:{:?}", from, tx);
+            native!(debug, "[sign_withdraw_tx] from:{:?}, proposal_id:{}, vote_tx:{:?}", from, proposal_id, tx);
 
-            Self::apply_sig_withdraw(from, tx)?;
+            Self::apply_sig_withdraw(from, proposal_id, tx)?;
             Ok(())
         }
 
@@ -371,27 +418,45 @@ decl_module! {
             Ok(())
         }
 
-        /// Dangerous! remove current withdrawal proposal directly. Please check business logic before
+        /// Dangerous! remove a withdrawal proposal directly. Please check business logic before
         /// do this operation.
         #[weight = <T as Trait>::WeightInfo::remove_proposal()]
-        pub fn remove_proposal(origin) -> DispatchResult {
+        pub fn remove_proposal(origin, #[compact] proposal_id: WithdrawalProposalId) -> DispatchResult {
             ensure_root(origin)?;
-            WithdrawalProposal::<T>::kill();
+            remove_withdrawal_proposal::<T>(proposal_id);
             Ok(())
         }
 
-        /// Dangerous! force replace current withdrawal proposal transaction. Please check business
+        /// Dangerous! force replace a withdrawal proposal transaction. Please check business
         /// logic before do this operation. Must make sure current proposal transaction is invalid
         /// (e.g. when created a proposal, the inputs are not in double spend state, but after other
         /// trustees finish signing, the inputs are in double spend due other case. Thus could create
         /// a new valid transaction which outputs same to current proposal to replace current proposal
         /// transaction.)
         #[weight = <T as Trait>::WeightInfo::force_replace_proposal_tx()]
-        pub fn force_replace_proposal_tx(origin, tx: Vec<u8>) -> DispatchResult {
+        pub fn force_replace_proposal_tx(
+            origin,
+            #[compact] proposal_id: WithdrawalProposalId,
+            tx: Vec<u8>
+        ) -> DispatchResult {
             T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
             let tx = Self::deserialize_tx(tx.as_slice())?;
-            native!(debug, "[force_replace_proposal_tx] new_tx:{:?}", tx);
-            Self::force_replace_withdraw_tx(tx)
+            native!(debug, "[force_replace_proposal_tx] proposal_id:{}, new_tx:{:?}", proposal_id, tx);
+            Self::force_replace_withdraw_tx(proposal_id, tx)
+        }
+
+        /// Bootstrap the trustee utxos created before they were tracked, e.g., on an existing
+        /// chain. `utxos` is a list of (txid, out index, utxo), the tracked ones are skipped and
+        /// the others are counted as the inflows of their trustee addresses.
+        #[weight = <T as Trait>::WeightInfo::force_add_trustee_utxos(utxos.len() as u32)]
+        pub fn force_add_trustee_utxos(origin, utxos: Vec<(H256, u32, BtcTrusteeUtxo)>) -> DispatchResult {
+            ensure_root(origin)?;
+            for (txid, index, utxo) in utxos {
+                if !TrusteeUtxos::contains_key((txid, index)) {
+                    insert_trustee_utxo((txid, index), utxo);
+                }
+            }
+            Ok(())
         }
 
         /// Set bitcoin withdrawal fee
@@ -0,0 +1,40 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    storage::migration::take_storage_value, traits::Get, weights::Weight, StorageValue,
+};
+
+use xp_logging::info;
+
+use crate::trustee::insert_withdrawal_proposal;
+use crate::types::{BtcWithdrawalProposal, Releases};
+use crate::{NextWithdrawalProposalId, StorageVersion, Trait};
+
+/// Apply all of the migrations due to `StorageVersion`.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    match StorageVersion::get() {
+        Releases::V2_0_0 => 0,
+        Releases::V1_0_0 => migrate_to_v2::<T>(),
+    }
+}
+
+/// Move the pending `WithdrawalProposal` if any to `WithdrawalProposals` with id 0, and
+/// index it by the hash of its tx.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+    let proposal = take_storage_value::<BtcWithdrawalProposal<T::AccountId>>(
+        b"XGatewayBitcoin",
+        b"WithdrawalProposal",
+        &[],
+    );
+    let moved = proposal.is_some();
+    if let Some(proposal) = proposal {
+        insert_withdrawal_proposal::<T>(0, proposal);
+        NextWithdrawalProposalId::put(1);
+    }
+    StorageVersion::put(Releases::V2_0_0);
+    info!(
+        "[migrate_to_v2] moved the pending withdrawal proposal:{}",
+        moved
+    );
+    T::DbWeight::get().reads_writes(2, 6)
+}
@@ -97,12 +97,15 @@ impl pallet_balances::Trait for Test {
 // assets
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const RegistrationDeposit: Balance = 100;
 }
 
 impl xpallet_assets_registrar::Trait for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
     type WeightInfo = ();
 }
 
@@ -113,6 +116,7 @@ impl xpallet_assets::Trait for Test {
     type TreasuryAccount = ();
     type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
     type OnAssetChanged = ();
+    type MaxVestingDelay = ();
     type WeightInfo = ();
 }
 
@@ -150,6 +154,10 @@ impl UnixTime for Timestamp {
     }
 }
 
+parameter_types! {
+    pub const MaxConcurrentProposals: u32 = 4;
+}
+
 impl Trait for Test {
     type Event = ();
     type UnixTime = Timestamp;
@@ -162,6 +170,7 @@ impl Trait for Test {
     >;
     type ReferralBinding = XGatewayCommon;
     type AddressBinding = XGatewayCommon;
+    type MaxConcurrentProposals = MaxConcurrentProposals;
     type WeightInfo = ();
 }
 
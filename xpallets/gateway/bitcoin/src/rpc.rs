@@ -0,0 +1,163 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_support::IterableStorageMap;
+use sp_runtime::RuntimeDebug;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+use light_bitcoin::primitives::{hash_rev, H256};
+
+use chainx_primitives::AddrStr;
+use xpallet_assets::{AssetType, BalanceOf, Chain, ChainT};
+use xpallet_gateway_common::traits::TrusteeSession;
+use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalState};
+
+use crate::{types::BtcAddress, Module, PendingDeposits, Trait, TrusteeAddrFlows, TrusteeUtxos};
+
+/// The custody info of X-BTC.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct BtcCustodyInfo<Balance> {
+    /// Total value of the unspent outputs owned by the trustee addresses, in satoshi.
+    pub custodied: u64,
+    /// Number of the unspent outputs owned by the trustee addresses.
+    pub utxo_count: u32,
+    /// Total issuance of X-BTC.
+    pub total_issuance: Balance,
+}
+
+/// A pending X-BTC withdrawal, which has not been paid on bitcoin yet.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct BtcPendingWithdrawal<Balance> {
+    /// The bitcoin address to receive the withdrawal.
+    pub addr: AddrStr,
+    pub balance: Balance,
+    pub state: WithdrawalState,
+}
+
+/// A BTC deposit which has not been claimed by any account yet.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct BtcPendingDeposit {
+    /// The input address of the deposit tx.
+    pub addr: BtcAddress,
+    /// The deposit txid, in the byte order of bitcoin explorers.
+    pub txid: H256,
+    /// The deposit value, in satoshi.
+    pub balance: u64,
+}
+
+/// The processed flows and the remaining unspent value of a trustee address.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct BtcTrusteeAddrReserves {
+    pub addr: BtcAddress,
+    /// Total value of the processed outputs paid to this address, in satoshi.
+    pub inflow: u64,
+    /// Total value of the processed outputs spent from this address, in satoshi.
+    pub outflow: u64,
+    /// Total value of the unspent outputs owned by this address, in satoshi.
+    pub unspent: u64,
+}
+
+/// The X-BTC liabilities and the BTC reserves known by the chain, an external tool
+/// could reconcile them against the bitcoin chain.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct BtcProofOfReserves<Balance> {
+    /// Total issuance of X-BTC, broken down by `AssetType`.
+    pub total_issuance: BTreeMap<AssetType, Balance>,
+    pub pending_withdrawals: BTreeMap<WithdrawalRecordId, BtcPendingWithdrawal<Balance>>,
+    pub pending_deposits: Vec<BtcPendingDeposit>,
+    /// The current and the last trustee addresses, as well as any other address
+    /// which used to hold the trustee funds.
+    pub trustee_addrs: Vec<BtcTrusteeAddrReserves>,
+}
+
+impl<T: Trait> Module<T> {
+    /// Get the BTC custodied by the trustee addresses and the total issuance of X-BTC.
+    pub fn custody_info() -> BtcCustodyInfo<BalanceOf<T>> {
+        let (custodied, utxo_count) = TrusteeUtxos::iter()
+            .fold((0u64, 0u32), |(custodied, count), (_, utxo)| {
+                (custodied.saturating_add(utxo.balance), count + 1)
+            });
+        BtcCustodyInfo {
+            custodied,
+            utxo_count,
+            total_issuance: xpallet_assets::Module::<T>::total_issuance(
+                &<Self as ChainT<_>>::ASSET_ID,
+            ),
+        }
+    }
+
+    /// Get the proof of reserves of X-BTC.
+    pub fn proof_of_reserves() -> BtcProofOfReserves<BalanceOf<T>> {
+        let asset_id = <Self as ChainT<_>>::ASSET_ID;
+
+        let pending_withdrawals =
+            xpallet_gateway_records::Module::<T>::withdrawals_list_by_chain(Chain::Bitcoin)
+                .into_iter()
+                .filter(|(_, withdrawal)| withdrawal.asset_id == asset_id)
+                .map(|(id, withdrawal)| {
+                    let withdrawal = BtcPendingWithdrawal {
+                        addr: withdrawal.addr,
+                        balance: withdrawal.balance,
+                        state: withdrawal.state,
+                    };
+                    (id, withdrawal)
+                })
+                .collect();
+
+        let pending_deposits = PendingDeposits::iter()
+            .flat_map(|(addr, caches)| {
+                caches.into_iter().map(move |cache| BtcPendingDeposit {
+                    addr: addr.clone(),
+                    txid: hash_rev(cache.txid),
+                    balance: cache.balance,
+                })
+            })
+            .collect();
+
+        let mut trustee_addrs = BTreeMap::<BtcAddress, BtcTrusteeAddrReserves>::new();
+        let sessions = T::TrusteeSessionProvider::current_trustee_session()
+            .into_iter()
+            .chain(T::TrusteeSessionProvider::last_trustee_session());
+        for session_info in sessions {
+            for addr in vec![
+                session_info.hot_address.addr,
+                session_info.cold_address.addr,
+            ] {
+                trustee_addrs
+                    .entry(addr.clone())
+                    .or_insert_with(|| BtcTrusteeAddrReserves {
+                        addr,
+                        ..Default::default()
+                    });
+            }
+        }
+        for (addr, flow) in TrusteeAddrFlows::iter() {
+            let reserves =
+                trustee_addrs
+                    .entry(addr.clone())
+                    .or_insert_with(|| BtcTrusteeAddrReserves {
+                        addr,
+                        ..Default::default()
+                    });
+            reserves.inflow = flow.inflow;
+            reserves.outflow = flow.outflow;
+        }
+        for (_, utxo) in TrusteeUtxos::iter() {
+            if let Some(reserves) = trustee_addrs.get_mut(&utxo.addr) {
+                reserves.unspent = reserves.unspent.saturating_add(utxo.balance);
+            }
+        }
+
+        BtcProofOfReserves {
+            total_issuance: xpallet_assets::Module::<T>::total_asset_balance(&asset_id),
+            pending_withdrawals,
+            pending_deposits,
+            trustee_addrs: trustee_addrs.into_iter().map(|(_, v)| v).collect(),
+        }
+    }
+}
@@ -1,6 +1,6 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::{assert_noop, assert_ok, storage::StorageValue};
+use frame_support::{assert_noop, assert_ok, storage::StorageValue, traits::Get};
 use frame_system::RawOrigin;
 use hex_literal::hex;
 
@@ -19,10 +19,10 @@ use crate::mock::{
     XGatewayRecords, X_BTC,
 };
 use crate::{
-    trustee::create_multi_address,
+    trustee::{create_multi_address, insert_withdrawal_proposal, remove_withdrawal_proposal},
     tx::validator::parse_and_check_signed_tx_impl,
-    types::{BtcTxVerifier, BtcWithdrawalProposal, VoteResult},
-    Verifier, WithdrawalProposal,
+    types::{BtcTrusteeUtxo, BtcTxVerifier, BtcWithdrawalProposal, VoteResult},
+    PendingProposalCount, Trait, TrusteeUtxos, Verifier,
 };
 
 #[test]
@@ -172,6 +172,7 @@ fn force_replace_withdraw() {
         // https://btc.com/62c389f1974b8a44737d76f92da0f5cd7f6f48d065e7af6ba368298361141270.rawhex
         const RAW_TX: &str = "0100000001052ceda6cf9c93012a994f4ffa2a29c9e31ecf96f472b175eb8e602bfa2b2c5100000000fdfd000047304402200e4d732c456f4722d376252be16554edb27fc93c55db97859e16682bc62b014502202b9c4b01ad55daa1f76e6a564b7762cd0a81240c947806ab3f3b056f2e77c1da01483045022100c7cd680992de60da8c33fc3ef7f5ead85b204660822d9fbda2d85f9fadba732a022021fdc49b20a6007ea971a385732a4065d1d7c792ac9dc391034fb78aa9f5034b014c69522102df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6210244d81efeb4171b1a8a433b87dd202117f94e44c909c49e42e77b69b5a6ce7d0d2103a36339f413da869df12b1ab0def91749413a0dee87f0bfa85ba7196e6cdad10253aeffffffff03e0349500000000001976a91413256ff2dee6e80c275ddb877abc1ffe453a731488ace00f9700000000001976a914ea6e8dd56703ace584eb9dff0224629f8486672988acc88a02000000000017a914cb94110435d0635223eebe25ed2aaabc03781c458700000000";
         let old_withdraw = RAW_TX.parse::<Transaction>().unwrap();
+        let old_tx_hash = old_withdraw.hash();
         // https://btc.com/092684402f9b21abdb1d2d76511d5983bd1250d173ced171a3f76d03fcc43e97.rawhex
         const ANOTHER_TX: &str = "0100000001059ec66e2a2123364a56bd48f10f57d8a41ecf4082669e6fc85485637043879100000000fdfd00004830450221009fbe7b8f2f4ae771e8773cb5206b9f20286676e2c7cfa98a8e95368acfc3cb3c02203969727a276d7333d5f8815fa364307b8015783cfefbd53def28befdb81855fc0147304402205e5bbe039457d7657bb90dbe63ac30b9547242b44cc03e1f7a690005758e34aa02207208ed76a269d193f1e10583bd902561dbd02826d0486c33a4b1b1839a3d226f014c69522102df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6210244d81efeb4171b1a8a433b87dd202117f94e44c909c49e42e77b69b5a6ce7d0d2103a36339f413da869df12b1ab0def91749413a0dee87f0bfa85ba7196e6cdad10253aeffffffff04288e0300000000001976a914eb016d7998c88a79a50a0408dd7d5839b1ce1a6888aca0bb0d00000000001976a914646fe05e35369248c3f8deea436dc2b92c7dc86888ac50c30000000000001976a914d1a68d6e891a88d53d9bc3b88d172a3ff6b238c388ac20ee03020000000017a914cb94110435d0635223eebe25ed2aaabc03781c458700000000";
         let tmp = ANOTHER_TX.parse::<Transaction>().unwrap();
@@ -206,14 +207,81 @@ fn force_replace_withdraw() {
             tx: old_withdraw.clone(),
             trustee_list: vec![(alice, true), (bob, true)],
         };
-        WithdrawalProposal::<Test>::put(proposal);
+        insert_withdrawal_proposal::<Test>(0, proposal);
+
+        // another pending proposal spends the same inputs
+        let conflicted = BtcWithdrawalProposal::<AccountId> {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![],
+            tx: tmp.clone(),
+            trustee_list: vec![],
+        };
+        insert_withdrawal_proposal::<Test>(1, conflicted);
 
         // replace tx
         let mut new_withdraw = old_withdraw;
         new_withdraw.inputs = tmp.inputs; // replace inputs
 
         let raw = serialization::serialize(&new_withdraw);
-        assert_ok!(XGatewayBitcoin::force_replace_proposal_tx(RawOrigin::Root.into(), raw.into()));
-        assert_eq!(XGatewayBitcoin::withdrawal_proposal().unwrap().tx, new_withdraw);
+        // the inputs must be the trustee utxos
+        assert_noop!(
+            XGatewayBitcoin::force_replace_proposal_tx(RawOrigin::Root.into(), 0, raw.clone().into()),
+            XGatewayBitcoinErr::NotTrusteeUtxo
+        );
+        for input in new_withdraw.inputs.iter() {
+            let outpoint = &input.previous_output;
+            TrusteeUtxos::insert(
+                (outpoint.txid, outpoint.index),
+                BtcTrusteeUtxo {
+                    addr: b"3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF".to_vec(),
+                    balance: 0,
+                },
+            );
+        }
+
+        assert_noop!(
+            XGatewayBitcoin::force_replace_proposal_tx(RawOrigin::Root.into(), 0, raw.clone().into()),
+            XGatewayBitcoinErr::ConflictedProposal
+        );
+        remove_withdrawal_proposal::<Test>(1);
+
+        assert_ok!(XGatewayBitcoin::force_replace_proposal_tx(RawOrigin::Root.into(), 0, raw.into()));
+        assert_eq!(XGatewayBitcoin::withdrawal_proposal(0).unwrap().tx, new_withdraw);
+        assert_eq!(XGatewayBitcoin::proposal_id_by_tx_hash(old_tx_hash), None);
+        assert_eq!(
+            XGatewayBitcoin::proposal_id_by_tx_hash(new_withdraw.hash()),
+            Some(0)
+        );
+    });
+}
+
+#[test]
+fn create_withdraw_should_respect_max_concurrent_proposals() {
+    ExtBuilder::default().build_and_execute(|| {
+        // https://btc.com/62c389f1974b8a44737d76f92da0f5cd7f6f48d065e7af6ba368298361141270.rawhex
+        const RAW_TX: &str = "0100000001052ceda6cf9c93012a994f4ffa2a29c9e31ecf96f472b175eb8e602bfa2b2c5100000000fdfd000047304402200e4d732c456f4722d376252be16554edb27fc93c55db97859e16682bc62b014502202b9c4b01ad55daa1f76e6a564b7762cd0a81240c947806ab3f3b056f2e77c1da01483045022100c7cd680992de60da8c33fc3ef7f5ead85b204660822d9fbda2d85f9fadba732a022021fdc49b20a6007ea971a385732a4065d1d7c792ac9dc391034fb78aa9f5034b014c69522102df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6210244d81efeb4171b1a8a433b87dd202117f94e44c909c49e42e77b69b5a6ce7d0d2103a36339f413da869df12b1ab0def91749413a0dee87f0bfa85ba7196e6cdad10253aeffffffff03e0349500000000001976a91413256ff2dee6e80c275ddb877abc1ffe453a731488ace00f9700000000001976a914ea6e8dd56703ace584eb9dff0224629f8486672988acc88a02000000000017a914cb94110435d0635223eebe25ed2aaabc03781c458700000000";
+        let tx = RAW_TX.parse::<Transaction>().unwrap();
+        let proposal = |tx: Transaction| BtcWithdrawalProposal::<AccountId> {
+            sig_state: VoteResult::Unfinish,
+            withdrawal_id_list: vec![],
+            tx,
+            trustee_list: vec![],
+        };
+
+        let max = <Test as Trait>::MaxConcurrentProposals::get();
+        PendingProposalCount::put(max);
+        assert_noop!(
+            XGatewayBitcoin::apply_create_withdraw(alice(), tx.clone(), vec![]),
+            XGatewayBitcoinErr::TooManyProposals
+        );
+
+        // the count is kept along with the pending proposals
+        PendingProposalCount::put(0);
+        insert_withdrawal_proposal::<Test>(0, proposal(tx.clone()));
+        assert_eq!(XGatewayBitcoin::pending_proposal_count(), 1);
+        assert_eq!(XGatewayBitcoin::proposal_id_by_tx_hash(tx.hash()), Some(0));
+        assert_eq!(remove_withdrawal_proposal::<Test>(0), Some(proposal(tx.clone())));
+        assert_eq!(XGatewayBitcoin::pending_proposal_count(), 0);
+        assert_eq!(XGatewayBitcoin::proposal_id_by_tx_hash(tx.hash()), None);
     });
 }
@@ -2,13 +2,17 @@
 
 #![allow(non_upper_case_globals)]
 
-use frame_support::{assert_noop, assert_ok, storage::StorageValue};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{IterableStorageMap, StorageMap, StorageValue},
+};
 use sp_core::crypto::{set_default_ss58_version, Ss58AddressFormat};
 
 use light_bitcoin::{
     chain::Transaction,
     keys::{Address, Network},
     merkle::PartialMerkleTree,
+    primitives::{hash_rev, H256},
     serialization::{self, Reader},
 };
 
@@ -19,12 +23,13 @@ use crate::mock::{
     XGatewayBitcoinErr, XGatewayCommon, X_BTC,
 };
 use crate::{
+    trustee::{ensure_no_conflicted_inputs, insert_withdrawal_proposal},
     tx::process_tx,
     types::{
-        BtcDepositCache, BtcRelayedTxInfo, BtcTxResult, BtcTxState, BtcWithdrawalProposal,
-        VoteResult,
+        BtcDepositCache, BtcRelayedTxInfo, BtcTrusteeAddrFlow, BtcTrusteeUtxo, BtcTxResult,
+        BtcTxState, BtcWithdrawalProposal, Releases, VoteResult,
     },
-    Trait, WithdrawalProposal,
+    BtcPendingDeposit, StorageVersion, Trait, TrusteeUtxos, TxState,
 };
 
 const DEPOSIT_HOT_ADDR: &str = "3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF";
@@ -239,6 +244,23 @@ fn test_process_tx() {
     ExtBuilder::default().build_and_execute(|| {
         let r = mock_process_tx::<Test>(deposit1.clone(), None);
         assert_eq!(r.result, BtcTxResult::Success);
+        // the output paid to the hot address is recorded as a trustee utxo
+        assert_eq!(
+            XGatewayBitcoin::trustee_utxos((deposit1.hash(), 1)),
+            Some(BtcTrusteeUtxo {
+                addr: DEPOSIT_HOT_ADDR.as_bytes().to_vec(),
+                balance: 22000
+            })
+        );
+        let info = XGatewayBitcoin::custody_info();
+        assert_eq!((info.custodied, info.utxo_count), (22000, 1));
+        assert_eq!(
+            XGatewayBitcoin::trustee_addr_flows(DEPOSIT_HOT_ADDR.as_bytes().to_vec()),
+            BtcTrusteeAddrFlow {
+                inflow: 22000,
+                outflow: 0
+            }
+        );
         let r = mock_process_tx::<Test>(deposit2.clone(), None);
         assert_eq!(r.result, BtcTxResult::Success);
 
@@ -264,6 +286,19 @@ fn test_process_tx() {
                 balance: 190850000
             }]
         );
+        let reserves = XGatewayBitcoin::proof_of_reserves();
+        assert_eq!(
+            reserves
+                .pending_deposits
+                .into_iter()
+                .filter(|deposit| deposit.addr == deposit3_addr.to_vec())
+                .collect::<Vec<_>>(),
+            vec![BtcPendingDeposit {
+                addr: deposit3_addr.to_vec(),
+                txid: hash_rev(deposit3_1.hash()),
+                balance: 190850000
+            }]
+        );
 
         // 4
         let r = mock_process_tx::<Test>(deposit4_0.clone(), None);
@@ -304,18 +339,204 @@ fn test_process_tx() {
         let r = mock_process_tx::<Test>(cold_to_hot.clone(), Some(cold_to_hot_prev.clone()));
         assert_eq!(r.result, BtcTxResult::Success);
 
-        // withdraw
-        WithdrawalProposal::<Test>::put(BtcWithdrawalProposal {
-            sig_state: VoteResult::Unfinish,
-            withdrawal_id_list: vec![],
-            tx: withdraw.clone(),
-            trustee_list: vec![],
-        });
+        // withdraw, matching the second pending proposal
+        insert_withdrawal_proposal::<Test>(0, mock_proposal(hot_to_cold.clone()));
+        insert_withdrawal_proposal::<Test>(1, mock_proposal(withdraw.clone()));
 
         let r = mock_process_tx::<Test>(withdraw.clone(), None);
         assert_eq!(r.result, BtcTxResult::Failure);
         let r = mock_process_tx::<Test>(withdraw.clone(), Some(withdraw_prev.clone()));
         assert_eq!(r.result, BtcTxResult::Success);
+        assert_eq!(XGatewayBitcoin::withdrawal_proposal(1), None);
+        assert_eq!(
+            XGatewayBitcoin::proposal_id_by_tx_hash(withdraw.hash()),
+            None
+        );
+        assert_eq!(
+            XGatewayBitcoin::withdrawal_proposal(0),
+            Some(mock_proposal(hot_to_cold.clone()))
+        );
+        assert_eq!(XGatewayBitcoin::pending_proposal_count(), 1);
+    })
+}
+
+fn trustee_utxos_of(txid: H256) -> Vec<(u32, BtcTrusteeUtxo)> {
+    TrusteeUtxos::iter()
+        .filter(|((hash, _), _)| *hash == txid)
+        .map(|((_, index), utxo)| (index, utxo))
+        .collect()
+}
+
+#[test]
+fn test_replay_failed_tx_after_spent() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        // no prev, the deposit failed but its output to the hot address is recorded
+        let r = mock_process_tx::<Test>(deposit3_1.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Failure);
+        TxState::insert(deposit3_1.hash(), r);
+        let utxos = trustee_utxos_of(deposit3_1.hash());
+        assert_eq!(utxos.len(), 1);
+
+        // the output is spent by a withdrawal later
+        TrusteeUtxos::remove((deposit3_1.hash(), utxos[0].0));
+
+        // the spent output must not be recorded again when replaying the failed tx
+        let r = mock_process_tx::<Test>(deposit3_1.clone(), Some(deposit3_1_prev.clone()));
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert!(trustee_utxos_of(deposit3_1.hash()).is_empty());
+    })
+}
+
+#[test]
+fn test_replay_deposit_flows() {
+    set_default_ss58_version(Ss58AddressFormat::ChainXAccount);
+    ExtBuilder::default().build_and_execute(|| {
+        let hot_addr = DEPOSIT_HOT_ADDR.as_bytes().to_vec();
+        let r = mock_process_tx::<Test>(deposit3_1.clone(), None);
+        assert_eq!(r.result, BtcTxResult::Failure);
+        TxState::insert(deposit3_1.hash(), r);
+        let (index, utxo) = trustee_utxos_of(deposit3_1.hash()).remove(0);
+        let flow = XGatewayBitcoin::trustee_addr_flows(&hot_addr);
+        assert_eq!(flow.inflow, utxo.balance);
+
+        // spend the output by a tx relayed later
+        let mut spend = withdraw.clone();
+        spend.inputs[0].previous_output.txid = deposit3_1.hash();
+        spend.inputs[0].previous_output.index = index;
+        spend.outputs.clear();
+        let r = mock_process_tx::<Test>(spend, None);
+        assert_eq!(r.result, BtcTxResult::Failure);
+        assert_eq!(
+            XGatewayBitcoin::trustee_addr_flows(&hot_addr),
+            BtcTrusteeAddrFlow {
+                inflow: utxo.balance,
+                outflow: utxo.balance
+            }
+        );
+
+        // the replayed deposit is not counted twice
+        let r = mock_process_tx::<Test>(deposit3_1.clone(), Some(deposit3_1_prev.clone()));
+        assert_eq!(r.result, BtcTxResult::Success);
+        assert_eq!(
+            XGatewayBitcoin::trustee_addr_flows(&hot_addr),
+            BtcTrusteeAddrFlow {
+                inflow: utxo.balance,
+                outflow: utxo.balance
+            }
+        );
+        let reserves = XGatewayBitcoin::proof_of_reserves();
+        assert_eq!(
+            reserves
+                .trustee_addrs
+                .into_iter()
+                .find(|reserve| reserve.addr == hot_addr)
+                .map(|reserve| (reserve.inflow, reserve.outflow, reserve.unspent)),
+            Some((utxo.balance, utxo.balance, 0))
+        );
+    })
+}
+
+#[test]
+fn test_force_add_trustee_utxos() {
+    ExtBuilder::default().build_and_execute(|| {
+        let utxo = |balance| BtcTrusteeUtxo {
+            addr: DEPOSIT_HOT_ADDR.as_bytes().to_vec(),
+            balance,
+        };
+        assert_noop!(
+            XGatewayBitcoin::force_add_trustee_utxos(
+                frame_system::RawOrigin::Signed(Default::default()).into(),
+                vec![(deposit1.hash(), 1, utxo(22000))],
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XGatewayBitcoin::force_add_trustee_utxos(
+            frame_system::RawOrigin::Root.into(),
+            vec![
+                (deposit1.hash(), 1, utxo(22000)),
+                (deposit2.hash(), 0, utxo(100))
+            ],
+        ));
+        // the tracked ones are skipped
+        assert_ok!(XGatewayBitcoin::force_add_trustee_utxos(
+            frame_system::RawOrigin::Root.into(),
+            vec![(deposit1.hash(), 1, utxo(1))],
+        ));
+        assert_eq!(
+            XGatewayBitcoin::trustee_utxos((deposit1.hash(), 1)),
+            Some(utxo(22000))
+        );
+        let info = XGatewayBitcoin::custody_info();
+        assert_eq!((info.custodied, info.utxo_count), (22100, 2));
+        assert_eq!(
+            XGatewayBitcoin::trustee_addr_flows(DEPOSIT_HOT_ADDR.as_bytes().to_vec()),
+            BtcTrusteeAddrFlow {
+                inflow: 22100,
+                outflow: 0
+            }
+        );
+    })
+}
+
+fn mock_proposal(tx: Transaction) -> BtcWithdrawalProposal<AccountId> {
+    BtcWithdrawalProposal {
+        sig_state: VoteResult::Unfinish,
+        withdrawal_id_list: vec![],
+        tx,
+        trustee_list: vec![],
+    }
+}
+
+#[test]
+fn test_concurrent_withdrawal_proposals() {
+    ExtBuilder::default().build_and_execute(|| {
+        insert_withdrawal_proposal::<Test>(0, mock_proposal(withdraw.clone()));
+
+        // the proposal spending the other inputs could be pending at the same time
+        assert_ok!(ensure_no_conflicted_inputs::<Test>(&hot_to_cold, None));
+        insert_withdrawal_proposal::<Test>(1, mock_proposal(hot_to_cold.clone()));
+        assert_ok!(ensure_no_conflicted_inputs::<Test>(&cold_to_hot, None));
+
+        // but not the one spending the same inputs, unless it replaces the proposal
+        assert_noop!(
+            ensure_no_conflicted_inputs::<Test>(&withdraw, None),
+            XGatewayBitcoinErr::ConflictedProposal
+        );
+        assert_noop!(
+            ensure_no_conflicted_inputs::<Test>(&withdraw, Some(1)),
+            XGatewayBitcoinErr::ConflictedProposal
+        );
+        assert_ok!(ensure_no_conflicted_inputs::<Test>(&withdraw, Some(0)));
+    })
+}
+
+#[test]
+fn test_migrate_withdrawal_proposal() {
+    use frame_support::{storage::migration::put_storage_value, traits::OnRuntimeUpgrade};
+
+    ExtBuilder::default().build_and_execute(|| {
+        put_storage_value(
+            b"XGatewayBitcoin",
+            b"WithdrawalProposal",
+            &[],
+            mock_proposal(withdraw.clone()),
+        );
+        StorageVersion::put(Releases::V1_0_0);
+
+        XGatewayBitcoin::on_runtime_upgrade();
+
+        assert_eq!(
+            XGatewayBitcoin::withdrawal_proposal(0),
+            Some(mock_proposal(withdraw.clone()))
+        );
+        assert_eq!(XGatewayBitcoin::next_proposal_id(), 1);
+        assert_eq!(
+            XGatewayBitcoin::proposal_id_by_tx_hash(withdraw.hash()),
+            Some(0)
+        );
+        assert_eq!(XGatewayBitcoin::pending_proposal_count(), 1);
+        assert_eq!(StorageVersion::get(), Releases::V2_0_0);
     })
 }
 
@@ -3,7 +3,9 @@
 use frame_support::{
     debug::native,
     dispatch::{DispatchError, DispatchResult},
-    ensure, StorageValue,
+    ensure,
+    traits::Get,
+    IterableStorageMap, StorageMap, StorageValue,
 };
 use sp_runtime::SaturatedConversion;
 use sp_std::{convert::TryFrom, prelude::*};
@@ -12,7 +14,7 @@ use light_bitcoin::{
     chain::Transaction,
     crypto::dhash160,
     keys::{Address, Public, Type},
-    primitives::Bytes,
+    primitives::{hash_rev, Bytes, H256},
     script::{Builder, Opcode, Script},
 };
 
@@ -28,8 +30,9 @@ use xpallet_gateway_common::{
 
 use crate::{
     tx::{addr2vecu8, ensure_identical, validator::parse_and_check_signed_tx},
-    types::{BtcWithdrawalProposal, VoteResult},
-    Error, Event, Module, Trait, WithdrawalProposal,
+    types::{BtcWithdrawalProposal, VoteResult, WithdrawalProposalId},
+    Error, Event, Module, NextWithdrawalProposalId, PendingProposalCount, ProposalIdByTxHash,
+    Trait, TrusteeUtxos, WithdrawalProposals,
 };
 
 pub fn current_trustee_session<T: Trait>(
@@ -239,6 +242,11 @@ impl<T: Trait> Module<T> {
         tx: Transaction,
         withdrawal_id_list: Vec<u32>,
     ) -> DispatchResult {
+        ensure!(
+            Self::pending_proposal_count() < T::MaxConcurrentProposals::get(),
+            Error::<T>::TooManyProposals
+        );
+
         let withdraw_amount = Self::max_withdrawal_count();
         if withdrawal_id_list.len() > withdraw_amount as usize {
             error!(
@@ -252,7 +260,7 @@ impl<T: Trait> Module<T> {
         withdrawal_id_list.sort();
         withdrawal_id_list.dedup();
 
-        check_withdraw_tx::<T>(&tx, &withdrawal_id_list)?;
+        check_withdraw_tx::<T>(&tx, &withdrawal_id_list, None)?;
         info!(
             "[apply_create_withdraw] Create new withdraw, id_list:{:?}",
             withdrawal_id_list
@@ -272,6 +280,11 @@ impl<T: Trait> Module<T> {
             return Err(Error::<T>::InvalidSignCount.into());
         };
 
+        let proposal_id = Self::next_proposal_id();
+        let next_id = proposal_id
+            .checked_add(1_u32)
+            .ok_or(Error::<T>::ProposalIdOverflow)?;
+
         xpallet_gateway_records::Module::<T>::process_withdrawals(
             &withdrawal_id_list,
             Chain::Bitcoin,
@@ -284,9 +297,15 @@ impl<T: Trait> Module<T> {
             Vec::new(),
         );
 
-        info!("[apply_create_withdraw] Pass the legality check of withdrawal");
+        NextWithdrawalProposalId::put(next_id);
+
+        info!(
+            "[apply_create_withdraw] Pass the legality check of withdrawal, proposal_id:{}",
+            proposal_id
+        );
 
         Self::deposit_event(Event::<T>::WithdrawalProposalCreated(
+            proposal_id,
             who.clone(),
             withdrawal_id_list,
         ));
@@ -295,21 +314,28 @@ impl<T: Trait> Module<T> {
             info!("[apply_create_withdraw] Apply sign after creating proposal");
             // due to `SignWithdrawalProposal` event should after `WithdrawalProposalCreated`, thus this function should after proposal
             // but this function would have an error return, this error return should not meet.
-            if insert_trustee_vote_state::<T>(true, &who, &mut proposal.trustee_list).is_err() {
+            if insert_trustee_vote_state::<T>(proposal_id, true, &who, &mut proposal.trustee_list)
+                .is_err()
+            {
                 // should not be error in this function, if hit this branch, panic to clear all modification
                 // TODO change to revoke in future
                 panic!("insert_trustee_vote_state should not be error")
             }
         }
 
-        WithdrawalProposal::<T>::put(proposal);
+        insert_withdrawal_proposal::<T>(proposal_id, proposal);
 
         Ok(())
     }
 
-    pub fn apply_sig_withdraw(who: T::AccountId, tx: Option<Transaction>) -> DispatchResult {
+    pub fn apply_sig_withdraw(
+        who: T::AccountId,
+        proposal_id: WithdrawalProposalId,
+        tx: Option<Transaction>,
+    ) -> DispatchResult {
         let mut proposal: BtcWithdrawalProposal<T::AccountId> =
-            Self::withdrawal_proposal().ok_or(Error::<T>::NoProposal)?;
+            Self::withdrawal_proposal(proposal_id).ok_or(Error::<T>::NoProposal)?;
+        let old_tx_hash = proposal.tx.hash();
 
         if proposal.sig_state == VoteResult::Finish {
             error!("[apply_sig_withdraw] Proposal is on FINISH state, can't sign for this proposal:{:?}", proposal);
@@ -344,16 +370,27 @@ impl<T: Trait> Module<T> {
                     return Err(Error::<T>::InvalidSignCount.into());
                 }
 
-                insert_trustee_vote_state::<T>(true, &who, &mut proposal.trustee_list)?;
+                insert_trustee_vote_state::<T>(
+                    proposal_id,
+                    true,
+                    &who,
+                    &mut proposal.trustee_list,
+                )?;
                 // check required count
                 // required count should be equal or more than (2/3)*total
                 // e.g. total=6 => required=2*6/3=4, thus equal to 4 should mark as finish
                 if sigs_count == sig_num {
                     // mark as finish, can't do anything for this proposal
-                    info!("[apply_sig_withdraw] Signature completed:{}", sigs_count);
+                    info!(
+                        "[apply_sig_withdraw] Signature completed:{}, proposal_id:{}",
+                        sigs_count, proposal_id
+                    );
                     proposal.sig_state = VoteResult::Finish;
 
-                    Self::deposit_event(Event::<T>::WithdrawalProposalCompleted(tx.hash()))
+                    Self::deposit_event(Event::<T>::WithdrawalProposalCompleted(
+                        proposal_id,
+                        tx.hash(),
+                    ))
                 } else {
                     proposal.sig_state = VoteResult::Unfinish;
                 }
@@ -362,7 +399,12 @@ impl<T: Trait> Module<T> {
             }
             None => {
                 // reject
-                insert_trustee_vote_state::<T>(false, &who, &mut proposal.trustee_list)?;
+                insert_trustee_vote_state::<T>(
+                    proposal_id,
+                    false,
+                    &who,
+                    &mut proposal.trustee_list,
+                )?;
 
                 let reject_count = proposal
                     .trustee_list
@@ -376,8 +418,8 @@ impl<T: Trait> Module<T> {
                 let need_reject = total - sig_num + 1;
                 if reject_count == need_reject {
                     info!(
-                        "[apply_sig_withdraw] {}/{} opposition, clear withdrawal proposal",
-                        reject_count, total
+                        "[apply_sig_withdraw] {}/{} opposition, clear withdrawal proposal:{}",
+                        reject_count, total, proposal_id
                     );
 
                     // release withdrawal for applications
@@ -388,9 +430,10 @@ impl<T: Trait> Module<T> {
                         );
                     }
 
-                    WithdrawalProposal::<T>::kill();
+                    remove_withdrawal_proposal::<T>(proposal_id);
 
                     Self::deposit_event(Event::<T>::WithdrawalProposalDropped(
+                        proposal_id,
                         reject_count as u32,
                         sig_num as u32,
                         proposal.withdrawal_id_list,
@@ -401,17 +444,20 @@ impl<T: Trait> Module<T> {
         }
 
         info!(
-            "[apply_sig_withdraw] Current sig state:{:?}, trustee vote:{:?}",
-            proposal.sig_state, proposal.trustee_list
+            "[apply_sig_withdraw] Proposal:{}, current sig state:{:?}, trustee vote:{:?}",
+            proposal_id, proposal.sig_state, proposal.trustee_list
         );
 
-        WithdrawalProposal::<T>::put(proposal);
+        update_withdrawal_proposal::<T>(proposal_id, old_tx_hash, proposal);
         Ok(())
     }
 
-    pub fn force_replace_withdraw_tx(tx: Transaction) -> DispatchResult {
+    pub fn force_replace_withdraw_tx(
+        proposal_id: WithdrawalProposalId,
+        tx: Transaction,
+    ) -> DispatchResult {
         let mut proposal: BtcWithdrawalProposal<T::AccountId> =
-            Self::withdrawal_proposal().ok_or(Error::<T>::NoProposal)?;
+            Self::withdrawal_proposal(proposal_id).ok_or(Error::<T>::NoProposal)?;
 
         ensure!(
             proposal.sig_state == VoteResult::Finish,
@@ -420,7 +466,7 @@ impl<T: Trait> Module<T> {
 
         // make sure withdrawal list is same as current proposal
         let current_withdrawal_list = &proposal.withdrawal_id_list;
-        check_withdraw_tx_impl::<T>(&tx, current_withdrawal_list)?;
+        check_withdraw_tx::<T>(&tx, current_withdrawal_list, Some(proposal_id))?;
 
         // sign
         // check first and get signatures from commit transaction
@@ -431,13 +477,48 @@ impl<T: Trait> Module<T> {
         );
 
         // replace old transaction
+        let old_tx_hash = proposal.tx.hash();
         proposal.tx = tx;
 
-        WithdrawalProposal::<T>::put(proposal);
+        update_withdrawal_proposal::<T>(proposal_id, old_tx_hash, proposal);
         Ok(())
     }
 }
 
+/// Stores a new pending withdrawal proposal and indexes it by the hash of its tx.
+pub(crate) fn insert_withdrawal_proposal<T: Trait>(
+    proposal_id: WithdrawalProposalId,
+    proposal: BtcWithdrawalProposal<T::AccountId>,
+) {
+    ProposalIdByTxHash::insert(proposal.tx.hash(), proposal_id);
+    PendingProposalCount::mutate(|count| *count = count.saturating_add(1));
+    WithdrawalProposals::<T>::insert(proposal_id, proposal);
+}
+
+/// Updates a pending withdrawal proposal, re-indexing it if its tx was replaced or signed.
+fn update_withdrawal_proposal<T: Trait>(
+    proposal_id: WithdrawalProposalId,
+    old_tx_hash: H256,
+    proposal: BtcWithdrawalProposal<T::AccountId>,
+) {
+    let tx_hash = proposal.tx.hash();
+    if tx_hash != old_tx_hash {
+        ProposalIdByTxHash::remove(old_tx_hash);
+        ProposalIdByTxHash::insert(tx_hash, proposal_id);
+    }
+    WithdrawalProposals::<T>::insert(proposal_id, proposal);
+}
+
+/// Removes a pending withdrawal proposal along with its index.
+pub(crate) fn remove_withdrawal_proposal<T: Trait>(
+    proposal_id: WithdrawalProposalId,
+) -> Option<BtcWithdrawalProposal<T::AccountId>> {
+    let proposal = WithdrawalProposals::<T>::take(proposal_id)?;
+    ProposalIdByTxHash::remove(proposal.tx.hash());
+    PendingProposalCount::mutate(|count| *count = count.saturating_sub(1));
+    Some(proposal)
+}
+
 /// Get the required number of signatures
 /// sig_num: Number of signatures required
 /// trustee_num: Total number of multiple signatures
@@ -497,6 +578,7 @@ pub(crate) fn create_multi_address<T: Trait>(
 /// state: false -> Veto signature, true -> Consent signature
 /// only allow inseRelayedTx once
 fn insert_trustee_vote_state<T: Trait>(
+    proposal_id: WithdrawalProposalId,
     state: bool,
     who: &T::AccountId,
     trustee_list: &mut Vec<(T::AccountId, bool)>,
@@ -515,16 +597,68 @@ fn insert_trustee_vote_state<T: Trait>(
             );
         }
     }
-    Module::<T>::deposit_event(Event::<T>::WithdrawalProposalVoted(who.clone(), state));
+    Module::<T>::deposit_event(Event::<T>::WithdrawalProposalVoted(
+        proposal_id,
+        who.clone(),
+        state,
+    ));
     Ok(())
 }
 
-/// Check that the cash withdrawal transaction is correct
-fn check_withdraw_tx<T: Trait>(tx: &Transaction, withdrawal_id_list: &[u32]) -> DispatchResult {
-    match Module::<T>::withdrawal_proposal() {
-        Some(_) => Err(Error::<T>::NotFinishProposal.into()),
-        None => check_withdraw_tx_impl::<T>(tx, withdrawal_id_list),
+/// Check that the cash withdrawal transaction is correct, only spends the trustee utxos and
+/// does not spend any input that is already spent by another pending proposal (`exclude` is
+/// the proposal being replaced).
+fn check_withdraw_tx<T: Trait>(
+    tx: &Transaction,
+    withdrawal_id_list: &[u32],
+    exclude: Option<WithdrawalProposalId>,
+) -> DispatchResult {
+    ensure_trustee_inputs::<T>(tx)?;
+    ensure_no_conflicted_inputs::<T>(tx, exclude)?;
+    check_withdraw_tx_impl::<T>(tx, withdrawal_id_list)
+}
+
+pub(crate) fn ensure_trustee_inputs<T: Trait>(tx: &Transaction) -> DispatchResult {
+    for input in tx.inputs.iter() {
+        let outpoint = &input.previous_output;
+        if !TrusteeUtxos::contains_key((outpoint.txid, outpoint.index)) {
+            error!(
+                "[ensure_trustee_inputs] Tx input ({:?}, {}) is not a trustee utxo, tx:{:?}",
+                hash_rev(outpoint.txid),
+                outpoint.index,
+                tx.hash()
+            );
+            return Err(Error::<T>::NotTrusteeUtxo.into());
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn ensure_no_conflicted_inputs<T: Trait>(
+    tx: &Transaction,
+    exclude: Option<WithdrawalProposalId>,
+) -> DispatchResult {
+    for (id, proposal) in WithdrawalProposals::<T>::iter() {
+        if Some(id) == exclude {
+            continue;
+        }
+        let conflicted = tx.inputs.iter().any(|input| {
+            proposal
+                .tx
+                .inputs
+                .iter()
+                .any(|other| other.previous_output == input.previous_output)
+        });
+        if conflicted {
+            error!(
+                "[ensure_no_conflicted_inputs] Tx inputs conflict with pending proposal:{}, tx:{:?}",
+                id,
+                tx.hash()
+            );
+            return Err(Error::<T>::ConflictedProposal.into());
+        }
     }
+    Ok(())
 }
 
 fn check_withdraw_tx_impl<T: Trait>(
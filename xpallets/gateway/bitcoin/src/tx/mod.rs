@@ -3,7 +3,7 @@
 mod secp256k1_verifier;
 pub mod validator;
 
-use frame_support::{debug::native, dispatch::DispatchResult, StorageMap, StorageValue};
+use frame_support::{debug::native, dispatch::DispatchResult, StorageMap};
 use sp_runtime::{traits::Zero, SaturatedConversion};
 use sp_std::prelude::*;
 
@@ -14,7 +14,7 @@ use light_bitcoin::{
 };
 
 use chainx_primitives::AssetId;
-use xp_gateway_bitcoin::{BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector};
+use xp_gateway_bitcoin::{extract_output_addr, BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector};
 use xp_gateway_common::AccountExtractor;
 use xp_logging::{debug, error, info, warn};
 use xpallet_assets::ChainT;
@@ -24,8 +24,9 @@ use xpallet_support::try_str;
 pub use self::validator::validate_transaction;
 use crate::{
     native,
-    types::{AccountInfo, BtcAddress, BtcDepositCache, BtcTxResult, BtcTxState},
-    BalanceOf, Error, Event, Module, PendingDeposits, Trait, WithdrawalProposal,
+    trustee::remove_withdrawal_proposal,
+    types::{AccountInfo, BtcAddress, BtcDepositCache, BtcTrusteeUtxo, BtcTxResult, BtcTxState},
+    BalanceOf, Error, Event, Module, PendingDeposits, Trait, TrusteeAddrFlows, TrusteeUtxos,
 };
 
 pub fn process_tx<T: Trait>(
@@ -46,6 +47,14 @@ pub fn process_tx<T: Trait>(
     );
 
     let tx_type = meta_type.ref_into();
+    // A failed tx could be replayed later, the trustee utxos are only updated when the tx is
+    // processed for the first time, otherwise the spent outputs would be recorded again.
+    if Module::<T>::tx_state(tx.hash()).is_none() {
+        let mut trustee_pairs = vec![current_trustee_pair];
+        trustee_pairs.extend(last_trustee_pair);
+        update_trustee_utxos::<T>(&tx, network, &trustee_pairs);
+    }
+
     let result = match meta_type {
         BtcTxMetaType::<_>::Deposit(deposit_info) => deposit::<T>(tx.hash(), deposit_info),
         BtcTxMetaType::<_>::Withdrawal => withdraw::<T>(tx),
@@ -57,6 +66,73 @@ pub fn process_tx<T: Trait>(
     BtcTxState { tx_type, result }
 }
 
+/// Keep `TrusteeUtxos` in sync with the bitcoin chain: the trustee outputs spent by the inputs of
+/// `tx` are removed and the outputs of `tx` to the trustee addresses are recorded.
+///
+/// The processed inflows/outflows of the trustee addresses are accumulated in `TrusteeAddrFlows`
+/// at the same time. The update is not idempotent, it must be applied once per tx.
+fn update_trustee_utxos<T: Trait>(
+    tx: &Transaction,
+    network: Network,
+    trustee_pairs: &[(Address, Address)],
+) {
+    for input in tx.inputs.iter() {
+        let outpoint = &input.previous_output;
+        if let Some(utxo) = TrusteeUtxos::take((outpoint.txid, outpoint.index)) {
+            TrusteeAddrFlows::mutate(&utxo.addr, |flow| {
+                flow.outflow = flow.outflow.saturating_add(utxo.balance)
+            });
+            debug!(
+                "[update_trustee_utxos] Spend trustee utxo ({:?}, {}), addr:{:?}, balance:{}",
+                hash_rev(outpoint.txid),
+                outpoint.index,
+                try_str(&utxo.addr),
+                utxo.balance
+            );
+        }
+    }
+
+    let txid = tx.hash();
+    for (index, output) in tx.outputs.iter().enumerate() {
+        let trustee_addr = extract_output_addr(output, network).and_then(|addr| {
+            trustee_pairs.iter().find_map(|(hot, cold)| {
+                if hot.hash == addr.hash {
+                    Some(*hot)
+                } else if cold.hash == addr.hash {
+                    Some(*cold)
+                } else {
+                    None
+                }
+            })
+        });
+        if let Some(trustee_addr) = trustee_addr {
+            let utxo = BtcTrusteeUtxo {
+                addr: addr2vecu8(&trustee_addr),
+                balance: output.value,
+            };
+            debug!(
+                "[update_trustee_utxos] Add trustee utxo ({:?}, {}), addr:{:?}, balance:{}",
+                hash_rev(txid),
+                index,
+                try_str(&utxo.addr),
+                utxo.balance
+            );
+            insert_trustee_utxo((txid, index as u32), utxo);
+        }
+    }
+}
+
+/// Records the trustee `utxo` and accumulates its value in the inflow of the trustee address.
+///
+/// The flows follow `TrusteeUtxos`, every recorded output counts once in the inflow and once
+/// in the outflow when it's spent.
+pub(crate) fn insert_trustee_utxo(outpoint: (H256, u32), utxo: BtcTrusteeUtxo) {
+    TrusteeAddrFlows::mutate(&utxo.addr, |flow| {
+        flow.inflow = flow.inflow.saturating_add(utxo.balance)
+    });
+    TrusteeUtxos::insert(outpoint, utxo);
+}
+
 fn deposit<T: Trait>(txid: H256, deposit_info: BtcDepositInfo<T::AccountId>) -> BtcTxResult {
     let account_info = match (deposit_info.op_return, deposit_info.input_addr) {
         (Some((account, referral)), Some(input_addr)) => {
@@ -187,70 +263,58 @@ fn insert_pending_deposit<T: Trait>(input_address: &Address, txid: H256, balance
 }
 
 fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
-    if let Some(proposal) = WithdrawalProposal::<T>::take() {
+    let tx_hash = tx.hash();
+    // find the pending proposal which is confirmed by this tx
+    let matched = Module::<T>::proposal_id_by_tx_hash(tx_hash).and_then(|proposal_id| {
+        remove_withdrawal_proposal::<T>(proposal_id).map(|proposal| (proposal_id, proposal))
+    });
+    if let Some((proposal_id, proposal)) = matched {
         native::debug!(
             target: xp_logging::RUNTIME_TARGET,
-            "[withdraw] Withdraw tx {:?}, proposal:{:?}",
-            proposal,
-            tx
+            "[withdraw] Withdraw tx {:?}, proposal_id:{}, proposal:{:?}",
+            tx,
+            proposal_id,
+            proposal
         );
-        let proposal_hash = proposal.tx.hash();
-        let tx_hash = tx.hash();
 
-        if proposal_hash == tx_hash {
-            let mut total = BalanceOf::<T>::zero();
-            for number in proposal.withdrawal_id_list.iter() {
-                // just for event record
-                let withdraw_balance =
-                    xpallet_gateway_records::Module::<T>::pending_withdrawals(number)
-                        .map(|record| record.balance())
-                        .unwrap_or(BalanceOf::<T>::zero());
-                total += withdraw_balance;
+        let mut total = BalanceOf::<T>::zero();
+        for number in proposal.withdrawal_id_list.iter() {
+            // just for event record
+            let withdraw_balance =
+                xpallet_gateway_records::Module::<T>::pending_withdrawals(number)
+                    .map(|record| record.balance())
+                    .unwrap_or(BalanceOf::<T>::zero());
+            total += withdraw_balance;
 
-                match xpallet_gateway_records::Module::<T>::finish_withdrawal(*number, None) {
-                    Ok(_) => {
-                        info!("[withdraw] Withdrawal ({}) completion", *number);
-                    }
-                    Err(err) => {
-                        error!(
-                            "[withdraw] Withdrawal ({}) error:{:?}, must use root to fix it",
-                            *number, err
-                        );
-                    }
+            match xpallet_gateway_records::Module::<T>::finish_withdrawal(*number, None) {
+                Ok(_) => {
+                    info!("[withdraw] Withdrawal ({}) completion", *number);
+                }
+                Err(err) => {
+                    error!(
+                        "[withdraw] Withdrawal ({}) error:{:?}, must use root to fix it",
+                        *number, err
+                    );
                 }
             }
-
-            let btc_withdrawal_fee = Module::<T>::btc_withdrawal_fee();
-            // real withdraw value would reduce withdraw_fee
-            total -=
-                (proposal.withdrawal_id_list.len() as u64 * btc_withdrawal_fee).saturated_into();
-            Module::<T>::deposit_event(Event::<T>::Withdrawn(
-                tx_hash,
-                proposal.withdrawal_id_list,
-                total,
-            ));
-            BtcTxResult::Success
-        } else {
-            error!(
-                "[withdraw] Withdraw error: mismatch (tx_hash:{:?}, proposal_hash:{:?}), id_list:{:?}, must use root to fix it",
-                tx_hash, proposal_hash, proposal.withdrawal_id_list
-            );
-            // re-store proposal into storage.
-            WithdrawalProposal::<T>::put(proposal);
-
-            Module::<T>::deposit_event(Event::<T>::WithdrawalFatalErr(proposal_hash, tx_hash));
-            BtcTxResult::Failure
         }
+
+        let btc_withdrawal_fee = Module::<T>::btc_withdrawal_fee();
+        // real withdraw value would reduce withdraw_fee
+        total -= (proposal.withdrawal_id_list.len() as u64 * btc_withdrawal_fee).saturated_into();
+        Module::<T>::deposit_event(Event::<T>::Withdrawn(
+            tx_hash,
+            proposal.withdrawal_id_list,
+            total,
+        ));
+        BtcTxResult::Success
     } else {
         error!(
-            "[withdraw] Withdrawal error: proposal is EMPTY (tx_hash:{:?}), but receive a withdrawal tx, must use root to fix it",
-            tx.hash()
+            "[withdraw] Withdrawal error: no pending proposal matches the withdrawal tx (tx_hash:{:?}), must use root to fix it",
+            tx_hash
         );
-        // no proposal, but find a withdraw tx, it's a fatal error in withdrawal
-        Module::<T>::deposit_event(Event::<T>::WithdrawalFatalErr(
-            tx.hash(),
-            Default::default(),
-        ));
+        // no matched proposal, but find a withdraw tx, it's a fatal error in withdrawal
+        Module::<T>::deposit_event(Event::<T>::WithdrawalFatalErr(tx_hash, Default::default()));
 
         BtcTxResult::Failure
     }
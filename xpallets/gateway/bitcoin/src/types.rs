@@ -83,6 +83,27 @@ pub struct BtcDepositCache {
     pub balance: u64,
 }
 
+/// An unspent output owned by the trustee hot/cold address.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+pub struct BtcTrusteeUtxo {
+    /// the trustee address (base58) which owns this output
+    pub addr: BtcAddress,
+    /// the value of this output, in satoshi
+    pub balance: u64,
+}
+
+/// The processed BTC flows of a trustee hot/cold address.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Default, RuntimeDebug)]
+pub struct BtcTrusteeAddrFlow {
+    /// total value of the processed outputs paid to this address, in satoshi
+    pub inflow: u64,
+    /// total value of the processed outputs spent from this address, in satoshi
+    pub outflow: u64,
+}
+
+/// The id of a bitcoin withdrawal proposal.
+pub type WithdrawalProposalId = u32;
+
 #[derive(PartialEq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct BtcWithdrawalProposal<AccountId> {
     pub sig_state: VoteResult,
@@ -189,3 +210,18 @@ impl Default for BtcTxVerifier {
         BtcTxVerifier::Recover
     }
 }
+
+/// The storage version of this pallet.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    /// A single pending `WithdrawalProposal`.
+    V1_0_0,
+    /// Several pending `WithdrawalProposals` keyed by the proposal id.
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
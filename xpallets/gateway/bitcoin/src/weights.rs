@@ -41,6 +41,7 @@ pub trait WeightInfo {
     fn force_replace_proposal_tx() -> Weight;
     fn set_btc_withdrawal_fee() -> Weight;
     fn set_btc_deposit_limit() -> Weight;
+    fn force_add_trustee_utxos(n: u32) -> Weight;
 }
 
 /// Weights for xpallet_gateway_bitcoin using the Substrate node and recommended hardware.
@@ -91,6 +92,12 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_btc_deposit_limit() -> Weight {
         (4_570_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn force_add_trustee_utxos(n: u32) -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add((18_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -140,4 +147,10 @@ impl WeightInfo for () {
     fn set_btc_deposit_limit() -> Weight {
         (4_570_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn force_add_trustee_utxos(n: u32) -> Weight {
+        (12_000_000 as Weight)
+            .saturating_add((18_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+    }
 }
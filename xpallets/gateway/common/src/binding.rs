@@ -25,7 +25,10 @@ impl<T: Trait> ReferralBinding<T::AccountId> for Module<T> {
         };
 
         if let Some(name) = referral_name {
-            if let Some(referral) = T::Validator::validator_for(&name) {
+            // The deposits may still refer to the validator by the referral before renaming.
+            let referral = T::Validator::validator_for(&name)
+                .or_else(|| T::Validator::renamed_validator_for(&name));
+            if let Some(referral) = referral {
                 match Self::referral_binding_of(who, chain) {
                     None => {
                         // set to storage
@@ -89,11 +89,14 @@ impl pallet_balances::Trait for Test {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const RegistrationDeposit: Balance = 100;
 }
 impl xpallet_assets_registrar::Trait for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = ();
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
     type WeightInfo = ();
 }
 impl xpallet_assets::Trait for Test {
@@ -103,6 +106,7 @@ impl xpallet_assets::Trait for Test {
     type TreasuryAccount = ();
     type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
     type OnAssetChanged = ();
+    type MaxVestingDelay = ();
     type WeightInfo = ();
 }
 
@@ -129,6 +133,10 @@ impl UnixTime for Timestamp {
         })
     }
 }
+parameter_types! {
+    pub const MaxConcurrentProposals: u32 = 4;
+}
+
 impl xpallet_gateway_bitcoin::Trait for Test {
     type Event = ();
     type UnixTime = Timestamp;
@@ -137,6 +145,7 @@ impl xpallet_gateway_bitcoin::Trait for Test {
     type TrusteeOrigin = EnsureSignedBy<BtcTrusteeMultisig<Test>, AccountId>;
     type ReferralBinding = ();
     type AddressBinding = ();
+    type MaxConcurrentProposals = MaxConcurrentProposals;
     type WeightInfo = ();
 }
 
@@ -156,6 +165,10 @@ impl Validator<AccountId> for AlwaysValidator {
     fn validator_for(_: &[u8]) -> Option<AccountId> {
         None
     }
+
+    fn renamed_validator_for(_: &[u8]) -> Option<AccountId> {
+        None
+    }
 }
 pub struct MockBitcoin<T: xpallet_gateway_bitcoin::Trait>(sp_std::marker::PhantomData<T>);
 impl<T: xpallet_gateway_bitcoin::Trait> ChainT<BalanceOf<T>> for MockBitcoin<T> {
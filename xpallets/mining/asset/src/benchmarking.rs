@@ -2,7 +2,8 @@
 
 pub use frame_benchmarking::{account, benchmarks};
 use frame_system::RawOrigin;
-use xp_protocol::{X_BTC, X_DOT};
+use sp_runtime::Perbill;
+use xp_protocol::{X_BTC, X_DOT, X_ETH};
 
 use super::*;
 
@@ -49,6 +50,46 @@ benchmarks! {
         assert!(Module::<T>::free_balance(&miner) == 90.into());
     }
 
+    claim_all {
+        let n in 1 .. 100;
+
+        if !xpallet_assets_registrar::Module::<T>::exists(&X_ETH) {
+            xpallet_assets_registrar::Module::<T>::register(
+                frame_system::RawOrigin::Root.into(),
+                X_ETH,
+                xpallet_assets_registrar::AssetInfo::new::<T>(
+                    b"X-ETH".to_vec(),
+                    b"Ethereum".to_vec(),
+                    xpallet_assets_registrar::Chain::Ethereum,
+                    18,
+                    b"Ethereum".to_vec(),
+                ).unwrap(),
+                true,
+                true,
+            ).unwrap();
+        }
+
+        let miner: T::AccountId = account("claim_all_miner", n, SEED);
+        xpallet_assets::Module::<T>::issue(&X_ETH, &miner, 1000.into())?;
+
+        let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&X_ETH);
+        <T as xpallet_assets::Trait>::Currency::make_free_balance_be(&reward_pot, 100.into());
+        <T as xpallet_assets::Trait>::Currency::issue(100.into());
+
+        Module::<T>::set_claim_staking_requirement(RawOrigin::Root.into(), X_ETH, 0)?;
+
+        // The rest of miner ledgers belong to the assets without the mining rights.
+        for i in 1..n {
+            MinerLedgers::<T>::insert(&miner, X_ETH + i, MinerLedger::default());
+        }
+
+        let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(block_number + 100.into());
+    }: _(RawOrigin::Signed(miner.clone()), n)
+    verify {
+        assert!(!Module::<T>::free_balance(&miner).is_zero());
+    }
+
     set_claim_staking_requirement {
         let c = 1000;
     }: _(RawOrigin::Root, X_BTC, c)
@@ -69,6 +110,84 @@ benchmarks! {
     verify {
         assert_eq!(FixedAssetPowerOf::get(X_BTC), c);
     }
+
+    set_price_power_params {
+        let params = PricePowerParams {
+            ratio: Perbill::from_percent(10),
+            min_power: 100,
+            max_power: 10_000,
+            max_change_per_era: Perbill::from_percent(5),
+        };
+    }: _(RawOrigin::Root, X_BTC, Some(params.clone()))
+    verify {
+        assert_eq!(PricePowerParamsOf::get(X_BTC), Some(params));
+    }
+
+    claim_liquidity {
+        let pair_id = 0;
+        let params = LiquidityMiningParams {
+            band: Perbill::from_percent(10),
+            power: 1,
+        };
+        Module::<T>::set_liquidity_mining_params(RawOrigin::Root.into(), pair_id, Some(params))?;
+
+        let maker: T::AccountId = account("maker", u, SEED);
+        let current_block = frame_system::Module::<T>::block_number();
+        MakerLedgers::<T>::insert(&maker, pair_id, MakerLedger {
+            liquidity: 1000,
+            last_mining_weight_update: current_block,
+            ..Default::default()
+        });
+        LiquidityLedgers::<T>::insert(pair_id, LiquidityLedger {
+            total_liquidity: 1000,
+            last_total_mining_weight_update: current_block,
+            ..Default::default()
+        });
+
+        let asset_id = Module::<T>::liquidity_mining_asset_id(pair_id);
+        let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&asset_id);
+        <T as xpallet_assets::Trait>::Currency::make_free_balance_be(&reward_pot, 100.into());
+        <T as xpallet_assets::Trait>::Currency::issue(100.into());
+
+        frame_system::Module::<T>::set_block_number(current_block + 100.into());
+    }: _(RawOrigin::Signed(maker.clone()), pair_id)
+    verify {
+        assert!(Module::<T>::free_balance(&maker) == 90.into());
+    }
+
+    refresh_liquidity {
+        let pair_id = 0;
+        let params = LiquidityMiningParams {
+            band: Perbill::from_percent(10),
+            power: 1,
+        };
+        Module::<T>::set_liquidity_mining_params(RawOrigin::Root.into(), pair_id, Some(params))?;
+
+        let caller: T::AccountId = account("caller", u, SEED);
+        let maker: T::AccountId = account("maker", u, SEED);
+        MakerLedgers::<T>::insert(&maker, pair_id, MakerLedger {
+            liquidity: 1000,
+            ..Default::default()
+        });
+        LiquidityLedgers::<T>::insert(pair_id, LiquidityLedger {
+            total_liquidity: 1000,
+            ..Default::default()
+        });
+        let maker_lookup = T::Lookup::unlookup(maker.clone());
+    }: _(RawOrigin::Signed(caller), maker_lookup, pair_id)
+    verify {
+        assert_eq!(MakerLedgers::<T>::get(&maker, pair_id).liquidity, 0);
+    }
+
+    set_liquidity_mining_params {
+        let params = LiquidityMiningParams {
+            band: Perbill::from_percent(10),
+            power: 1,
+        };
+    }: _(RawOrigin::Root, 0, Some(params.clone()))
+    verify {
+        assert_eq!(LiquidityMiningParamsOf::get(0), Some(params));
+    }
 }
 
 #[cfg(test)]
@@ -82,9 +201,14 @@ mod tests {
         ExtBuilder::default().build().execute_with(|| {
             assert_ok!(crate::tests::t_register_xbtc());
             assert_ok!(test_benchmark_claim::<Test>());
+            assert_ok!(test_benchmark_claim_all::<Test>());
             assert_ok!(test_benchmark_set_claim_staking_requirement::<Test>());
             assert_ok!(test_benchmark_set_claim_frequency_limit::<Test>());
             assert_ok!(test_benchmark_set_asset_power::<Test>());
+            assert_ok!(test_benchmark_set_price_power_params::<Test>());
+            assert_ok!(test_benchmark_claim_liquidity::<Test>());
+            assert_ok!(test_benchmark_refresh_liquidity::<Test>());
+            assert_ok!(test_benchmark_set_liquidity_mining_params::<Test>());
         });
     }
 }
@@ -172,7 +172,7 @@ impl<T: Trait> Module<T> {
     /// total_asset_miner_dividend
     ///   ├──> referral(treasury) 10%
     ///   └──> claimer            90%
-    fn allocate_dividend(
+    pub(crate) fn allocate_dividend(
         claimee_reward_pot: &T::AccountId,
         claimer: &T::AccountId,
         claimee: &AssetId,
@@ -194,19 +194,24 @@ impl<T: Trait> Module<T> {
     }
 }
 
-impl<T: Trait> Claim<T::AccountId> for Module<T> {
-    type Claimee = AssetId;
-    type Error = Error<T>;
-
-    fn claim(claimer: &T::AccountId, claimee: &Self::Claimee) -> Result<(), Error<T>> {
-        let current_block = <frame_system::Module<T>>::block_number();
-
+impl<T: Trait> Module<T> {
+    /// Claims the dividend of `claimer` from `claimee` and returns the claimed amount.
+    fn apply_claim(
+        claimer: &T::AccountId,
+        claimee: &AssetId,
+        current_block: T::BlockNumber,
+    ) -> Result<BalanceOf<T>, Error<T>> {
         let ClaimRestriction {
             staking_requirement,
             frequency_limit,
         } = ClaimRestrictionOf::<T>::get(claimee);
 
-        Self::passed_enough_interval(claimer, claimee, frequency_limit, current_block)?;
+        Self::passed_enough_interval(
+            claimer,
+            Self::last_claim(claimer, claimee),
+            frequency_limit,
+            current_block,
+        )?;
 
         let (dividend, source_weight, target_weight, claimee_reward_pot) =
             Self::calculate_dividend_on_claim(claimer, claimee, current_block)?;
@@ -226,6 +231,53 @@ impl<T: Trait> Claim<T::AccountId> for Module<T> {
             miner_ledger.last_claim = Some(current_block);
         });
 
+        Ok(dividend)
+    }
+
+    /// Claims the dividends of `claimer` from all the given `assets`, returns the total amount.
+    ///
+    /// The assets that can not be claimed are skipped with a `ClaimSkipped` event.
+    pub(crate) fn apply_claim_all(claimer: &T::AccountId, assets: Vec<AssetId>) -> BalanceOf<T> {
+        let current_block = <frame_system::Module<T>>::block_number();
+        let previleged_assets = Self::mining_previleged_assets();
+        assets.into_iter().fold(Zero::zero(), |total, asset_id| {
+            let claimed = if !previleged_assets.contains(&asset_id) {
+                Err(ClaimSkipReason::NotPrevilegedAsset)
+            } else if Self::compute_dividend_at(claimer, &asset_id, current_block)
+                .map(|dividend| dividend.is_zero())
+                .unwrap_or(true)
+            {
+                Err(ClaimSkipReason::ZeroDividend)
+            } else {
+                Self::apply_claim(claimer, &asset_id, current_block).map_err(Into::into)
+            };
+            match claimed {
+                Ok(dividend) => {
+                    Self::deposit_event(Event::<T>::Claimed(claimer.clone(), asset_id, dividend));
+                    total + dividend
+                }
+                Err(reason) => {
+                    Self::deposit_event(Event::<T>::ClaimSkipped(
+                        claimer.clone(),
+                        asset_id,
+                        reason,
+                    ));
+                    total
+                }
+            }
+        })
+    }
+}
+
+impl<T: Trait> Claim<T::AccountId> for Module<T> {
+    type Claimee = AssetId;
+    type Error = Error<T>;
+
+    fn claim(claimer: &T::AccountId, claimee: &Self::Claimee) -> Result<(), Error<T>> {
+        let current_block = <frame_system::Module<T>>::block_number();
+
+        let dividend = Self::apply_claim(claimer, claimee, current_block)?;
+
         Self::deposit_event(Event::<T>::Claimed(claimer.clone(), *claimee, dividend));
 
         Ok(())
@@ -290,13 +342,15 @@ impl<T: Trait> xp_mining_staking::AssetMining<BalanceOf<T>> for Module<T> {
         FixedAssetPowerOf::iter()
             .map(|(asset_id, fixed_power)| {
                 let total_issuance = <xpallet_assets::Module<T>>::total_issuance(&asset_id);
+                let power = Self::asset_power_of(&asset_id, fixed_power);
                 (
                     asset_id,
                     total_issuance
-                        .saturating_mul(fixed_power.saturated_into())
+                        .saturating_mul(power.saturated_into())
                         .saturated_into::<MiningPower>(),
                 )
             })
+            .chain(Self::liquidity_mining_power())
             .collect()
     }
 
@@ -306,4 +360,17 @@ impl<T: Trait> xp_mining_staking::AssetMining<BalanceOf<T>> for Module<T> {
         <T as xpallet_assets::Trait>::Currency::deposit_creating(&reward_pot, value);
         Self::deposit_event(Event::<T>::Minted(reward_pot, value));
     }
+
+    /// Recomputes the mining power derived from the price and starts re-measuring
+    /// the liquidity of all the makers at the era boundary.
+    fn on_new_era() {
+        let weight =
+            Self::update_dynamic_asset_powers().saturating_add(Self::start_liquidity_sweep());
+        // The era hook of staking has no weight return, register the cost of
+        // the price derivation and the sweep as the mandatory extra weight.
+        frame_system::Module::<T>::register_extra_weight_unchecked(
+            weight,
+            DispatchClass::Mandatory,
+        );
+    }
 }
@@ -6,6 +6,8 @@
 #![allow(clippy::type_complexity)]
 
 mod impls;
+mod liquidity;
+mod price;
 mod rpc;
 mod types;
 pub mod weights;
@@ -23,11 +25,15 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    storage::IterableStorageMap,
-    traits::{Currency, ExistenceRequirement},
+    storage::{IterableStorageDoubleMap, IterableStorageMap},
+    traits::{Currency, ExistenceRequirement, Get},
+    weights::{DispatchClass, Weight},
 };
 use frame_system::{ensure_root, ensure_signed};
-use sp_runtime::traits::{SaturatedConversion, Zero};
+use sp_runtime::{
+    traits::{SaturatedConversion, StaticLookup, Zero},
+    Perbill,
+};
 
 use chainx_primitives::AssetId;
 use xp_logging::warn;
@@ -36,9 +42,11 @@ use xp_mining_common::{
     ZeroMiningWeightError,
 };
 use xpallet_assets::{AssetType, BalanceOf};
+use xpallet_dex_spot::TradingPairId;
 use xpallet_support::traits::TreasuryAccount;
 
 pub use self::impls::SimpleAssetRewardPotAccountDeterminer;
+pub use self::liquidity::LiquidityMining;
 pub use self::rpc::*;
 pub use self::types::*;
 pub use self::weights::WeightInfo;
@@ -59,6 +67,12 @@ pub trait Trait: xpallet_assets::Trait {
     /// Generate the reward pot account for mining asset.
     type DetermineRewardPotAccount: RewardPotAccountFor<Self::AccountId, AssetId>;
 
+    /// Get the price of mining assets measured in PCX.
+    type PriceFeed: PriceFeed;
+
+    /// Get the liquidity of the makers in Spot.
+    type SpotInterface: SpotInterface<Self::AccountId, u128>;
+
     type WeightInfo: WeightInfo;
 }
 
@@ -93,6 +107,41 @@ impl<AccountId> GatewayInterface<AccountId> for () {
     }
 }
 
+pub trait SpotInterface<AccountId, Balance> {
+    /// Returns the liquidity of `who` in `pair_id` within `band` around the mid price.
+    fn liquidity_of(who: &AccountId, pair_id: TradingPairId, band: Perbill) -> Balance;
+}
+
+impl<AccountId, Balance: Default> SpotInterface<AccountId, Balance> for () {
+    fn liquidity_of(_: &AccountId, _: TradingPairId, _: Perbill) -> Balance {
+        Default::default()
+    }
+}
+
+impl<T: Trait> SpotInterface<<T as frame_system::Trait>::AccountId, u128> for T
+where
+    T: xpallet_dex_spot::Trait,
+{
+    fn liquidity_of(
+        who: &<T as frame_system::Trait>::AccountId,
+        pair_id: TradingPairId,
+        band: Perbill,
+    ) -> u128 {
+        xpallet_dex_spot::Module::<T>::liquidity_in_band(who, pair_id, band).saturated_into()
+    }
+}
+
+pub trait PriceFeed {
+    /// Returns how many smallest units of PCX one smallest unit of `asset_id` is worth.
+    fn native_price_of(asset_id: AssetId) -> Option<u128>;
+}
+
+impl PriceFeed for () {
+    fn native_price_of(_: AssetId) -> Option<u128> {
+        None
+    }
+}
+
 decl_storage! {
     trait Store for Module<T: Trait> as XMiningAsset {
         /// Possible reward for the new asset owners that does not have native coins yet.
@@ -117,6 +166,57 @@ decl_storage! {
         /// Mining power map of X-type assets.
         pub FixedAssetPowerOf get(fn fixed_asset_power_of):
             map hasher(twox_64_concat) AssetId => FixedAssetPower;
+
+        /// Parameters of the assets whose mining power is derived from the price.
+        pub PricePowerParamsOf get(fn price_power_params_of):
+            map hasher(twox_64_concat) AssetId => Option<PricePowerParams>;
+
+        /// Price samples of the assets in the current era.
+        pub PriceAccumulatorOf get(fn price_accumulator_of):
+            map hasher(twox_64_concat) AssetId => PriceAccumulator;
+
+        /// Time-weighted average price of the assets in last era, measured by PCX.
+        pub AveragePriceOf get(fn average_price_of):
+            map hasher(twox_64_concat) AssetId => Option<u128>;
+
+        /// Mining power derived from the price, which overrides `FixedAssetPowerOf`.
+        pub DynamicAssetPowerOf get(fn dynamic_asset_power_of):
+            map hasher(twox_64_concat) AssetId => Option<FixedAssetPower>;
+
+        /// Parameters of the spot trading pairs that have the liquidity mining rights.
+        pub LiquidityMiningParamsOf get(fn liquidity_mining_params_of):
+            map hasher(twox_64_concat) TradingPairId => Option<LiquidityMiningParams>;
+
+        /// Mining weight information of the liquidity of spot trading pairs.
+        pub LiquidityLedgers get(fn liquidity_ledgers):
+            map hasher(twox_64_concat) TradingPairId => LiquidityLedger<MiningWeight, T::BlockNumber>;
+
+        /// The map from maker to the mining weight ledger of all the trading pairs.
+        pub MakerLedgers get(fn maker_ledgers):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) TradingPairId
+            => MakerLedger<MiningWeight, T::BlockNumber>;
+
+        /// The makers whose orders have changed and the liquidity is yet to be refreshed.
+        pub PendingLiquidityRefresh get(fn pending_liquidity_refresh):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) TradingPairId
+            => bool;
+
+        /// Number of the makers having some liquidity in a trading pair.
+        pub LiquidityMakerCount get(fn liquidity_maker_count):
+            map hasher(twox_64_concat) TradingPairId => u32;
+
+        /// The makers having some liquidity given the trading pair ID and the maker index.
+        pub LiquidityMakerAt get(fn liquidity_maker_at):
+            double_map hasher(twox_64_concat) TradingPairId, hasher(twox_64_concat) u32
+            => Option<T::AccountId>;
+
+        /// The index of a maker in `LiquidityMakerAt`.
+        pub LiquidityMakerIndexOf get(fn liquidity_maker_index_of):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) TradingPairId
+            => Option<u32>;
+
+        /// The trading pairs and the number of their makers yet to be re-measured in this era.
+        pub LiquiditySweep get(fn liquidity_sweep): Vec<(TradingPairId, u32)>;
     }
     add_extra_genesis {
         config(claim_restrictions): Vec<(AssetId, (StakingRequirement, T::BlockNumber))>;
@@ -145,6 +245,14 @@ decl_event!(
         Claimed(AccountId, AssetId, Balance),
         /// Issue new balance to the reward pot. [reward_pot_account, amount]
         Minted(AccountId, Balance),
+        /// An asset is skipped when claiming all the mining rewards. [claimer, asset_id, reason]
+        ClaimSkipped(AccountId, AssetId, ClaimSkipReason),
+        /// An asset miner claimed the mining rewards of all the assets. [claimer, total_amount]
+        AllClaimed(AccountId, Balance),
+        /// The mining power of an asset has been derived from the price. [asset_id, power]
+        AssetPowerUpdated(AssetId, FixedAssetPower),
+        /// A maker claimed the liquidity mining reward. [claimer, pair_id, amount]
+        LiquidityClaimed(AccountId, TradingPairId, Balance),
     }
 );
 
@@ -159,6 +267,12 @@ decl_error! {
         UnexpiredFrequencyLimit,
         /// Zero mining weight.
         ZeroMiningWeight,
+        /// The number of mining assets exceeds the witness given by the claimer.
+        InvalidMinerLedgersWitness,
+        /// The lower bound of mining power exceeds the upper bound.
+        InvalidPricePowerParams,
+        /// The trading pair does not have the liquidity mining rights.
+        NotLiquidityMiningPair,
         /// Balances error.
         DispatchError
     }
@@ -176,12 +290,28 @@ impl<T: Trait> From<DispatchError> for Error<T> {
     }
 }
 
+impl<T: Trait> From<Error<T>> for ClaimSkipReason {
+    fn from(e: Error<T>) -> Self {
+        match e {
+            Error::<T>::NotPrevilegedAsset => Self::NotPrevilegedAsset,
+            Error::<T>::UnexpiredFrequencyLimit => Self::UnexpiredFrequencyLimit,
+            Error::<T>::InsufficientStaking => Self::InsufficientStaking,
+            Error::<T>::ZeroMiningWeight => Self::ZeroDividend,
+            _ => Self::TransferFailed,
+        }
+    }
+}
+
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         type Error = Error<T>;
 
         fn deposit_event() = default;
 
+        fn on_initialize() -> Weight {
+            Self::sample_asset_prices().saturating_add(Self::refresh_liquidity_in_batch())
+        }
+
         /// Claims the staking reward given the `target` validator.
         #[weight = <T as Trait>::WeightInfo::claim()]
         fn claim(origin, #[compact] target: AssetId) {
@@ -195,6 +325,27 @@ decl_module! {
             <Self as Claim<T::AccountId>>::claim(&sender, &target)?;
         }
 
+        /// Claims the mining rewards of all the assets the origin account has mined.
+        ///
+        /// The assets that can not be claimed at the moment are skipped, each with a
+        /// `ClaimSkipped` event telling the reason. `miner_ledgers_count` is the witness
+        /// of the number of mining assets of the origin account.
+        #[weight = <T as Trait>::WeightInfo::claim_all(*miner_ledgers_count)]
+        fn claim_all(origin, #[compact] miner_ledgers_count: u32) {
+            let sender = ensure_signed(origin)?;
+
+            let assets = MinerLedgers::<T>::iter_prefix(&sender)
+                .map(|(asset_id, _)| asset_id)
+                .collect::<Vec<_>>();
+            ensure!(
+                assets.len() as u32 <= miner_ledgers_count,
+                Error::<T>::InvalidMinerLedgersWitness
+            );
+
+            let total_dividend = Self::apply_claim_all(&sender, assets);
+            Self::deposit_event(Event::<T>::AllClaimed(sender, total_dividend));
+        }
+
         #[weight = <T as Trait>::WeightInfo::set_claim_staking_requirement()]
         fn set_claim_staking_requirement(origin, #[compact] asset_id: AssetId, #[compact] new: StakingRequirement) {
             ensure_root(origin)?;
@@ -216,6 +367,78 @@ decl_module! {
             ensure_root(origin)?;
             FixedAssetPowerOf::insert(asset_id, new);
         }
+
+        /// Derives the mining power of `asset_id` from its price since next era.
+        ///
+        /// `FixedAssetPowerOf` is used again once the params are removed.
+        #[weight = <T as Trait>::WeightInfo::set_price_power_params()]
+        fn set_price_power_params(origin, #[compact] asset_id: AssetId, params: Option<PricePowerParams>) {
+            ensure_root(origin)?;
+            match params {
+                Some(params) => {
+                    ensure!(params.min_power <= params.max_power, Error::<T>::InvalidPricePowerParams);
+                    PricePowerParamsOf::insert(asset_id, params);
+                }
+                None => {
+                    PricePowerParamsOf::remove(asset_id);
+                    PriceAccumulatorOf::remove(asset_id);
+                    AveragePriceOf::remove(asset_id);
+                    DynamicAssetPowerOf::remove(asset_id);
+                }
+            }
+        }
+
+        /// Claims the liquidity mining reward of the spot trading pair `pair_id`.
+        #[weight = <T as Trait>::WeightInfo::claim_liquidity()
+            .saturating_add(Module::<T>::update_maker_liquidity_weight())]
+        fn claim_liquidity(origin, #[compact] pair_id: TradingPairId) {
+            let sender = ensure_signed(origin)?;
+            <LiquidityMining<T> as Claim<T::AccountId>>::claim(&sender, &pair_id)?;
+        }
+
+        /// Refreshes the liquidity of `maker` in `pair_id` against the latest mid price.
+        ///
+        /// The liquidity is refreshed automatically whenever the orders of maker change
+        /// and at the era boundary, this is for the case of the mid price moving away
+        /// within an era, which can be done by anyone.
+        #[weight = <T as Trait>::WeightInfo::refresh_liquidity()
+            .saturating_add(Module::<T>::update_maker_liquidity_weight())]
+        fn refresh_liquidity(
+            origin,
+            maker: <T::Lookup as StaticLookup>::Source,
+            #[compact] pair_id: TradingPairId
+        ) {
+            ensure_signed(origin)?;
+            let maker = T::Lookup::lookup(maker)?;
+            ensure!(
+                LiquidityMiningParamsOf::contains_key(pair_id),
+                Error::<T>::NotLiquidityMiningPair
+            );
+            let current_block = <frame_system::Module<T>>::block_number();
+            Self::update_maker_liquidity(&maker, pair_id, current_block);
+        }
+
+        /// Sets the liquidity mining parameters of `pair_id`, `None` disables it.
+        #[weight = <T as Trait>::WeightInfo::set_liquidity_mining_params()]
+        fn set_liquidity_mining_params(
+            origin,
+            #[compact] pair_id: TradingPairId,
+            params: Option<LiquidityMiningParams>
+        ) {
+            ensure_root(origin)?;
+            match params {
+                Some(params) => {
+                    if !LiquidityLedgers::<T>::contains_key(pair_id) {
+                        LiquidityLedgers::<T>::insert(pair_id, LiquidityLedger {
+                            last_total_mining_weight_update: <frame_system::Module<T>>::block_number(),
+                            ..Default::default()
+                        });
+                    }
+                    LiquidityMiningParamsOf::insert(pair_id, params);
+                }
+                None => LiquidityMiningParamsOf::remove(pair_id),
+            }
+        }
     }
 }
 
@@ -249,12 +472,12 @@ impl<T: Trait> Module<T> {
     /// This rule doesn't take effect if the interval is zero.
     fn passed_enough_interval(
         who: &T::AccountId,
-        asset_id: &AssetId,
+        last_claim: Option<T::BlockNumber>,
         frequency_limit: T::BlockNumber,
         current_block: T::BlockNumber,
     ) -> Result<(), Error<T>> {
         if !frequency_limit.is_zero() {
-            if let Some(last_claim) = Self::last_claim(who, asset_id) {
+            if let Some(last_claim) = last_claim {
                 if current_block <= last_claim + frequency_limit {
                     warn!(
                         "{:?} can not claim until block {:?}",
@@ -0,0 +1,332 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Liquidity mining of the spot trading pairs.
+//!
+//! The makers accrue the mining weight in proportion to the value of their resting
+//! orders within a band around the mid price, measured by the base currency:
+//!
+//! ```no_compile
+//! liquidity_in_band(Balance) * time(BlockNumber) = liquidity_mining_weight
+//! ```
+//!
+//! Each trading pair is treated as a virtual mining asset `LIQUIDITY_MINING | pair_id`,
+//! which shares the reward distribution, reward pot and claim restriction with the
+//! regular mining assets.
+//!
+//! The liquidity of a maker is re-measured in the following blocks once the orders of
+//! the maker change, when the maker claims, and for all the makers at the era boundary.
+
+use sp_std::marker::PhantomData;
+
+use xp_mining_common::{
+    generic_weight_factors, BaseMiningWeight, Claim, ComputeMiningWeight, Delta, MiningWeight as _,
+    WeightFactors, WeightType,
+};
+use xp_mining_staking::MiningPower;
+use xp_protocol::LIQUIDITY_MINING;
+use xpallet_dex_spot::MAX_OPEN_ORDERS_PER_PAIR;
+
+use super::*;
+
+/// Maximum of the makers whose liquidity is refreshed in `on_initialize`.
+pub const MAX_LIQUIDITY_REFRESH_PER_BLOCK: u32 = 64;
+
+impl<'a, T: Trait> BaseMiningWeight<u128, T::BlockNumber> for LiquidityLedgerWrapper<'a, T> {
+    fn amount(&self) -> u128 {
+        self.inner.total_liquidity
+    }
+
+    fn set_amount(&mut self, new: u128) {
+        self.inner.total_liquidity = new;
+    }
+
+    fn last_acum_weight(&self) -> WeightType {
+        self.inner.last_total_mining_weight
+    }
+
+    fn set_last_acum_weight(&mut self, latest_mining_weight: WeightType) {
+        self.inner.last_total_mining_weight = latest_mining_weight;
+    }
+
+    fn last_acum_weight_update(&self) -> T::BlockNumber {
+        self.inner.last_total_mining_weight_update
+    }
+
+    fn set_last_acum_weight_update(&mut self, current_block: T::BlockNumber) {
+        self.inner.last_total_mining_weight_update = current_block;
+    }
+}
+
+impl<'a, T: Trait> BaseMiningWeight<u128, T::BlockNumber> for MakerLedgerWrapper<'a, T> {
+    fn amount(&self) -> u128 {
+        self.inner.liquidity
+    }
+
+    fn set_amount(&mut self, new: u128) {
+        self.inner.liquidity = new;
+    }
+
+    fn last_acum_weight(&self) -> WeightType {
+        self.inner.last_mining_weight
+    }
+
+    fn set_last_acum_weight(&mut self, latest_mining_weight: WeightType) {
+        self.inner.last_mining_weight = latest_mining_weight;
+    }
+
+    fn last_acum_weight_update(&self) -> T::BlockNumber {
+        self.inner.last_mining_weight_update
+    }
+
+    fn set_last_acum_weight_update(&mut self, current_block: T::BlockNumber) {
+        self.inner.last_mining_weight_update = current_block;
+    }
+}
+
+/// The mining entity of the makers in the spot trading pairs.
+pub struct LiquidityMining<T: Trait>(PhantomData<T>);
+
+impl<T: Trait> ComputeMiningWeight<T::AccountId, T::BlockNumber> for LiquidityMining<T> {
+    type Claimee = TradingPairId;
+    type Error = Error<T>;
+
+    fn claimer_weight_factors(
+        who: &T::AccountId,
+        target: &Self::Claimee,
+        current_block: T::BlockNumber,
+    ) -> WeightFactors {
+        let mut inner = MakerLedgers::<T>::get(who, target);
+        let wrapper = MakerLedgerWrapper::<T>::new(who, target, &mut inner);
+        generic_weight_factors::<u128, T::BlockNumber, _>(wrapper, current_block)
+    }
+
+    fn claimee_weight_factors(
+        target: &Self::Claimee,
+        current_block: T::BlockNumber,
+    ) -> WeightFactors {
+        let mut inner = LiquidityLedgers::<T>::get(target);
+        let wrapper = LiquidityLedgerWrapper::<T>::new(target, &mut inner);
+        generic_weight_factors::<u128, T::BlockNumber, _>(wrapper, current_block)
+    }
+}
+
+impl<T: Trait> Claim<T::AccountId> for LiquidityMining<T> {
+    type Claimee = TradingPairId;
+    type Error = Error<T>;
+
+    fn claim(claimer: &T::AccountId, claimee: &Self::Claimee) -> Result<(), Error<T>> {
+        let current_block = <frame_system::Module<T>>::block_number();
+        let asset_id = Module::<T>::liquidity_mining_asset_id(*claimee);
+
+        let ClaimRestriction {
+            staking_requirement,
+            frequency_limit,
+        } = ClaimRestrictionOf::<T>::get(asset_id);
+
+        Module::<T>::passed_enough_interval(
+            claimer,
+            MakerLedgers::<T>::get(claimer, claimee).last_claim,
+            frequency_limit,
+            current_block,
+        )?;
+
+        // Settle the mining weight against the latest liquidity before claiming.
+        Module::<T>::update_maker_liquidity(claimer, *claimee, current_block);
+
+        let reward_pot = Module::<T>::reward_pot_for(&asset_id);
+        let (dividend, source_weight, target_weight) = Self::compute_dividend(
+            claimer,
+            claimee,
+            current_block,
+            Module::<T>::free_balance(&reward_pot),
+        )?;
+
+        Module::<T>::has_enough_staking(claimer, dividend, staking_requirement)?;
+
+        Module::<T>::allocate_dividend(&reward_pot, claimer, &asset_id, dividend)?;
+
+        MakerLedgers::<T>::mutate(claimer, claimee, |maker_ledger| {
+            let mut wrapper = MakerLedgerWrapper::<T>::new(claimer, claimee, maker_ledger);
+            wrapper.set_state_weight(0, current_block);
+            maker_ledger.last_claim = Some(current_block);
+        });
+        LiquidityLedgers::<T>::mutate(claimee, |liquidity_ledger| {
+            let mut wrapper = LiquidityLedgerWrapper::<T>::new(claimee, liquidity_ledger);
+            wrapper.set_state_weight(target_weight - source_weight, current_block);
+        });
+
+        Module::<T>::deposit_event(Event::<T>::LiquidityClaimed(
+            claimer.clone(),
+            *claimee,
+            dividend,
+        ));
+
+        Ok(())
+    }
+}
+
+impl<T: Trait> xpallet_dex_spot::OnOrderChanged<T::AccountId> for Module<T> {
+    fn on_order_changed(who: &T::AccountId, pair_id: TradingPairId) {
+        if LiquidityMiningParamsOf::contains_key(pair_id) {
+            PendingLiquidityRefresh::<T>::insert(who, pair_id, true);
+        }
+    }
+
+    fn on_order_changed_weight() -> Weight {
+        T::DbWeight::get().reads_writes(1, 1)
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Returns the virtual mining asset id of the liquidity of `pair_id`.
+    pub fn liquidity_mining_asset_id(pair_id: TradingPairId) -> AssetId {
+        LIQUIDITY_MINING | pair_id
+    }
+
+    /// Collects the mining power of the liquidity of all the trading pairs.
+    pub(crate) fn liquidity_mining_power() -> Vec<(AssetId, MiningPower)> {
+        LiquidityMiningParamsOf::iter()
+            .map(|(pair_id, params)| {
+                let total_liquidity = LiquidityLedgers::<T>::get(pair_id).total_liquidity;
+                (
+                    Self::liquidity_mining_asset_id(pair_id),
+                    total_liquidity.saturating_mul(MiningPower::from(params.power)),
+                )
+            })
+            .collect()
+    }
+
+    /// Starts re-measuring the liquidity of all the makers, which is done in the following
+    /// blocks by `refresh_liquidity_in_batch`.
+    pub(crate) fn start_liquidity_sweep() -> Weight {
+        let pairs = LiquidityMiningParamsOf::iter()
+            .map(|(pair_id, _)| (pair_id, Self::liquidity_maker_count(pair_id)))
+            .collect::<Vec<_>>();
+        let weight = T::DbWeight::get().reads_writes(pairs.len() as u64 * 2, 1);
+        let sweep = pairs
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .collect::<Vec<_>>();
+        LiquiditySweep::put(sweep);
+        weight
+    }
+
+    /// Refreshes the liquidity of at most `MAX_LIQUIDITY_REFRESH_PER_BLOCK` makers, the
+    /// makers whose orders have changed go first, then the ones of the ongoing sweep.
+    pub(crate) fn refresh_liquidity_in_batch() -> Weight {
+        let current_block = <frame_system::Module<T>>::block_number();
+
+        let pending = PendingLiquidityRefresh::<T>::drain()
+            .take(MAX_LIQUIDITY_REFRESH_PER_BLOCK as usize)
+            .collect::<Vec<_>>();
+        for (maker, pair_id, _) in pending.iter() {
+            Self::update_maker_liquidity(maker, *pair_id, current_block);
+        }
+        let mut refreshed = pending.len() as u32;
+
+        let mut sweep = Self::liquidity_sweep();
+        let sweeping = !sweep.is_empty();
+        // The makers are visited in the descending order of the index, so that none of
+        // them is skipped when a maker is swapped out of the index in the meantime.
+        while refreshed < MAX_LIQUIDITY_REFRESH_PER_BLOCK {
+            let (pair_id, remaining) = match sweep.last_mut() {
+                Some(entry) => {
+                    if entry.1 == 0 {
+                        sweep.pop();
+                        continue;
+                    }
+                    entry.1 -= 1;
+                    *entry
+                }
+                None => break,
+            };
+            if let Some(maker) = Self::liquidity_maker_at(pair_id, remaining) {
+                Self::update_maker_liquidity(&maker, pair_id, current_block);
+            }
+            refreshed += 1;
+        }
+        if sweeping {
+            LiquiditySweep::put(sweep);
+        }
+
+        let refresh_weight = <T as Trait>::WeightInfo::refresh_liquidity()
+            .saturating_add(Self::update_maker_liquidity_weight());
+        T::DbWeight::get()
+            .reads_writes(2, 1)
+            .saturating_add(refresh_weight.saturating_mul(refreshed.into()))
+    }
+
+    /// Returns the weight of `update_maker_liquidity` on top of the benchmarked one, which
+    /// reads at most `MAX_OPEN_ORDERS_PER_PAIR` open orders and updates the maker index.
+    pub(crate) fn update_maker_liquidity_weight() -> Weight {
+        T::DbWeight::get().reads_writes(MAX_OPEN_ORDERS_PER_PAIR as Weight + 3, 3)
+    }
+
+    /// Settles the mining weights and then refreshes the liquidity of `maker` in `pair_id`.
+    pub(crate) fn update_maker_liquidity(
+        maker: &T::AccountId,
+        pair_id: TradingPairId,
+        current_block: T::BlockNumber,
+    ) {
+        let params = match Self::liquidity_mining_params_of(pair_id) {
+            Some(params) => params,
+            None => return,
+        };
+
+        let mut maker_ledger = MakerLedgers::<T>::get(maker, pair_id);
+        let old_liquidity = maker_ledger.liquidity;
+        let new_liquidity = T::SpotInterface::liquidity_of(maker, pair_id, params.band);
+        let delta = if new_liquidity > old_liquidity {
+            Delta::Add(new_liquidity - old_liquidity)
+        } else if new_liquidity < old_liquidity {
+            Delta::Sub(old_liquidity - new_liquidity)
+        } else {
+            return;
+        };
+
+        let maker_weight =
+            LiquidityMining::<T>::settle_claimer_weight(maker, &pair_id, current_block);
+        let total_weight = LiquidityMining::<T>::settle_claimee_weight(&pair_id, current_block);
+
+        let mut wrapper = MakerLedgerWrapper::<T>::new(maker, &pair_id, &mut maker_ledger);
+        wrapper.set_state(maker_weight, current_block, &delta);
+        MakerLedgers::<T>::insert(maker, pair_id, maker_ledger);
+
+        let mut liquidity_ledger = LiquidityLedgers::<T>::get(pair_id);
+        let mut wrapper = LiquidityLedgerWrapper::<T>::new(&pair_id, &mut liquidity_ledger);
+        wrapper.set_state(total_weight, current_block, &delta);
+        LiquidityLedgers::<T>::insert(pair_id, liquidity_ledger);
+
+        if old_liquidity.is_zero() {
+            Self::add_liquidity_maker(maker, pair_id);
+        } else if new_liquidity.is_zero() {
+            Self::remove_liquidity_maker(maker, pair_id);
+        }
+    }
+
+    fn add_liquidity_maker(maker: &T::AccountId, pair_id: TradingPairId) {
+        if LiquidityMakerIndexOf::<T>::contains_key(maker, pair_id) {
+            return;
+        }
+        let index = Self::liquidity_maker_count(pair_id);
+        LiquidityMakerAt::<T>::insert(pair_id, index, maker);
+        LiquidityMakerIndexOf::<T>::insert(maker, pair_id, index);
+        LiquidityMakerCount::insert(pair_id, index + 1);
+    }
+
+    fn remove_liquidity_maker(maker: &T::AccountId, pair_id: TradingPairId) {
+        let index = match LiquidityMakerIndexOf::<T>::take(maker, pair_id) {
+            Some(index) => index,
+            None => return,
+        };
+        let last = Self::liquidity_maker_count(pair_id).saturating_sub(1);
+        if index != last {
+            if let Some(last_maker) = LiquidityMakerAt::<T>::get(pair_id, last) {
+                LiquidityMakerAt::<T>::insert(pair_id, index, &last_maker);
+                LiquidityMakerIndexOf::<T>::insert(&last_maker, pair_id, index);
+            }
+        }
+        LiquidityMakerAt::<T>::remove(pair_id, last);
+        LiquidityMakerCount::insert(pair_id, last);
+    }
+}
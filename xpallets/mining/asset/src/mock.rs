@@ -2,7 +2,7 @@
 
 use std::{
     cell::RefCell,
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
 };
 
 use frame_support::{
@@ -10,7 +10,7 @@ use frame_support::{
 };
 use sp_core::H256;
 use sp_runtime::{
-    testing::{Header, UintAuthorityId},
+    testing::{Header, TestXt, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
     Perbill,
 };
@@ -129,11 +129,14 @@ impl pallet_balances::Trait for Test {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const RegistrationDeposit: Balance = 100;
 }
 impl xpallet_assets_registrar::Trait for Test {
     type Event = MetaEvent;
     type NativeAssetId = ChainXAssetId;
     type RegistrarHandler = XMiningAsset;
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
     type WeightInfo = ();
 }
 
@@ -144,6 +147,7 @@ impl xpallet_assets::Trait for Test {
     type TreasuryAccount = ();
     type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
     type OnAssetChanged = XMiningAsset;
+    type MaxVestingDelay = ();
     type WeightInfo = ();
 }
 
@@ -257,6 +261,13 @@ impl xp_mining_common::RewardPotAccountFor<AccountId, AccountId>
     }
 }
 
+pub type StakingExtrinsic = TestXt<xpallet_mining_staking::Call<Test>, ()>;
+
+impl frame_system::offchain::SendTransactionTypes<xpallet_mining_staking::Call<Test>> for Test {
+    type OverarchingCall = xpallet_mining_staking::Call<Test>;
+    type Extrinsic = StakingExtrinsic;
+}
+
 impl xpallet_mining_staking::Trait for Test {
     type Currency = Balances;
     type Event = MetaEvent;
@@ -289,12 +300,35 @@ impl GatewayInterface<AccountId> for DummyGatewayReferralGetter {
     }
 }
 
+pub struct DummySpot;
+
+impl SpotInterface<AccountId, u128> for DummySpot {
+    fn liquidity_of(who: &AccountId, pair_id: TradingPairId, _: Perbill) -> u128 {
+        MAKER_LIQUIDITY.with(|v| {
+            v.borrow()
+                .get(&(*who, pair_id))
+                .copied()
+                .unwrap_or_default()
+        })
+    }
+}
+
+pub struct DummyPriceFeed;
+
+impl PriceFeed for DummyPriceFeed {
+    fn native_price_of(_: AssetId) -> Option<u128> {
+        NATIVE_PRICE.with(|v| *v.borrow())
+    }
+}
+
 impl Trait for Test {
     type StakingInterface = Self;
     type GatewayInterface = DummyGatewayReferralGetter;
     type Event = MetaEvent;
     type TreasuryAccount = ();
     type DetermineRewardPotAccount = DummyAssetRewardPotAccountDeterminer;
+    type PriceFeed = DummyPriceFeed;
+    type SpotInterface = DummySpot;
     type WeightInfo = ();
 }
 
@@ -305,6 +339,8 @@ thread_local! {
     static ELECTION_LOOKAHEAD: RefCell<BlockNumber> = RefCell::new(0);
     static PERIOD: RefCell<BlockNumber> = RefCell::new(1);
     static MAX_ITERATIONS: RefCell<u32> = RefCell::new(0);
+    pub static NATIVE_PRICE: RefCell<Option<u128>> = RefCell::new(None);
+    pub static MAKER_LIQUIDITY: RefCell<HashMap<(AccountId, TradingPairId), u128>> = RefCell::new(Default::default());
 }
 
 pub struct ExtBuilder {
@@ -0,0 +1,83 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_runtime::PerThing;
+use sp_std::cmp;
+
+use super::*;
+
+impl<T: Trait> Module<T> {
+    /// Samples the price of each asset whose mining power is derived from the price.
+    pub(crate) fn sample_asset_prices() -> Weight {
+        let mut sampled = 0u64;
+        let assets = PricePowerParamsOf::iter()
+            .map(|(asset_id, _)| asset_id)
+            .collect::<Vec<_>>();
+        for asset_id in assets.iter() {
+            if let Some(price) = T::PriceFeed::native_price_of(*asset_id) {
+                PriceAccumulatorOf::mutate(asset_id, |accumulator| {
+                    accumulator.cumulative_price =
+                        accumulator.cumulative_price.saturating_add(price);
+                    accumulator.samples = accumulator.samples.saturating_add(1);
+                });
+                sampled += 1;
+            }
+        }
+        T::DbWeight::get().reads_writes(assets.len() as u64 * 2, sampled)
+    }
+
+    /// Derives the mining power of the assets from the time-weighted average price of last era.
+    ///
+    /// The mining power stays the same if no price has been sampled in last era.
+    pub(crate) fn update_dynamic_asset_powers() -> Weight {
+        let mut assets = 0u64;
+        let mut updated = 0u64;
+        for (asset_id, params) in PricePowerParamsOf::iter() {
+            assets += 1;
+            let average_price = match PriceAccumulatorOf::take(asset_id).average() {
+                Some(average_price) => average_price,
+                None => continue,
+            };
+            updated += 1;
+            AveragePriceOf::insert(asset_id, average_price);
+            let previous = Self::dynamic_asset_power_of(asset_id)
+                .unwrap_or_else(|| Self::fixed_asset_power_of(asset_id));
+            let new_power = Self::smooth_asset_power(&params, previous, average_price);
+            DynamicAssetPowerOf::insert(asset_id, new_power);
+            Self::deposit_event(Event::<T>::AssetPowerUpdated(asset_id, new_power));
+        }
+        T::DbWeight::get().reads_writes(assets * 2 + updated * 2, assets + updated * 2)
+    }
+
+    /// Returns the mining power bounded by `params` given the previous power and the price.
+    ///
+    /// The maximum change per era does not apply if the previous power is zero.
+    pub(crate) fn smooth_asset_power(
+        params: &PricePowerParams,
+        previous: FixedAssetPower,
+        average_price: u128,
+    ) -> FixedAssetPower {
+        let target = params
+            .ratio
+            .mul_floor(average_price)
+            .saturated_into::<FixedAssetPower>();
+        let smoothed = if previous.is_zero() {
+            target
+        } else {
+            let max_change = params.max_change_per_era.mul_ceil(previous);
+            if target > previous {
+                cmp::min(target, previous.saturating_add(max_change))
+            } else {
+                cmp::max(target, previous.saturating_sub(max_change))
+            }
+        };
+        cmp::min(cmp::max(smoothed, params.min_power), params.max_power)
+    }
+
+    /// Returns the mining power of `asset_id` per smallest unit.
+    pub(crate) fn asset_power_of(
+        asset_id: &AssetId,
+        fixed_power: FixedAssetPower,
+    ) -> FixedAssetPower {
+        Self::dynamic_asset_power_of(asset_id).unwrap_or(fixed_power)
+    }
+}
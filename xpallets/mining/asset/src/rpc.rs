@@ -50,7 +50,8 @@ impl<T: Trait> Module<T> {
         MiningPrevilegedAssets::get()
             .into_iter()
             .map(|asset_id| {
-                let mining_power = FixedAssetPowerOf::get(asset_id);
+                let mining_power =
+                    Self::asset_power_of(&asset_id, FixedAssetPowerOf::get(asset_id));
                 let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&asset_id);
                 let reward_pot_balance: BalanceOf<T> = Self::free_balance(&reward_pot);
                 let ledger_info: AssetLedger<MiningWeight, T::BlockNumber> =
@@ -5,8 +5,9 @@ use frame_support::{
     traits::{Get, OnInitialize},
 };
 use frame_system::RawOrigin;
+use sp_runtime::Perbill;
 
-use xp_mining_staking::SessionIndex;
+use xp_mining_staking::{AssetMining, SessionIndex};
 use xp_protocol::X_BTC;
 
 use super::*;
@@ -449,3 +450,281 @@ fn asset_mining_reward_should_work() {
         assert_eq!(Balances::free_balance(&TREASURY_ACCOUNT), treasury_balance);
     });
 }
+
+fn t_has_mining_asset_event(event: Event<Test>) -> bool {
+    System::events()
+        .iter()
+        .any(|record| record.event == MetaEvent::mining_asset(event.clone()))
+}
+
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_register_xbtc());
+        let t_1 = 777;
+        assert_ok!(t_issue_xbtc(t_1, 100));
+
+        let reward_pot = DummyAssetRewardPotAccountDeterminer::reward_pot_account_for(&X_BTC);
+        Balances::make_free_balance_be(&reward_pot, 1000);
+
+        // A stale miner ledger of some asset without the mining rights.
+        let unprevileged = X_BTC + 1;
+        MinerLedgers::<Test>::insert(t_1, unprevileged, MinerLedger::default());
+
+        t_system_block_number_inc(1);
+
+        assert_err!(
+            XMiningAsset::claim_all(Origin::signed(t_1), 1),
+            Error::<Test>::InvalidMinerLedgersWitness
+        );
+
+        // The genesis staking requirement of X-BTC is 7.
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1), 2));
+        assert!(t_has_mining_asset_event(Event::<Test>::ClaimSkipped(
+            t_1,
+            unprevileged,
+            ClaimSkipReason::NotPrevilegedAsset
+        )));
+        assert!(t_has_mining_asset_event(Event::<Test>::ClaimSkipped(
+            t_1,
+            X_BTC,
+            ClaimSkipReason::InsufficientStaking
+        )));
+        assert!(t_has_mining_asset_event(Event::<Test>::AllClaimed(t_1, 0)));
+        assert_eq!(Balances::free_balance(&t_1), 0);
+
+        t_xbtc_set_claim_staking_requirement(0);
+        t_xbtc_set_claim_frequency_limit(2);
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1), 2));
+        assert!(t_has_mining_asset_event(Event::<Test>::Claimed(
+            t_1, X_BTC, 1000
+        )));
+        assert!(t_has_mining_asset_event(Event::<Test>::AllClaimed(
+            t_1, 1000
+        )));
+        assert_eq!(Balances::free_balance(&t_1), 900);
+        assert_xbtc_reward_pot_balance(0);
+
+        // The reward pot has been drained.
+        t_system_block_number_inc(1);
+        System::reset_events();
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1), 2));
+        assert!(t_has_mining_asset_event(Event::<Test>::ClaimSkipped(
+            t_1,
+            X_BTC,
+            ClaimSkipReason::ZeroDividend
+        )));
+
+        Balances::make_free_balance_be(&reward_pot, 1000);
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1), 2));
+        assert!(t_has_mining_asset_event(Event::<Test>::ClaimSkipped(
+            t_1,
+            X_BTC,
+            ClaimSkipReason::UnexpiredFrequencyLimit
+        )));
+        assert_eq!(Balances::free_balance(&t_1), 900);
+    });
+}
+
+#[test]
+fn price_derived_asset_power_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_register_xbtc());
+        assert_ok!(t_issue_xbtc(777, 10));
+        t_set_xbtc_asset_power(100);
+
+        let params = PricePowerParams {
+            ratio: Perbill::from_percent(50),
+            min_power: 10,
+            max_power: 1000,
+            max_change_per_era: Perbill::from_percent(20),
+        };
+        assert_err!(
+            XMiningAsset::set_price_power_params(
+                Origin::root(),
+                X_BTC,
+                Some(PricePowerParams {
+                    min_power: 1001,
+                    ..params.clone()
+                })
+            ),
+            Error::<Test>::InvalidPricePowerParams
+        );
+        assert_ok!(XMiningAsset::set_price_power_params(
+            Origin::root(),
+            X_BTC,
+            Some(params.clone())
+        ));
+
+        // No price is available.
+        XMiningAsset::on_initialize(System::block_number());
+        for price in [1000u128, 3000].iter() {
+            NATIVE_PRICE.with(|v| *v.borrow_mut() = Some(*price));
+            XMiningAsset::on_initialize(System::block_number());
+        }
+        assert_eq!(
+            XMiningAsset::price_accumulator_of(X_BTC),
+            PriceAccumulator {
+                cumulative_price: 4000,
+                samples: 2
+            }
+        );
+
+        // New era, average price: 2000, target power: 1000, which is limited to 100 + 20%.
+        t_start_session(3);
+        assert_eq!(XMiningAsset::dynamic_asset_power_of(X_BTC), Some(120));
+        assert_eq!(XMiningAsset::average_price_of(X_BTC), Some(2000));
+        assert_eq!(
+            XMiningAsset::price_accumulator_of(X_BTC),
+            PriceAccumulator::default()
+        );
+        assert_eq!(XMiningAsset::asset_mining_power(), vec![(X_BTC, 10 * 120)]);
+
+        // Bounded by the min/max power.
+        assert_eq!(
+            XMiningAsset::smooth_asset_power(&params, 0, 1_000_000),
+            1000
+        );
+        assert_eq!(XMiningAsset::smooth_asset_power(&params, 120, 0), 96);
+        assert_eq!(XMiningAsset::smooth_asset_power(&params, 11, 0), 10);
+
+        assert_ok!(XMiningAsset::set_price_power_params(
+            Origin::root(),
+            X_BTC,
+            None
+        ));
+        assert_eq!(XMiningAsset::dynamic_asset_power_of(X_BTC), None);
+        assert_eq!(XMiningAsset::average_price_of(X_BTC), None);
+        assert_eq!(XMiningAsset::asset_mining_power(), vec![(X_BTC, 10 * 100)]);
+    });
+}
+
+fn t_set_maker_liquidity(who: AccountId, pair_id: TradingPairId, liquidity: u128) {
+    MAKER_LIQUIDITY.with(|v| v.borrow_mut().insert((who, pair_id), liquidity));
+}
+
+#[test]
+fn liquidity_mining_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        use xpallet_dex_spot::OnOrderChanged;
+
+        let pair_id = 0;
+        assert_ok!(XMiningAsset::set_liquidity_mining_params(
+            Origin::root(),
+            pair_id,
+            Some(LiquidityMiningParams {
+                band: Perbill::from_percent(10),
+                power: 2,
+            })
+        ));
+
+        t_set_maker_liquidity(1, pair_id, 100);
+        t_set_maker_liquidity(2, pair_id, 300);
+        XMiningAsset::on_order_changed(&1, pair_id);
+        XMiningAsset::on_order_changed(&2, pair_id);
+        assert!(XMiningAsset::pending_liquidity_refresh(1, pair_id));
+        assert_eq!(XMiningAsset::liquidity_ledgers(pair_id).total_liquidity, 0);
+
+        XMiningAsset::on_initialize(System::block_number());
+        assert!(!XMiningAsset::pending_liquidity_refresh(1, pair_id));
+        assert_eq!(XMiningAsset::liquidity_maker_count(pair_id), 2);
+
+        let asset_id = XMiningAsset::liquidity_mining_asset_id(pair_id);
+        assert_eq!(
+            XMiningAsset::liquidity_ledgers(pair_id).total_liquidity,
+            400
+        );
+        assert_eq!(
+            XMiningAsset::asset_mining_power(),
+            vec![(X_BTC, 0), (asset_id, 800)]
+        );
+
+        let reward_pot = DummyAssetRewardPotAccountDeterminer::reward_pot_account_for(&asset_id);
+        Balances::make_free_balance_be(&reward_pot, 1000);
+
+        // Block 11
+        t_system_block_number_inc(10);
+        assert_ok!(XMiningAsset::claim_liquidity(Origin::signed(1), pair_id));
+        // 1000 * 100 / 400 = 250
+        assert_eq!(Balances::free_balance(&1), 100 + 225);
+        assert_eq!(Balances::free_balance(&reward_pot), 750);
+
+        // The orders of maker 2 are out of band now.
+        t_set_maker_liquidity(2, pair_id, 0);
+        assert_err!(
+            XMiningAsset::refresh_liquidity(Origin::signed(3), 2, pair_id + 1),
+            Error::<Test>::NotLiquidityMiningPair
+        );
+        assert_ok!(XMiningAsset::refresh_liquidity(
+            Origin::signed(3),
+            2,
+            pair_id
+        ));
+        assert_eq!(XMiningAsset::maker_ledgers(2, pair_id).liquidity, 0);
+        assert_eq!(
+            XMiningAsset::liquidity_ledgers(pair_id).total_liquidity,
+            100
+        );
+        assert_eq!(XMiningAsset::liquidity_maker_count(pair_id), 1);
+        assert_eq!(XMiningAsset::liquidity_maker_at(pair_id, 0), Some(1));
+
+        // Block 21
+        t_system_block_number_inc(10);
+        assert_ok!(XMiningAsset::claim_liquidity(Origin::signed(2), pair_id));
+        // 750 * (300 * 10) / (300 * 10 + 100 * 10) = 562
+        assert_eq!(Balances::free_balance(&2), 200 + 562 - 56);
+    });
+}
+
+#[test]
+fn liquidity_should_be_remeasured_at_claim_and_era_boundary() {
+    ExtBuilder::default().build_and_execute(|| {
+        use xpallet_dex_spot::OnOrderChanged;
+
+        let pair_id = 0;
+        assert_ok!(XMiningAsset::set_liquidity_mining_params(
+            Origin::root(),
+            pair_id,
+            Some(LiquidityMiningParams {
+                band: Perbill::from_percent(10),
+                power: 2,
+            })
+        ));
+
+        for maker in 1..=3 {
+            t_set_maker_liquidity(maker, pair_id, 100);
+            XMiningAsset::on_order_changed(&maker, pair_id);
+        }
+        XMiningAsset::on_initialize(System::block_number());
+        assert_eq!(
+            XMiningAsset::liquidity_ledgers(pair_id).total_liquidity,
+            300
+        );
+
+        // The mid price moves without any change of the orders.
+        t_set_maker_liquidity(1, pair_id, 0);
+        t_set_maker_liquidity(2, pair_id, 0);
+        t_set_maker_liquidity(3, pair_id, 50);
+
+        // Block 11
+        t_system_block_number_inc(10);
+        let asset_id = XMiningAsset::liquidity_mining_asset_id(pair_id);
+        let reward_pot = DummyAssetRewardPotAccountDeterminer::reward_pot_account_for(&asset_id);
+        Balances::make_free_balance_be(&reward_pot, 1000);
+        assert_ok!(XMiningAsset::claim_liquidity(Origin::signed(3), pair_id));
+        assert_eq!(XMiningAsset::maker_ledgers(3, pair_id).liquidity, 50);
+        assert_eq!(XMiningAsset::maker_ledgers(1, pair_id).liquidity, 100);
+
+        XMiningAsset::on_new_era();
+        assert_eq!(XMiningAsset::liquidity_sweep(), vec![(pair_id, 3)]);
+        XMiningAsset::on_initialize(System::block_number());
+        assert!(XMiningAsset::liquidity_sweep().is_empty());
+
+        assert_eq!(XMiningAsset::maker_ledgers(1, pair_id).liquidity, 0);
+        assert_eq!(XMiningAsset::maker_ledgers(2, pair_id).liquidity, 0);
+        assert_eq!(XMiningAsset::liquidity_ledgers(pair_id).total_liquidity, 50);
+        assert_eq!(XMiningAsset::liquidity_maker_count(pair_id), 1);
+        assert_eq!(XMiningAsset::liquidity_maker_at(pair_id, 0), Some(3));
+        assert_eq!(XMiningAsset::liquidity_maker_index_of(3, pair_id), Some(0));
+    });
+}
@@ -3,12 +3,12 @@
 use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{Perbill, RuntimeDebug};
 
 use chainx_primitives::AssetId;
 use xp_mining_common::WeightType;
 
-use crate::Trait;
+use crate::{TradingPairId, Trait};
 
 pub type MiningWeight = WeightType;
 pub type FixedAssetPower = u32;
@@ -85,3 +85,133 @@ pub struct ClaimRestriction<BlockNumber> {
     /// Claimer can only claim once per `frequency_limit`.
     pub frequency_limit: BlockNumber,
 }
+
+/// Parameters of deriving the mining power of an asset from its price.
+///
+/// The mining power of an asset stands for how many PCX one smallest unit of
+/// the asset is counted as in terms of the mining power, which is derived from
+/// the time-weighted average price of each era and then smoothed and bounded.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PricePowerParams {
+    /// Ratio of the PCX-equivalent value of the asset counted as the mining power.
+    pub ratio: Perbill,
+    /// Lower bound of the mining power.
+    pub min_power: FixedAssetPower,
+    /// Upper bound of the mining power.
+    pub max_power: FixedAssetPower,
+    /// Maximum change of the mining power per era, relative to the previous power.
+    pub max_change_per_era: Perbill,
+}
+
+/// Accumulated price samples of an asset in the current era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PriceAccumulator {
+    /// Sum of the prices sampled at each block.
+    pub cumulative_price: u128,
+    /// Number of the sampled blocks.
+    pub samples: u32,
+}
+
+impl PriceAccumulator {
+    /// Returns the time-weighted average price if there is any sample.
+    pub fn average(&self) -> Option<u128> {
+        if self.samples == 0 {
+            None
+        } else {
+            Some(self.cumulative_price / u128::from(self.samples))
+        }
+    }
+}
+
+/// The reason why an asset is skipped in `claim_all`.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ClaimSkipReason {
+    /// The asset no longer has the mining rights.
+    NotPrevilegedAsset,
+    /// The frequency limit of the asset has not expired yet.
+    UnexpiredFrequencyLimit,
+    /// The claimer does not have enough Staking locked balance.
+    InsufficientStaking,
+    /// There is nothing to claim.
+    ZeroDividend,
+    /// Failed to transfer the dividend from the reward pot.
+    TransferFailed,
+}
+
+/// Parameters of the liquidity mining of a spot trading pair.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LiquidityMiningParams {
+    /// Only the resting orders whose price lies within `band` around the mid price count.
+    pub band: Perbill,
+    /// Mining power per unit of the liquidity, measured by the base currency.
+    pub power: FixedAssetPower,
+}
+
+/// Mining weight properties of the liquidity of a spot trading pair.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LiquidityLedger<MiningWeight, BlockNumber> {
+    /// Total liquidity of all the makers in the band.
+    pub total_liquidity: u128,
+    /// Last calculated total mining weight of the trading pair.
+    pub last_total_mining_weight: MiningWeight,
+    /// Block number at which point `last_total_mining_weight` just updated.
+    pub last_total_mining_weight_update: BlockNumber,
+}
+
+pub struct LiquidityLedgerWrapper<'a, T: Trait> {
+    pub pair_id: &'a TradingPairId,
+    pub inner: &'a mut LiquidityLedger<MiningWeight, T::BlockNumber>,
+}
+
+impl<'a, T: Trait> LiquidityLedgerWrapper<'a, T> {
+    pub fn new(
+        pair_id: &'a TradingPairId,
+        inner: &'a mut LiquidityLedger<MiningWeight, T::BlockNumber>,
+    ) -> Self {
+        Self { pair_id, inner }
+    }
+}
+
+/// Mining weight properties of the makers of a spot trading pair.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MakerLedger<MiningWeight, BlockNumber> {
+    /// Liquidity of the maker in the band since the last update.
+    pub liquidity: u128,
+    /// Last calculated mining weight of the maker.
+    pub last_mining_weight: MiningWeight,
+    /// Block number at which point `last_mining_weight` just updated.
+    pub last_mining_weight_update: BlockNumber,
+    /// Block number at which point the maker claimed last time.
+    pub last_claim: Option<BlockNumber>,
+}
+
+pub struct MakerLedgerWrapper<'a, T: Trait> {
+    pub maker: &'a T::AccountId,
+    pub pair_id: &'a TradingPairId,
+    pub inner: &'a mut MakerLedger<MiningWeight, T::BlockNumber>,
+}
+
+impl<'a, T: Trait> MakerLedgerWrapper<'a, T> {
+    pub fn new(
+        maker: &'a T::AccountId,
+        pair_id: &'a TradingPairId,
+        inner: &'a mut MakerLedger<MiningWeight, T::BlockNumber>,
+    ) -> Self {
+        Self {
+            maker,
+            pair_id,
+            inner,
+        }
+    }
+}
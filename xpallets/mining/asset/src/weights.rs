@@ -31,9 +31,14 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for xpallet_mining_asset.
 pub trait WeightInfo {
     fn claim() -> Weight;
+    fn claim_all(n: u32) -> Weight;
     fn set_claim_staking_requirement() -> Weight;
     fn set_claim_frequency_limit() -> Weight;
     fn set_asset_power() -> Weight;
+    fn set_price_power_params() -> Weight;
+    fn claim_liquidity() -> Weight;
+    fn refresh_liquidity() -> Weight;
+    fn set_liquidity_mining_params() -> Weight;
 }
 
 /// Weights for xpallet_mining_asset using the Substrate node and recommended hardware.
@@ -44,6 +49,13 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(12 as Weight))
             .saturating_add(T::DbWeight::get().writes(5 as Weight))
     }
+    fn claim_all(n: u32) -> Weight {
+        (20_914_000 as Weight)
+            .saturating_add((226_475_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+    }
     fn set_claim_staking_requirement() -> Weight {
         (11_222_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
@@ -57,6 +69,24 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_asset_power() -> Weight {
         (5_538_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_price_power_params() -> Weight {
+        (14_271_000 as Weight).saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn claim_liquidity() -> Weight {
+        (265_841_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn refresh_liquidity() -> Weight {
+        (78_306_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_liquidity_mining_params() -> Weight {
+        (17_930_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -66,6 +96,13 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(12 as Weight))
             .saturating_add(RocksDbWeight::get().writes(5 as Weight))
     }
+    fn claim_all(n: u32) -> Weight {
+        (20_914_000 as Weight)
+            .saturating_add((226_475_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+    }
     fn set_claim_staking_requirement() -> Weight {
         (11_222_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
@@ -79,4 +116,22 @@ impl WeightInfo for () {
     fn set_asset_power() -> Weight {
         (5_538_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_price_power_params() -> Weight {
+        (14_271_000 as Weight).saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn claim_liquidity() -> Weight {
+        (265_841_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn refresh_liquidity() -> Weight {
+        (78_306_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_liquidity_mining_params() -> Weight {
+        (17_930_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
 }
@@ -10,11 +10,16 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use codec::Codec;
 
 pub use xpallet_mining_staking::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger, VoteWeight,
+    EraIndex, NominatorInfo, NominatorLedger, SessionIndex, UnappliedSlash, Unbonded,
+    ValidatorEraRecord, ValidatorInfo, ValidatorLedger, VoteWeight,
 };
 
 sp_api::decl_runtime_apis! {
     /// The API to query Staking info.
+    ///
+    /// Version 2: `commission` and `last_commission_change` are added to `ValidatorProfile`,
+    /// and `emission_projection`, `unapplied_slashes` and `validator_history` are added.
+    #[api_version(2)]
     pub trait XStakingApi<AccountId, Balance, VoteWeight, BlockNumber>
     where
         AccountId: Codec + Ord,
@@ -36,5 +41,14 @@ sp_api::decl_runtime_apis! {
 
         /// Get individual nominator information given the nominator AccountId.
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber>;
+
+        /// Get the projected PCX emission of the next `sessions` sessions.
+        fn emission_projection(sessions: u32) -> Vec<(SessionIndex, Balance)>;
+
+        /// Get all the unapplied slashes and the eras they will be applied in.
+        fn unapplied_slashes() -> Vec<(EraIndex, Vec<UnappliedSlash<AccountId, Balance>>)>;
+
+        /// Get the per-era records of the validator in the last `eras` eras.
+        fn validator_history(who: AccountId, eras: u32) -> Vec<(EraIndex, ValidatorEraRecord<Balance>)>;
     }
 }
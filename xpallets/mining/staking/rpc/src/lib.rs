@@ -17,8 +17,8 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcVoteWeight};
 
 use xpallet_mining_staking_rpc_runtime_api::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger,
-    XStakingApi as XStakingRuntimeApi,
+    EraIndex, NominatorInfo, NominatorLedger, SessionIndex, UnappliedSlash, Unbonded,
+    ValidatorEraRecord, ValidatorInfo, ValidatorLedger, XStakingApi as XStakingRuntimeApi,
 };
 
 /// XStaking RPC methods.
@@ -74,6 +74,35 @@ where
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Result<NominatorInfo<BlockNumber>>;
+
+    /// Get the projected PCX emission of the next `sessions` sessions.
+    #[rpc(name = "xstaking_getEmissionProjection")]
+    fn emission_projection(
+        &self,
+        sessions: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(SessionIndex, RpcBalance<Balance>)>>;
+
+    /// Get all the unapplied slashes and the eras they will be applied in.
+    #[rpc(name = "xstaking_getUnappliedSlashes")]
+    fn unapplied_slashes(
+        &self,
+        at: Option<BlockHash>,
+    ) -> Result<
+        Vec<(
+            EraIndex,
+            Vec<UnappliedSlash<AccountId, RpcBalance<Balance>>>,
+        )>,
+    >;
+
+    /// Get the per-era rewards, slashes and uptime of the validator in the last `eras` eras.
+    #[rpc(name = "xstaking_getValidatorHistory")]
+    fn validator_history(
+        &self,
+        who: AccountId,
+        eras: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(EraIndex, ValidatorEraRecord<RpcBalance<Balance>>)>>;
 }
 
 /// A struct that implements the [`XStakingApi`].
@@ -232,4 +261,85 @@ where
             .nominator_info_of(&at, who)
             .map_err(runtime_error_into_rpc_err)?)
     }
+
+    fn emission_projection(
+        &self,
+        sessions: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(SessionIndex, RpcBalance<Balance>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .emission_projection(&at, sessions)
+            .map(|projection| {
+                projection
+                    .into_iter()
+                    .map(|(session_index, reward)| (session_index, reward.into()))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?)
+    }
+    fn unapplied_slashes(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<
+        Vec<(
+            EraIndex,
+            Vec<UnappliedSlash<AccountId, RpcBalance<Balance>>>,
+        )>,
+    > {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .unapplied_slashes(&at)
+            .map(|unapplied| {
+                unapplied
+                    .into_iter()
+                    .map(|(era, slashes)| {
+                        let slashes = slashes
+                            .into_iter()
+                            .map(|slash| UnappliedSlash {
+                                validator: slash.validator,
+                                own: slash.own.into(),
+                                others: slash
+                                    .others
+                                    .into_iter()
+                                    .map(|(nominator, value)| (nominator, value.into()))
+                                    .collect(),
+                                offence_era: slash.offence_era,
+                            })
+                            .collect();
+                        (era, slashes)
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?)
+    }
+    fn validator_history(
+        &self,
+        who: AccountId,
+        eras: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(EraIndex, ValidatorEraRecord<RpcBalance<Balance>>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .validator_history(&at, who, eras)
+            .map(|history| {
+                history
+                    .into_iter()
+                    .map(|(era, record)| {
+                        let record = ValidatorEraRecord {
+                            session_rewards: record.session_rewards.into(),
+                            reward_pot_inflow: record.reward_pot_inflow.into(),
+                            slashed: record.slashed.into(),
+                            active_sessions: record.active_sessions,
+                            offline_sessions: record.offline_sessions,
+                        };
+                        (era, record)
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?)
+    }
 }
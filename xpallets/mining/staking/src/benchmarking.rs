@@ -38,6 +38,31 @@ pub fn create_validator<T: Trait>(string: &'static str, n: u32, value: u32) -> T
     validator
 }
 
+fn create_pool_with_validators<T: Trait>(v: u32) -> (PoolId, Vec<T::AccountId>) {
+    let validators = (0..v)
+        .map(|i| create_validator::<T>("pool_validator", 100 + i, 1000))
+        .collect::<Vec<_>>();
+    let owner = create_funded_user::<T>("pool_owner", 0, 100);
+    let pool_id = Module::<T>::apply_create_pool(owner, validators.clone());
+    (pool_id, validators)
+}
+
+fn b_bond_to_pool<T: Trait>(pool_id: PoolId, value: u32) -> T::AccountId {
+    let member = create_funded_user::<T>("pool_member", 0, 1000);
+    assert!(Module::<T>::bond_to_pool(
+        RawOrigin::Signed(member.clone()).into(),
+        pool_id,
+        value.into(),
+    )
+    .is_ok());
+    member
+}
+
+fn next_block<T: Trait>() {
+    let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+    frame_system::Module::<T>::set_block_number(block_number + 1.into());
+}
+
 benchmarks! {
     _{
         // User account seed
@@ -184,6 +209,266 @@ benchmarks! {
     verify {
         assert_eq!(SessionsPerEra::get(), c);
     }
+
+    set_emission_schedule {
+        let schedule = EmissionSchedule::LinearDecay {
+            initial_reward: 5_000_000_000u64.saturated_into(),
+            decay_per_session: 1_000u32.into(),
+            min_reward: 1_000_000u32.into(),
+            start_session: 1000,
+        };
+    }: _(RawOrigin::Root, schedule.clone())
+    verify {
+        assert_eq!(EmissionScheduleOf::<T>::get(), Some(schedule));
+    }
+
+    set_commission {
+        let validator = create_validator::<T>("validator", 2, 1000);
+        let commission = Perbill::from_percent(20);
+    }: _(RawOrigin::Signed(validator.clone()), commission)
+    verify {
+        assert_eq!(Validators::<T>::get(&validator).commission, commission);
+    }
+
+    set_maximum_commission {
+        let c = Perbill::from_percent(50);
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(MaximumCommission::get(), c);
+    }
+
+    set_commission_change_interval {
+        let c: T::BlockNumber = 1000u32.into();
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(CommissionChangeInterval::<T>::get(), c);
+    }
+
+    set_auto_compound {
+        let nominator = create_funded_user::<T>("nominator", u, 100);
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        b_bond::<T>(nominator.clone(), validator.clone(), 10);
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+    }: _(RawOrigin::Signed(nominator.clone()), validator_lookup, true)
+    verify {
+        assert!(AutoCompound::<T>::get(&nominator, &validator));
+    }
+
+    set_maximum_auto_compound_per_block {
+        let c = 1000u32;
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(MaximumAutoCompoundPerBlock::get(), c);
+    }
+
+    create_pool {
+        let v in 1 .. MAXIMUM_POOL_VALIDATORS;
+        let validators = (0..v)
+            .map(|i| create_validator::<T>("pool_validator", 100 + i, 1000))
+            .collect::<Vec<_>>();
+        let validators_lookup = validators
+            .iter()
+            .cloned()
+            .map(T::Lookup::unlookup)
+            .collect::<Vec<_>>();
+        let owner = create_funded_user::<T>("pool_owner", 0, 100);
+        let pool_id = NextPoolId::get();
+    }: _(RawOrigin::Signed(owner), validators_lookup)
+    verify {
+        assert_eq!(Pools::<T>::get(pool_id).unwrap().validators, validators);
+    }
+
+    bond_to_pool {
+        let (pool_id, _) = create_pool_with_validators::<T>(1);
+        let member = create_funded_user::<T>("pool_member", 0, 1000);
+    }: _(RawOrigin::Signed(member.clone()), pool_id, 100.into())
+    verify {
+        assert!(PoolMembers::<T>::get(pool_id, &member).points == 100.into());
+    }
+
+    unbond_from_pool {
+        let (pool_id, _) = create_pool_with_validators::<T>(1);
+        let member = b_bond_to_pool::<T>(pool_id, 100);
+    }: _(RawOrigin::Signed(member.clone()), pool_id, 40.into())
+    verify {
+        assert!(PoolMembers::<T>::get(pool_id, &member).points == 60.into());
+    }
+
+    withdraw_from_pool {
+        let (pool_id, _) = create_pool_with_validators::<T>(1);
+        let member = b_bond_to_pool::<T>(pool_id, 100);
+        Module::<T>::set_bonding_duration(RawOrigin::Root.into(), 0.into())?;
+        Module::<T>::unbond_from_pool(RawOrigin::Signed(member.clone()).into(), pool_id, 40.into())?;
+        next_block::<T>();
+    }: _(RawOrigin::Signed(member.clone()), pool_id)
+    verify {
+        assert!(PoolMembers::<T>::get(pool_id, &member).unbonded_chunks.is_empty());
+    }
+
+    claim_from_pool {
+        let v in 1 .. MAXIMUM_POOL_VALIDATORS;
+        let (pool_id, validators) = create_pool_with_validators::<T>(v);
+        let member = b_bond_to_pool::<T>(pool_id, 100);
+        for validator in validators.iter() {
+            let validator_pot = T::DetermineRewardPotAccount::reward_pot_account_for(validator);
+            T::Currency::make_free_balance_be(&validator_pot, 1_000_000u32.into());
+            T::Currency::issue(1_000_000u32.into());
+        }
+        next_block::<T>();
+    }: _(RawOrigin::Signed(member.clone()), pool_id)
+    verify {
+        assert_eq!(PoolMembers::<T>::get(pool_id, &member).last_weight, 0);
+    }
+
+    submit_election_solution {
+        let v in 1 .. 16;
+        let n in 1 .. 100;
+        let validators = (0..v)
+            .map(|i| create_validator::<T>("election_validator", 200 + i, 1000))
+            .collect::<Vec<_>>();
+        for i in 0..n {
+            let nominator = create_funded_user::<T>("election_nominator", i, 1000);
+            b_bond::<T>(nominator.clone(), validators[(i % v) as usize].clone(), 40);
+            b_bond::<T>(nominator, validators[((i + 1) % v) as usize].clone(), 40);
+        }
+        ValidatorCount::put(v);
+        ElectionStrategyOf::put(ElectionStrategy::SequentialPhragmen);
+        IsCurrentSessionFinal::put(true);
+        let era = Module::<T>::election_era();
+        let (winners, assignments) = Module::<T>::compute_election_solution();
+        let score = crate::election::evaluate_score(&winners, &assignments);
+        // The signature is verified in `validate_unsigned` rather than the dispatch.
+        let key = T::ElectionAuthorityId::generate_pair(None);
+        let payload = (&era, &winners, &assignments, &score, &0u32).encode();
+        let signature = key.sign(&payload).ok_or("couldn't make signature")?;
+    }: _(RawOrigin::None, era, winners, assignments, score, 0, signature)
+    verify {
+        assert_eq!(QueuedElectionOutcome::<T>::get().unwrap().score, score);
+    }
+
+    set_election_strategy {
+        let strategy = ElectionStrategy::SequentialPhragmen;
+    }: _(RawOrigin::Root, strategy)
+    verify {
+        assert_eq!(ElectionStrategyOf::get(), strategy);
+    }
+
+    set_minimum_election_score {
+        let score = [1, 1, 0];
+    }: _(RawOrigin::Root, score)
+    verify {
+        assert_eq!(MinimumElectionScore::get(), score);
+    }
+
+    unlock_all_due {
+        let n in 1 .. 100;
+        let nominator = create_funded_user::<T>("nominator", 0, 10_000);
+        Module::<T>::set_bonding_duration(RawOrigin::Root.into(), 0.into())?;
+        for i in 0..n {
+            let validator = create_validator::<T>("unlock_validator", 300 + i, 1000);
+            b_bond::<T>(nominator.clone(), validator.clone(), 20);
+            Module::<T>::unbond(
+                RawOrigin::Signed(nominator.clone()).into(),
+                T::Lookup::unlookup(validator),
+                10.into(),
+            )?;
+        }
+        next_block::<T>();
+    }: _(RawOrigin::Signed(nominator.clone()), n)
+    verify {
+        assert!(Locks::<T>::get(&nominator).get(&LockedType::BondedWithdrawal).is_none());
+    }
+
+    rebond_unbonded {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        let nominator = create_funded_user::<T>("nominator", 0, 1000);
+        b_bond::<T>(nominator.clone(), validator.clone(), 100);
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+        Module::<T>::unbond(
+            RawOrigin::Signed(nominator.clone()).into(),
+            validator_lookup.clone(),
+            50.into(),
+        )?;
+    }: _(RawOrigin::Signed(nominator.clone()), validator_lookup, 50.into())
+    verify {
+        assert!(Module::<T>::bonded_to(&nominator, &validator) == 100.into());
+        assert!(Module::<T>::unbonded_chunks_of(&nominator, &validator).is_empty());
+    }
+
+    set_slashing_mode {
+        let mode = SlashingMode::NominatorProportional;
+    }: _(RawOrigin::Root, mode)
+    verify {
+        assert_eq!(SlashingModeOf::get(), mode);
+    }
+
+    set_slash_defer_duration {
+        let d = 7;
+    }: _(RawOrigin::Root, d)
+    verify {
+        assert_eq!(SlashDeferDuration::get(), d);
+    }
+
+    cancel_deferred_slash {
+        let s in 1 .. 100;
+        let era: EraIndex = 10;
+        let slashes = (0..s)
+            .map(|i| UnappliedSlash {
+                validator: account("offender", i, SEED),
+                own: 10.into(),
+                others: Vec::new(),
+                offence_era: 0,
+            })
+            .collect::<Vec<_>>();
+        UnappliedSlashes::<T>::insert(era, slashes);
+        let slash_indices = (0..s).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, era, slash_indices)
+    verify {
+        assert!(UnappliedSlashes::<T>::get(era).is_empty());
+    }
+
+    rename_referral {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        T::Currency::make_free_balance_be(&validator, 2000u32.into());
+        ReferralRenameFee::<T>::put(BalanceOf::<T>::from(10u32));
+        let new_id = b"renamed".to_vec();
+    }: _(RawOrigin::Signed(validator.clone()), new_id.clone())
+    verify {
+        assert_eq!(Validators::<T>::get(&validator).referral_id, new_id);
+        assert_eq!(Module::<T>::validator_for(&new_id), Some(validator));
+    }
+
+    set_referral_rename_interval {
+        let c: T::BlockNumber = 1000u32.into();
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(ReferralRenameInterval::<T>::get(), c);
+    }
+
+    set_referral_rename_fee {
+        let c: BalanceOf<T> = 1000u32.into();
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(ReferralRenameFee::<T>::get(), c);
+    }
+
+    claim_all {
+        let n in 1 .. 100;
+        let nominator = create_funded_user::<T>("nominator", 0, 10_000);
+        let pot_balance = 50;
+        for i in 0..n {
+            let validator = create_validator::<T>("claim_validator", 400 + i, 1000);
+            b_bond::<T>(nominator.clone(), validator.clone(), 20);
+            let validator_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&validator);
+            T::Currency::make_free_balance_be(&validator_pot, pot_balance.into());
+            T::Currency::issue(pot_balance.into());
+        }
+        let balance_before = T::Currency::free_balance(&nominator);
+        next_block::<T>();
+    }: _(RawOrigin::Signed(nominator.clone()), n)
+    verify {
+        assert!(T::Currency::free_balance(&nominator) > balance_before);
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +492,29 @@ mod tests {
             assert_ok!(test_benchmark_set_minimum_validator_count::<Test>());
             assert_ok!(test_benchmark_set_bonding_duration::<Test>());
             assert_ok!(test_benchmark_set_validator_bonding_duration::<Test>());
+            assert_ok!(test_benchmark_set_emission_schedule::<Test>());
+            assert_ok!(test_benchmark_set_commission::<Test>());
+            assert_ok!(test_benchmark_set_maximum_commission::<Test>());
+            assert_ok!(test_benchmark_set_commission_change_interval::<Test>());
+            assert_ok!(test_benchmark_set_auto_compound::<Test>());
+            assert_ok!(test_benchmark_set_maximum_auto_compound_per_block::<Test>());
+            assert_ok!(test_benchmark_create_pool::<Test>());
+            assert_ok!(test_benchmark_bond_to_pool::<Test>());
+            assert_ok!(test_benchmark_unbond_from_pool::<Test>());
+            assert_ok!(test_benchmark_withdraw_from_pool::<Test>());
+            assert_ok!(test_benchmark_claim_from_pool::<Test>());
+            assert_ok!(test_benchmark_submit_election_solution::<Test>());
+            assert_ok!(test_benchmark_set_election_strategy::<Test>());
+            assert_ok!(test_benchmark_set_minimum_election_score::<Test>());
+            assert_ok!(test_benchmark_unlock_all_due::<Test>());
+            assert_ok!(test_benchmark_rebond_unbonded::<Test>());
+            assert_ok!(test_benchmark_set_slashing_mode::<Test>());
+            assert_ok!(test_benchmark_set_slash_defer_duration::<Test>());
+            assert_ok!(test_benchmark_cancel_deferred_slash::<Test>());
+            assert_ok!(test_benchmark_rename_referral::<Test>());
+            assert_ok!(test_benchmark_set_referral_rename_interval::<Test>());
+            assert_ok!(test_benchmark_set_referral_rename_fee::<Test>());
+            assert_ok!(test_benchmark_claim_all::<Test>());
         });
     }
 }
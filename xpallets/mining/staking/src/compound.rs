@@ -0,0 +1,136 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Auto-compounding of the staking dividends.
+
+use sp_std::prelude::*;
+
+use codec::Decode;
+use frame_support::{
+    storage::{StoragePrefixedMap, StorageValue},
+    traits::Get,
+    weights::Weight,
+    ReversibleStorageHasher, Twox64Concat,
+};
+use sp_runtime::traits::Zero;
+
+use xp_logging::debug;
+use xp_mining_common::Claim;
+
+use crate::{
+    AutoCompound, AutoCompoundCursor, AutoCompoundPending, BalanceOf, Event, Module, Trait,
+    WeightInfo,
+};
+
+impl<T: Trait> Module<T> {
+    /// Re-bonds the freshly claimed `dividend` to the same validator if the nominator
+    /// has opted in auto-compounding.
+    ///
+    /// Nothing happens if the validator can accept no more votes.
+    pub(crate) fn try_compound(
+        nominator: &T::AccountId,
+        validator: &T::AccountId,
+        dividend: BalanceOf<T>,
+    ) {
+        if dividend.is_zero() || !Self::auto_compound(nominator, validator) {
+            return;
+        }
+
+        if !Self::is_validator_bonding_itself(nominator, validator)
+            && Self::check_validator_acceptable_votes_limit(validator, dividend).is_err()
+        {
+            debug!(
+                "[try_compound] validator {:?} can accept no more votes, skip compounding {:?}",
+                validator, dividend
+            );
+            return;
+        }
+
+        if dividend + Self::total_locked_of(nominator) > Self::free_balance(nominator) {
+            return;
+        }
+
+        match Self::apply_bond(nominator, validator, dividend) {
+            Ok(_) => Self::deposit_event(Event::<T>::Compounded(
+                nominator.clone(),
+                validator.clone(),
+                dividend,
+            )),
+            Err(e) => debug!("[try_compound] failed to compound: {:?}", e),
+        }
+    }
+
+    /// Schedules a new round over all the auto-compounding nominations, which will be
+    /// processed gradually in the following blocks.
+    ///
+    /// The ongoing round, if any, is not interrupted, the new round starts right after it.
+    pub(crate) fn schedule_auto_compounds() {
+        AutoCompoundPending::put(true);
+    }
+
+    /// Claims and compounds at most `MaximumAutoCompoundPerBlock` nominations, resuming
+    /// from the cursor left by the previous block.
+    pub(crate) fn process_auto_compounds() -> Weight {
+        let prefix = AutoCompound::<T>::final_prefix();
+        let mut previous_key = match Self::auto_compound_cursor() {
+            Some(cursor) => cursor,
+            None if Self::auto_compound_pending() => {
+                AutoCompoundPending::put(false);
+                prefix.to_vec()
+            }
+            None => return T::DbWeight::get().reads(2),
+        };
+
+        let max_count = Self::maximum_auto_compound_per_block();
+        let mut count: u32 = 0;
+        let mut finished = false;
+        loop {
+            let key = match sp_io::storage::next_key(&previous_key) {
+                Some(key) if key.starts_with(&prefix) => key,
+                _ => {
+                    finished = true;
+                    break;
+                }
+            };
+            if count >= max_count {
+                break;
+            }
+            if let Some((nominator, validator)) =
+                Self::decode_auto_compound_key(&key[prefix.len()..])
+            {
+                // The nomination may have nothing to claim, just ignore it.
+                if let Err(e) = <Self as Claim<T::AccountId>>::claim(&nominator, &validator) {
+                    debug!(
+                        "[process_auto_compounds] {:?} failed to claim from {:?}: {:?}",
+                        nominator, validator, e
+                    );
+                }
+            }
+            previous_key = key;
+            count += 1;
+        }
+
+        if finished {
+            AutoCompoundCursor::kill();
+        } else {
+            AutoCompoundCursor::put(previous_key);
+        }
+
+        T::DbWeight::get()
+            .reads_writes(4 + count as Weight, 2)
+            .saturating_add(
+                T::WeightInfo::claim()
+                    .saturating_add(T::WeightInfo::bond())
+                    .saturating_mul(count as Weight),
+            )
+    }
+
+    /// Decodes the nominator and validator from the raw storage key of `AutoCompound`
+    /// without the prefix.
+    fn decode_auto_compound_key(key: &[u8]) -> Option<(T::AccountId, T::AccountId)> {
+        let mut key = Twox64Concat::reverse(key);
+        let nominator = T::AccountId::decode(&mut key).ok()?;
+        let mut key = Twox64Concat::reverse(key);
+        let validator = T::AccountId::decode(&mut key).ok()?;
+        Some((nominator, validator))
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use frame_support::traits::LockIdentifier;
+use sp_runtime::{transaction_validity::TransactionPriority, Perbill};
 
 pub const STAKING_ID: LockIdentifier = *b"staking ";
 
@@ -15,6 +16,19 @@ pub const INITIAL_REWARD: u64 = 5_000_000_000;
 /// is equivalent to `Block` in Bitcoin with regard to minting new coins.
 pub const SESSIONS_PER_ROUND: u32 = 210_000;
 
+/// The maximum number of sessions that can be projected by the emission projection RPC,
+/// i.e., one week of 5-minute sessions.
+pub const MAX_EMISSION_PROJECTION_SESSIONS: u32 = 12 * 24 * 7;
+
+/// The session reward of any emission schedule can not exceed the initial reward of ChainX 1.0.
+pub const MAX_SESSION_REWARD: u64 = INITIAL_REWARD;
+
+/// The annual inflation of the `FixedInflation` emission schedule is at most 10%.
+pub const MAX_ANNUAL_INFLATION: Perbill = Perbill::from_parts(100_000_000);
+
+/// The `FixedInflation` emission schedule has at least one session per hour.
+pub const MIN_SESSIONS_PER_YEAR: u32 = 24 * 365;
+
 /// The maximum number of Staking validators.
 ///
 /// Currently the election will perform a naive sort on the all candidates,
@@ -39,3 +53,37 @@ pub const DEFAULT_BONDING_DURATION: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24 *
 
 /// The default bonding duration for validator is 3 * 10 days.
 pub const DEFAULT_VALIDATOR_BONDING_DURATION: u64 = DEFAULT_BONDING_DURATION * 10;
+
+/// Validators directly gain 10% of their session reward by default.
+pub const DEFAULT_COMMISSION: Perbill = Perbill::from_parts(100_000_000);
+
+/// Validators can change their commission at most once a day by default.
+pub const DEFAULT_COMMISSION_CHANGE_INTERVAL: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24;
+
+/// Validators can rename their referral id at most once per 30 days by default.
+pub const DEFAULT_REFERRAL_RENAME_INTERVAL: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24 * 30;
+
+/// Renaming the referral id costs 10 PCX by default.
+pub const DEFAULT_REFERRAL_RENAME_FEE: u64 = 1_000_000_000;
+
+/// The maximum number of nominations being auto-compounded in a block by default.
+pub const DEFAULT_MAXIMUM_AUTO_COMPOUND_PER_BLOCK: u32 = 100;
+
+/// The maximum number of validators nominated by a nomination pool.
+pub const MAXIMUM_POOL_VALIDATORS: u32 = 16;
+
+/// The maximum number of members of a nomination pool.
+///
+/// A slash of the pool nominations reduces the points of every member.
+pub const MAXIMUM_POOL_MEMBERS: u32 = 256;
+
+/// The nominator-proportional slashes are deferred for 24 eras by default, during
+/// which the governance is able to cancel them.
+pub const DEFAULT_SLASH_DEFER_DURATION: u32 = 24;
+
+/// The maximum number of eras the per-era history of validators is kept for.
+pub const VALIDATOR_HISTORY_DEPTH: u32 = 84;
+
+/// The base priority of the unsigned election solution submitted by the offchain workers.
+pub const ELECTION_SOLUTION_UNSIGNED_PRIORITY: TransactionPriority =
+    TransactionPriority::max_value() / 2;
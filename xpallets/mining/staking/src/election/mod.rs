@@ -0,0 +1,268 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use super::*;
+use frame_support::storage::IterableStorageDoubleMap;
+use sp_std::collections::btree_set::BTreeSet;
+
+mod offchain;
+pub(crate) mod phragmen;
+
+pub(crate) use self::phragmen::evaluate_score;
+use self::phragmen::{is_score_better, PhragmenResult};
+
+impl<T: Trait> Module<T> {
+    /// Returns a new validator set for the new era.
+    pub(crate) fn new_era(start_session_index: SessionIndex) -> Option<Vec<T::AccountId>> {
+        // Increment or set current era.
+        let current_era = CurrentEra::mutate(|s| {
+            *s = Some(s.map(|s| s + 1).unwrap_or(0));
+            s.unwrap()
+        });
+        ErasStartSessionIndex::insert(&current_era, &start_session_index);
+
+        // The election window of this era is closed.
+        IsCurrentSessionFinal::put(false);
+
+        // Set staking information for new era.
+        let maybe_new_validators = Self::select_and_update_validators(current_era);
+        debug!(
+            "[new_era] era_index:{}, start_session_index:{}, maybe_new_validators:{:?}",
+            current_era, start_session_index, maybe_new_validators
+        );
+
+        maybe_new_validators
+    }
+
+    /// Returns true if the (potential) validator is able to join in the election.
+    ///
+    /// Two requirements:
+    /// 1. has the desire to win the election.
+    /// 2. meets the threshold of a valid candidate.
+    fn is_qualified_candidate(who: &T::AccountId) -> bool {
+        Self::is_active(who) && Self::meet_candidate_threshold(who)
+    }
+
+    /// Returns true if the candidate meets the minimum candidate threshold.
+    fn satisfy_candidate_threshold(who: &T::AccountId) -> bool {
+        let BondRequirement { self_bonded, total } = Self::validator_candidate_requirement();
+        Self::validator_self_bonded(who) >= self_bonded && Self::total_votes_of(who) >= total
+    }
+
+    /// Returns true if the candidate meets the minimum candidate threshold.
+    ///
+    /// Otherwise the candidate will be **forced to be chilled**.
+    fn meet_candidate_threshold(who: &T::AccountId) -> bool {
+        let threshold_satisfied = Self::satisfy_candidate_threshold(who);
+
+        if !threshold_satisfied && Self::try_force_chilled(who).is_ok() {
+            xp_logging::info!("[meet_candidate_threshold] Force {:?} to be inactive since it doesn't meet the minimum bond requirement", who);
+        }
+
+        threshold_satisfied
+    }
+
+    /// Filters out all the qualified validator candidates, sorted by the total nominations.
+    fn filter_out_candidates() -> Vec<(BalanceOf<T>, T::AccountId)> {
+        let mut candidates = Self::validator_set()
+            .filter(Self::is_qualified_candidate)
+            .map(|v| (Self::total_votes_of(&v), v))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|&(ref b1, _), &(ref b2, _)| b2.cmp(&b1));
+        candidates
+    }
+
+    /// Selects the new validator set at the end of the era.
+    ///
+    /// By default, order potential validators by their total nominations and
+    /// choose the top-most ValidatorCount::get() of them. Otherwise the validators
+    /// are elected by the sequential Phragmén solution submitted by the offchain
+    /// workers, and fall back to the former if no valid solution is queued.
+    ///
+    /// This should only be called at the end of an era.
+    fn select_and_update_validators(current_era: EraIndex) -> Option<Vec<T::AccountId>> {
+        let candidates = Self::filter_out_candidates();
+        debug!("[select_and_update_validators] candidates:{:?}", candidates);
+
+        // The queued outcome is useless once the era has been planned.
+        let queued_outcome = QueuedElectionOutcome::<T>::take();
+
+        // Avoid reevaluate validator set if it would leave us with fewer than the minimum
+        // needed validators.
+        if candidates.len() < Self::reasonable_minimum_validator_count() as usize {
+            return None;
+        }
+
+        let desired_validator_count = ValidatorCount::get() as usize;
+
+        let validators = match (Self::election_strategy(), queued_outcome) {
+            // The queued winners could have been chilled since the solution was verified.
+            (ElectionStrategy::SequentialPhragmen, Some(outcome))
+                if outcome.era == current_era
+                    && outcome.winners.len()
+                        >= Self::reasonable_minimum_validator_count() as usize
+                    && outcome.winners.iter().all(|w| {
+                        candidates
+                            .iter()
+                            .any(|(votes, v)| v == w && !votes.is_zero())
+                    }) =>
+            {
+                outcome.winners
+            }
+            // Running Phragmén on chain is too expensive for the session hook,
+            // sort the candidates instead.
+            _ => candidates
+                .into_iter()
+                .take(desired_validator_count)
+                .map(|(_, v)| v)
+                .collect::<Vec<_>>(),
+        };
+
+        // Always return Some(new_validators).
+        Some(validators)
+    }
+
+    /// Returns the index of the era being elected in the current election window.
+    pub(crate) fn election_era() -> EraIndex {
+        Self::current_era().map(|e| e + 1).unwrap_or(0)
+    }
+
+    /// Returns the qualified validator candidates of the Phragmén election.
+    ///
+    /// Unlike `filter_out_candidates`, no candidate will be chilled by this function.
+    fn phragmen_candidates() -> Vec<T::AccountId> {
+        Self::validator_set()
+            .filter(|v| {
+                Self::is_active(v)
+                    && Self::satisfy_candidate_threshold(v)
+                    && !Self::total_votes_of(v).is_zero()
+            })
+            .collect()
+    }
+
+    /// Returns the voters of the Phragmén election.
+    ///
+    /// The budget of a nominator is the sum of its nominations to the `candidates`,
+    /// which can be distributed among all the candidates it has nominated.
+    fn phragmen_voters(
+        candidates: &BTreeSet<T::AccountId>,
+    ) -> Vec<(T::AccountId, u128, Vec<T::AccountId>)> {
+        let mut voters = BTreeMap::<T::AccountId, (u128, Vec<T::AccountId>)>::new();
+        for (nominator, nominee, ledger) in Nominations::<T>::iter() {
+            if ledger.nomination.is_zero() || !candidates.contains(&nominee) {
+                continue;
+            }
+            let (budget, approvals) = voters.entry(nominator).or_default();
+            *budget = budget.saturating_add(ledger.nomination.saturated_into());
+            approvals.push(nominee);
+        }
+        voters
+            .into_iter()
+            .map(|(nominator, (budget, approvals))| (nominator, budget, approvals))
+            .collect()
+    }
+
+    /// Computes an election solution for the current election window.
+    ///
+    /// This is only meant to be run by the offchain workers.
+    pub(crate) fn compute_election_solution() -> (
+        Vec<T::AccountId>,
+        Vec<StakeAssignment<T::AccountId, BalanceOf<T>>>,
+    ) {
+        let candidates = Self::phragmen_candidates();
+        let voters = Self::phragmen_voters(&candidates.iter().cloned().collect());
+        let PhragmenResult {
+            winners,
+            assignments,
+        } = phragmen::seq_phragmen(ValidatorCount::get() as usize, candidates, voters);
+        let assignments = assignments
+            .into_iter()
+            .map(|(nominator, distribution)| {
+                let distribution = distribution
+                    .into_iter()
+                    .map(|(nominee, stake)| (nominee, stake.saturated_into()))
+                    .collect();
+                (nominator, distribution)
+            })
+            .collect();
+        (winners, assignments)
+    }
+
+    /// Verifies the election solution and returns its score.
+    ///
+    /// The solution must be submitted for the `election_era()` while the
+    /// election window is open, i.e., the current planned session is final.
+    pub(crate) fn check_election_solution(
+        era: EraIndex,
+        winners: &[T::AccountId],
+        assignments: &[StakeAssignment<T::AccountId, BalanceOf<T>>],
+    ) -> Result<ElectionScore, Error<T>> {
+        ensure!(
+            Self::election_strategy() == ElectionStrategy::SequentialPhragmen
+                && Self::is_current_session_final()
+                && era == Self::election_era(),
+            Error::<T>::ElectionSolutionNotAcceptable
+        );
+
+        let candidates = Self::phragmen_candidates()
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let expected_winners = candidates.len().min(ValidatorCount::get() as usize);
+        let winner_set = winners.iter().collect::<BTreeSet<_>>();
+        ensure!(
+            winners.len() == expected_winners
+                && winner_set.len() == winners.len()
+                && winners.iter().all(|w| candidates.contains(w)),
+            Error::<T>::InvalidElectionSolution
+        );
+
+        let mut nominators = BTreeSet::new();
+        for (nominator, distribution) in assignments.iter() {
+            ensure!(
+                nominators.insert(nominator),
+                Error::<T>::InvalidElectionSolution
+            );
+            let budget = Nominations::<T>::iter_prefix(nominator)
+                .filter(|(nominee, _)| candidates.contains(nominee))
+                .fold(Zero::zero(), |acc: BalanceOf<T>, (_, ledger)| {
+                    acc.saturating_add(ledger.nomination)
+                });
+            let mut nominees = BTreeSet::new();
+            let mut assigned: BalanceOf<T> = Zero::zero();
+            for (nominee, stake) in distribution.iter() {
+                ensure!(
+                    winner_set.contains(nominee)
+                        && nominees.insert(nominee)
+                        && !Self::bonded_to(nominator, nominee).is_zero(),
+                    Error::<T>::InvalidElectionSolution
+                );
+                assigned = assigned.saturating_add(*stake);
+            }
+            ensure!(assigned <= budget, Error::<T>::InvalidElectionSolution);
+        }
+
+        Ok(evaluate_score(winners, assignments))
+    }
+
+    /// Opens the election window of `election_era()`.
+    pub(crate) fn open_election_window() {
+        IsCurrentSessionFinal::put(true);
+    }
+
+    /// Ensures the score is better than `MinimumElectionScore`, and better than
+    /// the queued one of the same era if any.
+    pub(crate) fn ensure_better_election_score(
+        era: EraIndex,
+        score: &ElectionScore,
+    ) -> Result<(), Error<T>> {
+        ensure!(
+            is_score_better(score, &Self::minimum_election_score()),
+            Error::<T>::ElectionScoreTooLow
+        );
+        match Self::queued_election_outcome() {
+            Some(queued) if queued.era == era && !is_score_better(score, &queued.score) => {
+                Err(Error::<T>::WeakElectionSolution)
+            }
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Offchain worker computing the Phragmén election solution.
+
+use super::*;
+use frame_system::offchain::SubmitTransaction;
+use sp_runtime::offchain::storage::StorageValueRef;
+use xp_logging::{error, info};
+
+/// Key of the offchain storage recording the last era a solution was submitted for.
+pub(crate) const OFFCHAIN_ELECTION_LAST_ERA: &[u8] = b"xstaking/election/last_era";
+
+impl<T: Trait> Module<T> {
+    /// Computes and submits the election solution once per era while the election
+    /// window is open.
+    pub(crate) fn offchain_election() {
+        if Self::election_strategy() != ElectionStrategy::SequentialPhragmen
+            || !Self::is_current_session_final()
+            || !sp_io::offchain::is_validator()
+        {
+            return;
+        }
+
+        // Only the current validators are able to submit the solutions.
+        let authorities = T::ElectionAuthorities::authorities();
+        let local_keys = T::ElectionAuthorityId::all();
+        let signer = authorities
+            .into_iter()
+            .enumerate()
+            .find(|(_, authority)| local_keys.contains(authority));
+        let (validator_index, key) = match signer {
+            Some((index, key)) => (index as u32, key),
+            None => return,
+        };
+
+        let era = Self::election_era();
+        let last_era = StorageValueRef::persistent(OFFCHAIN_ELECTION_LAST_ERA);
+        let locked = last_era.mutate(|last: Option<Option<EraIndex>>| match last {
+            Some(Some(last)) if last >= era => Err(()),
+            _ => Ok(era),
+        });
+        // Either the solution of this era has been submitted or another worker is running.
+        if locked != Ok(Ok(era)) {
+            return;
+        }
+
+        let (winners, assignments) = Self::compute_election_solution();
+        let score = evaluate_score(&winners, &assignments);
+        info!(
+            "[offchain_election] era:{}, winners:{:?}, score:{:?}",
+            era, winners, score
+        );
+        // Nothing to submit if a solution as good has been queued.
+        if Self::ensure_better_election_score(era, &score).is_err() {
+            return;
+        }
+
+        let payload = (&era, &winners, &assignments, &score, &validator_index).encode();
+        let signature = match key.sign(&payload) {
+            Some(signature) => signature,
+            None => {
+                error!("[offchain_election] Failed to sign the election solution");
+                return;
+            }
+        };
+
+        let call = Call::submit_election_solution(
+            era,
+            winners,
+            assignments,
+            score,
+            validator_index,
+            signature,
+        );
+        if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+            error!("[offchain_election] Failed to submit the election solution");
+        }
+    }
+}
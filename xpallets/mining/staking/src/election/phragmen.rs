@@ -0,0 +1,213 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Sequential Phragmén method without the post-processing balancing.
+//!
+//! The loads are fixed-point numbers scaled by `DEN` and all the stakes are raw `u128`
+//! balances, so this module knows nothing about the runtime.
+
+use sp_arithmetic::{
+    helpers_128bit::multiply_by_rational,
+    traits::{UniqueSaturatedInto, Zero},
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+use crate::types::{ElectionScore, StakeAssignment};
+
+/// Denominator of the loads, large enough to keep the precision of small stakes.
+const DEN: u128 = u128::max_value() >> 32;
+
+struct Candidate<AccountId> {
+    who: AccountId,
+    approval_stake: u128,
+    score: u128,
+    elected: bool,
+}
+
+struct Edge {
+    candidate: usize,
+    load: u128,
+}
+
+struct Voter<AccountId> {
+    who: AccountId,
+    budget: u128,
+    edges: Vec<Edge>,
+    load: u128,
+}
+
+/// Winners and stake distribution of a Phragmén election.
+pub struct PhragmenResult<AccountId> {
+    pub winners: Vec<AccountId>,
+    pub assignments: Vec<StakeAssignment<AccountId, u128>>,
+}
+
+#[inline]
+fn mul_div(a: u128, b: u128, c: u128) -> u128 {
+    multiply_by_rational(a, b, c).unwrap_or_else(|_| u128::max_value())
+}
+
+/// Elects at most `to_elect` of `candidates` given the `voters`.
+///
+/// Each voter is `(who, budget, approvals)`, the approvals out of `candidates` are ignored.
+/// The winners are returned in the order they were elected.
+pub fn seq_phragmen<AccountId: Clone + Ord>(
+    to_elect: usize,
+    candidates: Vec<AccountId>,
+    voters: Vec<(AccountId, u128, Vec<AccountId>)>,
+) -> PhragmenResult<AccountId> {
+    let index_of = candidates
+        .iter()
+        .enumerate()
+        .map(|(idx, who)| (who.clone(), idx))
+        .collect::<BTreeMap<_, _>>();
+    let mut candidates = candidates
+        .into_iter()
+        .map(|who| Candidate {
+            who,
+            approval_stake: 0,
+            score: 0,
+            elected: false,
+        })
+        .collect::<Vec<_>>();
+
+    let mut voters = voters
+        .into_iter()
+        .map(|(who, budget, approvals)| {
+            let edges = approvals
+                .iter()
+                .filter_map(|target| index_of.get(target))
+                .map(|&candidate| {
+                    candidates[candidate].approval_stake =
+                        candidates[candidate].approval_stake.saturating_add(budget);
+                    Edge { candidate, load: 0 }
+                })
+                .collect::<Vec<_>>();
+            Voter {
+                who,
+                budget,
+                edges,
+                load: 0,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut winners = Vec::with_capacity(to_elect);
+    for _round in 0..to_elect {
+        for candidate in candidates.iter_mut().filter(|c| !c.elected) {
+            candidate.score = if candidate.approval_stake.is_zero() {
+                u128::max_value()
+            } else {
+                DEN / candidate.approval_stake
+            };
+        }
+
+        for voter in voters.iter() {
+            for edge in voter.edges.iter() {
+                let candidate = &mut candidates[edge.candidate];
+                if !candidate.elected && !candidate.approval_stake.is_zero() {
+                    let extra = mul_div(voter.budget, voter.load, candidate.approval_stake);
+                    candidate.score = candidate.score.saturating_add(extra);
+                }
+            }
+        }
+
+        let winner = match candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| !c.elected && !c.approval_stake.is_zero())
+            .min_by_key(|(_, c)| c.score)
+            .map(|(idx, _)| idx)
+        {
+            Some(winner) => winner,
+            None => break,
+        };
+
+        candidates[winner].elected = true;
+        let winner_score = candidates[winner].score;
+        for voter in voters.iter_mut() {
+            let voter_load = voter.load;
+            let mut approved = false;
+            for edge in voter.edges.iter_mut().filter(|e| e.candidate == winner) {
+                edge.load = winner_score.saturating_sub(voter_load);
+                approved = true;
+            }
+            if approved {
+                voter.load = winner_score;
+            }
+        }
+        winners.push(candidates[winner].who.clone());
+    }
+
+    let assignments = voters
+        .into_iter()
+        .filter(|voter| !voter.load.is_zero())
+        .filter_map(|voter| {
+            let elected_edges = voter
+                .edges
+                .iter()
+                .filter(|e| candidates[e.candidate].elected && !e.load.is_zero())
+                .collect::<Vec<_>>();
+            let mut remaining = voter.budget;
+            let mut distribution = Vec::with_capacity(elected_edges.len());
+            for (idx, edge) in elected_edges.iter().enumerate() {
+                // The last edge takes the rest so that no budget is lost in the rounding.
+                let stake = if idx + 1 == elected_edges.len() {
+                    remaining
+                } else {
+                    mul_div(voter.budget, edge.load, voter.load).min(remaining)
+                };
+                remaining -= stake;
+                if !stake.is_zero() {
+                    distribution.push((candidates[edge.candidate].who.clone(), stake));
+                }
+            }
+            if distribution.is_empty() {
+                None
+            } else {
+                Some((voter.who, distribution))
+            }
+        })
+        .collect();
+
+    PhragmenResult {
+        winners,
+        assignments,
+    }
+}
+
+/// Returns the score of the solution given the `winners` and the `assignments`.
+///
+/// The stakes assigned to someone not in `winners` are ignored.
+pub fn evaluate_score<AccountId: Ord, Balance: Copy + UniqueSaturatedInto<u128>>(
+    winners: &[AccountId],
+    assignments: &[StakeAssignment<AccountId, Balance>],
+) -> ElectionScore {
+    let mut supports = winners
+        .iter()
+        .map(|who| (who, 0u128))
+        .collect::<BTreeMap<_, _>>();
+    for (_, distribution) in assignments.iter() {
+        for (target, stake) in distribution.iter() {
+            if let Some(support) = supports.get_mut(target) {
+                *support = support.saturating_add((*stake).unique_saturated_into());
+            }
+        }
+    }
+
+    let min_support = supports.values().min().copied().unwrap_or_default();
+    let (sum_support, sum_support_squared) =
+        supports
+            .values()
+            .fold((0u128, 0u128), |(sum, sum_squared), &support| {
+                (
+                    sum.saturating_add(support),
+                    sum_squared.saturating_add(support.saturating_mul(support)),
+                )
+            });
+    [min_support, sum_support, sum_support_squared]
+}
+
+/// Returns true if `this` is strictly better than `that`.
+pub fn is_score_better(this: &ElectionScore, that: &ElectionScore) -> bool {
+    (this[0], this[1], that[2]) > (that[0], that[1], this[2])
+}
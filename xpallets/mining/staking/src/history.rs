@@ -0,0 +1,64 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Per-era history of the validators.
+//!
+//! The rewards, slashes and uptime of each validator are accumulated into the record
+//! of the active era, only the records of last `VALIDATOR_HISTORY_DEPTH` eras are kept.
+
+use sp_std::prelude::*;
+
+use frame_support::storage::{StorageDoubleMap, StorageValue};
+
+use crate::{
+    constants::VALIDATOR_HISTORY_DEPTH, types::*, ActiveEra, BalanceOf, EraIndex, Module, Trait,
+    ValidatorHistory,
+};
+
+impl<T: Trait> Module<T> {
+    /// Updates the record of `validator` in the active era.
+    pub(crate) fn note_validator_history(
+        validator: &T::AccountId,
+        f: impl FnOnce(&mut ValidatorEraRecord<BalanceOf<T>>),
+    ) {
+        let active_era = ActiveEra::get().map(|info| info.index).unwrap_or(0);
+        ValidatorHistory::<T>::mutate(active_era, validator, f);
+    }
+
+    /// Updates the record of `validator` in `era`, unless it has been pruned.
+    pub(crate) fn note_validator_history_at(
+        era: EraIndex,
+        validator: &T::AccountId,
+        f: impl FnOnce(&mut ValidatorEraRecord<BalanceOf<T>>),
+    ) {
+        let active_era = ActiveEra::get().map(|info| info.index).unwrap_or(0);
+        if era.saturating_add(VALIDATOR_HISTORY_DEPTH) > active_era {
+            ValidatorHistory::<T>::mutate(era, validator, f);
+        }
+    }
+
+    /// Removes the records out of the history depth given the new active era.
+    pub(crate) fn prune_validator_history(active_era: EraIndex) {
+        if let Some(stale_era) = active_era.checked_sub(VALIDATOR_HISTORY_DEPTH) {
+            ValidatorHistory::<T>::remove_prefix(stale_era);
+        }
+    }
+
+    /// Returns the records of `validator` in the last `eras` eras, the oldest first.
+    ///
+    /// The eras in which the validator has no record are skipped.
+    pub fn validator_history(
+        validator: T::AccountId,
+        eras: u32,
+    ) -> Vec<(EraIndex, ValidatorEraRecord<BalanceOf<T>>)> {
+        let active_era = match ActiveEra::get() {
+            Some(info) => info.index,
+            None => return Vec::new(),
+        };
+        let eras = eras.min(VALIDATOR_HISTORY_DEPTH);
+        let first_era = (active_era + 1).saturating_sub(eras);
+        (first_era..=active_era)
+            .filter(|era| ValidatorHistory::<T>::contains_key(era, &validator))
+            .map(|era| (era, ValidatorHistory::<T>::get(era, &validator)))
+            .collect()
+    }
+}
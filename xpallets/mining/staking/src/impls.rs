@@ -155,11 +155,19 @@ impl<T: Trait> Module<T> {
         current_block: T::BlockNumber,
         delta: Delta<BalanceOf<T>>,
     ) {
-        Nominations::<T>::mutate(nominator, validator, |claimer| {
+        if let Delta::Add(_) = delta {
+            NominatorsOf::<T>::insert(validator, nominator, true);
+        }
+        let nomination = Nominations::<T>::mutate(nominator, validator, |claimer| {
             claimer.nomination = delta.calculate(claimer.nomination);
             claimer.last_vote_weight = new_weight;
             claimer.last_vote_weight_update = current_block;
+            claimer.nomination
         });
+        // Nothing is left to be compounded.
+        if nomination.is_zero() {
+            AutoCompound::<T>::remove(nominator, validator);
+        }
     }
 
     ///
@@ -198,12 +206,7 @@ impl<T: Trait> Claim<T::AccountId> for Module<T> {
     type Error = Error<T>;
 
     fn claim(claimer: &T::AccountId, claimee: &Self::Claimee) -> Result<(), Self::Error> {
-        let current_block = <frame_system::Module<T>>::block_number();
-
-        let (dividend, source_weight, target_weight, claimee_pot) =
-            Self::calculate_dividend_on_claim(claimer, claimee, current_block)?;
-
-        Self::allocate_dividend(claimer, &claimee_pot, dividend)?;
+        let dividend = Self::apply_claim(claimer, claimee)?;
 
         Self::deposit_event(Event::<T>::Claimed(
             claimer.clone(),
@@ -211,12 +214,66 @@ impl<T: Trait> Claim<T::AccountId> for Module<T> {
             dividend,
         ));
 
+        Self::try_compound(claimer, claimee, dividend);
+
+        Ok(())
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Pays the dividend of `claimer` from the reward pot of `claimee` and settles the
+    /// vote weights, returns the dividend.
+    fn apply_claim(
+        claimer: &T::AccountId,
+        claimee: &T::AccountId,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let current_block = <frame_system::Module<T>>::block_number();
+
+        let (dividend, source_weight, target_weight, claimee_pot) =
+            Self::calculate_dividend_on_claim(claimer, claimee, current_block)?;
+
+        Self::allocate_dividend(claimer, &claimee_pot, dividend)?;
+
         let new_target_weight = target_weight - source_weight;
 
         Self::update_claimer_vote_weight_on_claim(claimer, claimee, current_block);
         Self::update_claimee_vote_weight_on_claim(claimee, new_target_weight, current_block);
 
-        Ok(())
+        Ok(dividend)
+    }
+
+    /// Claims the non-zero dividends of `nominator` from all the `validators`,
+    /// returns the total dividend.
+    ///
+    /// The validators whose dividend can not be claimed are skipped.
+    pub(crate) fn apply_claim_all(
+        nominator: &T::AccountId,
+        validators: Vec<T::AccountId>,
+    ) -> BalanceOf<T> {
+        let current_block = <frame_system::Module<T>>::block_number();
+        validators
+            .into_iter()
+            .filter(|validator| Self::is_validator(validator))
+            .filter(|validator| {
+                Self::compute_dividend_at(nominator, validator, current_block)
+                    .map(|dividend| !dividend.is_zero())
+                    .unwrap_or(false)
+            })
+            .fold(Zero::zero(), |total, validator| {
+                match Self::apply_claim(nominator, &validator) {
+                    Ok(dividend) => {
+                        Self::try_compound(nominator, &validator, dividend);
+                        total + dividend
+                    }
+                    Err(e) => {
+                        debug!(
+                            "[apply_claim_all] failed to claim from {:?}: {:?}",
+                            validator, e
+                        );
+                        total
+                    }
+                }
+            })
     }
 }
 
@@ -267,7 +324,7 @@ impl<T: Trait> Module<T> {
                 _ => {
                     // Either `ForceNone`, or `NotForcing && era_length < T::SessionsPerEra::get()`.
                     if era_length + 1 == ideal_era_length {
-                        IsCurrentSessionFinal::put(true);
+                        Self::open_election_window();
                     } else if era_length >= ideal_era_length {
                         // Should only happen when we are ready to trigger an era but we have ForceNone,
                         // otherwise previous arm would short circuit.
@@ -330,9 +387,10 @@ impl<T: Trait> Module<T> {
 
     /// * Increment `active_era.index`,
     /// * reset `active_era.start`,
-    /// * update `BondedEras` and apply slashes.
+    /// * update `BondedEras` and apply slashes,
+    /// * schedule a round over the auto-compounding nominations.
     fn start_era(_start_session: SessionIndex) {
-        let _active_era = ActiveEra::mutate(|active_era| {
+        let active_era = ActiveEra::mutate(|active_era| {
             let new_index = active_era.as_ref().map(|info| info.index + 1).unwrap_or(0);
             *active_era = Some(ActiveEraInfo {
                 index: new_index,
@@ -341,6 +399,10 @@ impl<T: Trait> Module<T> {
             });
             new_index
         });
+        Self::apply_unapplied_slashes(active_era);
+        Self::prune_validator_history(active_era);
+        Self::schedule_auto_compounds();
+        T::AssetMining::on_new_era();
     }
 
     /// Compute payout for era.
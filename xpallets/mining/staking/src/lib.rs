@@ -12,9 +12,13 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod compound;
 mod constants;
 mod election;
+mod history;
 mod impls;
+mod migration;
+mod pool;
 mod reward;
 mod rpc;
 mod slashing;
@@ -30,15 +34,21 @@ mod tests;
 
 use sp_std::prelude::*;
 
+use codec::Encode;
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage, ensure,
     storage::IterableStorageMap,
     traits::{Currency, ExistenceRequirement, Get, LockableCurrency, WithdrawReasons},
+    weights::{DispatchClass, Weight},
+    Parameter,
 };
-use frame_system::{ensure_root, ensure_signed};
+use frame_system::{ensure_none, ensure_root, ensure_signed, offchain::SendTransactionTypes};
 use sp_runtime::{
-    traits::{Convert, SaturatedConversion, Saturating, StaticLookup, Zero},
-    DispatchResult, Perbill,
+    traits::{Convert, Member, SaturatedConversion, Saturating, StaticLookup, Zero},
+    transaction_validity::{
+        InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
+    },
+    DispatchResult, PerThing, Perbill, RuntimeAppPublic,
 };
 use sp_std::collections::btree_map::BTreeMap;
 
@@ -46,11 +56,13 @@ use chainx_primitives::ReferralId;
 use xp_logging::debug;
 pub use xp_mining_common::RewardPotAccountFor;
 use xp_mining_common::{Claim, ComputeMiningWeight, Delta, ZeroMiningWeightError};
-use xp_mining_staking::{AssetMining, SessionIndex, UnbondedIndex};
+pub use xp_mining_staking::SessionIndex;
+use xp_mining_staking::{AssetMining, UnbondedIndex};
 use xpallet_support::traits::TreasuryAccount;
 
 use self::constants::*;
 pub use self::impls::{IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer};
+pub use self::pool::PoolMining;
 pub use self::rpc::*;
 pub use self::types::*;
 pub use self::weights::WeightInfo;
@@ -61,7 +73,7 @@ pub type BalanceOf<T> =
 /// Counter for the number of eras that have passed.
 pub type EraIndex = u32;
 
-pub trait Trait: frame_system::Trait {
+pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
@@ -99,6 +111,12 @@ pub trait Trait: frame_system::Trait {
     /// there is a chance the authority will produce a block and they won't be necessary.
     type SessionDuration: Get<Self::BlockNumber>;
 
+    /// The identifier type of the session key signing the election solutions.
+    type ElectionAuthorityId: Member + Parameter + RuntimeAppPublic + Default + Ord;
+
+    /// The session keys of the current validators signing the election solutions.
+    type ElectionAuthorities: ElectionAuthorities<Self::ElectionAuthorityId>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -204,6 +222,125 @@ decl_storage! {
         ///
         /// Immortals will be intialized from the genesis validators.
         Immortals get(fn immortals): Option<Vec<T::AccountId>>;
+
+        /// The emission schedule of PCX set by the governance.
+        ///
+        /// The default halving schedule is used if it's none.
+        pub EmissionScheduleOf get(fn emission_schedule_of): Option<EmissionSchedule<BalanceOf<T>>>;
+
+        /// The maximum commission a validator can set.
+        pub MaximumCommission get(fn maximum_commission): Perbill = Perbill::one();
+
+        /// The minimum interval in blocks between two commission changes of a validator.
+        pub CommissionChangeInterval get(fn commission_change_interval):
+            T::BlockNumber = T::BlockNumber::saturated_from::<u64>(DEFAULT_COMMISSION_CHANGE_INTERVAL);
+
+        /// The minimum interval in blocks between two referral renamings of a validator.
+        pub ReferralRenameInterval get(fn referral_rename_interval):
+            T::BlockNumber = T::BlockNumber::saturated_from::<u64>(DEFAULT_REFERRAL_RENAME_INTERVAL);
+
+        /// The fee paid to the treasury for renaming the referral id.
+        pub ReferralRenameFee get(fn referral_rename_fee):
+            BalanceOf<T> = BalanceOf::<T>::saturated_from::<u64>(DEFAULT_REFERRAL_RENAME_FEE);
+
+        /// The map from validator to the block number of last `rename_referral` operation.
+        pub LastReferralRenameOf get(fn last_referral_rename_of):
+            map hasher(twox_64_concat) T::AccountId => Option<T::BlockNumber>;
+
+        /// The validator account behind the referral id before renaming.
+        ///
+        /// The old referral id stays reserved for the validator until its next rename,
+        /// so that the deposits still referring to it are bound to the same validator.
+        pub RenamedReferralIds: map hasher(twox_64_concat) ReferralId => Option<T::AccountId>;
+
+        /// The old referral id currently reserved for the validator in `RenamedReferralIds`.
+        pub ReservedReferralIdOf get(fn reserved_referral_id_of):
+            map hasher(twox_64_concat) T::AccountId => Option<ReferralId>;
+
+        /// The nominations whose dividend will be re-bonded automatically on claim.
+        pub AutoCompound get(fn auto_compound):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
+            => bool;
+
+        /// Whether a new round over all the auto-compounding nominations is due since the
+        /// beginning of an era.
+        AutoCompoundPending get(fn auto_compound_pending): bool;
+
+        /// The raw storage key of the last auto-compounding nomination processed in the
+        /// ongoing round, `None` if there is no ongoing round.
+        AutoCompoundCursor get(fn auto_compound_cursor): Option<Vec<u8>>;
+
+        /// Maximum number of nominations being auto-compounded in a block.
+        pub MaximumAutoCompoundPerBlock get(fn maximum_auto_compound_per_block):
+            u32 = DEFAULT_MAXIMUM_AUTO_COMPOUND_PER_BLOCK;
+
+        /// The id of next nomination pool.
+        pub NextPoolId get(fn next_pool_id): PoolId;
+
+        /// The map from pool id to the profile of that nomination pool.
+        pub Pools get(fn pools):
+            map hasher(twox_64_concat) PoolId => Option<NominationPool<T::AccountId, T::BlockNumber>>;
+
+        /// The map from pool id to the vote weight ledger of that nomination pool.
+        pub PoolLedgers get(fn pool_ledgers):
+            map hasher(twox_64_concat) PoolId => PoolLedger<BalanceOf<T>, VoteWeight, T::BlockNumber>;
+
+        /// The map from pool member to its vote weight ledger in the pool.
+        pub PoolMembers get(fn pool_members):
+            double_map hasher(twox_64_concat) PoolId, hasher(twox_64_concat) T::AccountId
+            => PoolMember<BalanceOf<T>, VoteWeight, T::BlockNumber>;
+
+        /// The number of members of each nomination pool.
+        pub PoolMemberCount get(fn pool_member_count): map hasher(twox_64_concat) PoolId => u32;
+
+        /// The strategy of electing the new validator set.
+        pub ElectionStrategyOf get(fn election_strategy): ElectionStrategy;
+
+        /// The best verified Phragmén election outcome submitted in the current election window.
+        pub QueuedElectionOutcome get(fn queued_election_outcome):
+            Option<ElectionOutcome<T::AccountId>>;
+
+        /// The score that a submitted election solution must be strictly better than.
+        pub MinimumElectionScore get(fn minimum_election_score): ElectionScore;
+
+        /// Mode of slashing the offenders.
+        pub SlashingModeOf get(fn slashing_mode): SlashingMode;
+
+        /// Number of eras that the nominator-proportional slashes are deferred by.
+        ///
+        /// The governance is able to cancel the deferred slashes within this window.
+        pub SlashDeferDuration get(fn slash_defer_duration): EraIndex = DEFAULT_SLASH_DEFER_DURATION;
+
+        /// The current slashing span of a validator.
+        pub SlashingSpans get(fn slashing_spans):
+            map hasher(twox_64_concat) T::AccountId => SlashingSpan;
+
+        /// All the nominator-proportional slashes to be applied at the beginning of an era.
+        pub UnappliedSlashes get(fn unapplied_slashes_of):
+            map hasher(twox_64_concat) EraIndex => Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>;
+
+        /// The performance records of validators in the last `VALIDATOR_HISTORY_DEPTH` eras.
+        pub ValidatorHistory get(fn validator_history_of):
+            double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId
+            => ValidatorEraRecord<BalanceOf<T>>;
+
+        /// The map from the account of a nomination pool to the pool id.
+        pub PoolIdOf get(fn pool_id_of):
+            map hasher(twox_64_concat) T::AccountId => Option<PoolId>;
+
+        /// The index of `Nominations` keyed by the validator first, (validator, nominator) => true.
+        pub NominatorsOf get(fn nominators_of):
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
+            => bool;
+
+        /// Number of the deferred slashes of a validator yet to be applied.
+        ///
+        /// The nominations to the validator can not be moved away until they are applied.
+        pub PendingSlashCount get(fn pending_slash_count):
+            map hasher(twox_64_concat) T::AccountId => u32;
+
+        /// Storage version of the pallet.
+        StorageVersion build(|_: &GenesisConfig<T>| Releases::V3_0_0): Releases;
     }
 
     add_extra_genesis {
@@ -273,6 +410,40 @@ decl_event!(
         ForceChilled(SessionIndex, Vec<AccountId>),
         /// Unlock the unbonded withdrawal by force. [account]
         ForceAllWithdrawn(AccountId),
+        /// The emission schedule of PCX has been updated. [new_schedule]
+        EmissionScheduleUpdated(EmissionSchedule<Balance>),
+        /// A validator changed its commission. [validator, commission]
+        CommissionUpdated(AccountId, Perbill),
+        /// A nominator claimed the staking dividends from all the nominees. [nominator, total_dividend]
+        AllClaimed(AccountId, Balance),
+        /// A nominator switched the auto-compounding of the nomination. [nominator, validator, enabled]
+        AutoCompoundUpdated(AccountId, AccountId, bool),
+        /// The claimed dividend was bonded to the validator again. [nominator, validator, amount]
+        Compounded(AccountId, AccountId, Balance),
+        /// A nomination pool was created. [pool_id, owner]
+        PoolCreated(PoolId, AccountId),
+        /// A member bonded this amount via the pool. [pool_id, member, amount]
+        PoolBonded(PoolId, AccountId, Balance),
+        /// A member unbonded this amount from the pool. [pool_id, member, amount]
+        PoolUnbonded(PoolId, AccountId, Balance),
+        /// A member withdrew the unbonded balance from the pool. [pool_id, member, amount]
+        PoolWithdrawn(PoolId, AccountId, Balance),
+        /// A member claimed the dividend from the pool. [pool_id, member, dividend]
+        PoolDividendClaimed(PoolId, AccountId, Balance),
+        /// A nominator bonded the unbonded balances to the validator again. [nominator, validator, amount]
+        RebondedUnbonded(AccountId, AccountId, Balance),
+        /// The nominator withdrew all the due unbonded balances. [nominator, amount]
+        AllDueWithdrawn(AccountId, Balance),
+        /// A better election solution was verified and queued. [era, score]
+        ElectionSolutionQueued(EraIndex, ElectionScore),
+        /// A validator renamed its referral id. [validator, old_referral_id, new_referral_id]
+        ReferralRenamed(AccountId, ReferralId, ReferralId),
+        /// The nominations to a validator were slashed. [validator, slashed_amount]
+        NominationsSlashed(AccountId, Balance),
+        /// The slash of the nominations to a validator was deferred to this era. [era, validator]
+        SlashDeferred(EraIndex, AccountId),
+        /// The deferred slashes of an era were cancelled. [era, slash_indices]
+        DeferredSlashCancelled(EraIndex, Vec<u32>),
     }
 );
 
@@ -311,6 +482,10 @@ decl_error! {
         InvalidUnbondedIndex,
         /// The unbonded balances are still in the locked state.
         UnbondedWithdrawalNotYetDue,
+        /// An account can only rebond the balance that is no more than its unbonded balances of the validator.
+        InvalidRebondUnbondedBalance,
+        /// The witness of nominations count is less than the actual one.
+        InvalidNominationsWitness,
         /// The length of referral identity is either too long or too short.
         InvalidReferralIdentityLength,
         /// The referral identity has been claimed by someone else.
@@ -319,6 +494,36 @@ decl_error! {
         XssCheckFailed,
         /// Failed to allocate the dividend.
         AllocateDividendFailed,
+        /// The parameters of emission schedule are invalid.
+        InvalidEmissionSchedule,
+        /// The commission exceeds `MaximumCommission`.
+        CommissionTooHigh,
+        /// Can not change the commission again until `CommissionChangeInterval` has passed.
+        NoMoreCommissionChange,
+        /// Can not rename the referral id again until `ReferralRenameInterval` has passed.
+        NoMoreReferralRename,
+        /// The nomination pool does not exist.
+        PoolNotFound,
+        /// The validators of a nomination pool must be distinct validators, and no more
+        /// than `MAXIMUM_POOL_VALIDATORS`.
+        InvalidPoolValidators,
+        /// The nomination pool already has `MAXIMUM_POOL_MEMBERS` members.
+        TooManyPoolMembers,
+        /// The election solution is submitted out of the election window, or the election
+        /// strategy is not `SequentialPhragmen`.
+        ElectionSolutionNotAcceptable,
+        /// The election solution is invalid.
+        InvalidElectionSolution,
+        /// The election solution is no better than the queued one.
+        WeakElectionSolution,
+        /// The score of election solution is no better than `MinimumElectionScore`.
+        ElectionScoreTooLow,
+        /// The slash indices must be sorted, distinct and point to some unapplied slashes.
+        InvalidSlashIndex,
+        /// The validator has some deferred slashes, the nominations to it can not be moved away.
+        PendingSlash,
+        /// The auto-compounding can only be enabled for the validators nominated by the account.
+        NotNominated,
     }
 }
 
@@ -340,6 +545,18 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            migration::on_runtime_upgrade::<T>()
+        }
+
+        fn on_initialize(_n: T::BlockNumber) -> Weight {
+            Self::process_auto_compounds()
+        }
+
+        fn offchain_worker(_n: T::BlockNumber) {
+            Self::offchain_election();
+        }
+
         /// Nominate the `target` with `value` of the origin account's balance locked.
         #[weight = T::WeightInfo::bond()]
         pub fn bond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
@@ -370,6 +587,7 @@ decl_module! {
             ensure!(Self::is_validator(&from) && Self::is_validator(&to), Error::<T>::NotValidator);
             ensure!(sender != from, Error::<T>::RebondSelfBondedNotAllowed);
             ensure!(value <= Self::bonded_to(&sender, &from), Error::<T>::InvalidRebondBalance);
+            ensure!(!Self::has_pending_slash(&from), Error::<T>::PendingSlash);
 
             if !Self::is_validator_bonding_itself(&sender, &to) {
                 Self::check_validator_acceptable_votes_limit(&to, value)?;
@@ -414,6 +632,7 @@ decl_module! {
             let Unbonded { value, locked_until } = unbonded_chunks[unbonded_index as usize];
             let current_block = <frame_system::Module<T>>::block_number();
             ensure!(current_block > locked_until, Error::<T>::UnbondedWithdrawalNotYetDue);
+            ensure!(!Self::has_pending_slash(&target), Error::<T>::PendingSlash);
 
             Self::apply_unlock_unbonded_withdrawal(&sender, value);
 
@@ -425,6 +644,46 @@ decl_module! {
             Self::deposit_event(Event::<T>::Withdrawn(sender, value));
         }
 
+        /// Move the `value` of the newest unbonded balances for validator `target` back
+        /// into the nomination, without waiting for the unbonded balances to be due.
+        #[weight = T::WeightInfo::rebond_unbonded()]
+        fn rebond_unbonded(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+            ensure!(Self::is_validator(&target), Error::<T>::NotValidator);
+            let unbonded = Self::unbonded_chunks_of(&sender, &target)
+                .iter()
+                .fold(Zero::zero(), |acc: BalanceOf<T>, chunk| acc + chunk.value);
+            ensure!(value <= unbonded, Error::<T>::InvalidRebondUnbondedBalance);
+            if !Self::is_validator_bonding_itself(&sender, &target) {
+                Self::check_validator_acceptable_votes_limit(&target, value)?;
+            }
+
+            Self::apply_rebond_unbonded(&sender, &target, value);
+        }
+
+        /// Unlock all the frozen unbonded balances that are due across all the nominees.
+        ///
+        /// `nominations_count` is the witness of the number of validators the origin
+        /// account has nominated, which must be no less than the actual one.
+        #[weight = T::WeightInfo::unlock_all_due(*nominations_count)]
+        fn unlock_all_due(origin, #[compact] nominations_count: u32) {
+            let sender = ensure_signed(origin)?;
+
+            let nominations = Nominations::<T>::iter_prefix(&sender).collect::<Vec<_>>();
+            ensure!(
+                nominations.len() as u32 <= nominations_count,
+                Error::<T>::InvalidNominationsWitness
+            );
+
+            let value = Self::apply_unlock_all_due(&sender, nominations);
+            ensure!(!value.is_zero(), Error::<T>::UnbondedWithdrawalNotYetDue);
+
+            Self::deposit_event(Event::<T>::AllDueWithdrawn(sender, value));
+        }
+
         /// Claim the staking reward given the `target` validator.
         #[weight = T::WeightInfo::claim()]
         fn claim(origin, target: <T::Lookup as StaticLookup>::Source) {
@@ -436,6 +695,103 @@ decl_module! {
             <Self as Claim<T::AccountId>>::claim(&sender, &target)?;
         }
 
+        /// Claim the staking dividends from all the validators the origin account has nominated.
+        ///
+        /// The zero dividends are skipped. `nominations_count` is the witness of the number
+        /// of validators the origin account has nominated, which must be no less than the
+        /// actual one.
+        #[weight = T::WeightInfo::claim_all(*nominations_count)]
+        fn claim_all(origin, #[compact] nominations_count: u32) {
+            let sender = ensure_signed(origin)?;
+
+            let validators = Nominations::<T>::iter_prefix(&sender)
+                .map(|(validator, _)| validator)
+                .collect::<Vec<_>>();
+            ensure!(
+                validators.len() as u32 <= nominations_count,
+                Error::<T>::InvalidNominationsWitness
+            );
+
+            let total_dividend = Self::apply_claim_all(&sender, validators);
+            Self::deposit_event(Event::<T>::AllClaimed(sender, total_dividend));
+        }
+
+        /// Switch the auto-compounding of the nomination to validator `target`.
+        ///
+        /// Once enabled, the dividend will be bonded to the same validator again on each
+        /// claim, and the nomination is claimed automatically at the beginning of every era.
+        /// It's disabled automatically once the whole nomination is moved away.
+        #[weight = T::WeightInfo::set_auto_compound()]
+        fn set_auto_compound(origin, target: <T::Lookup as StaticLookup>::Source, enabled: bool) {
+            let sender = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            ensure!(Self::is_validator(&target), Error::<T>::NotValidator);
+
+            if enabled {
+                ensure!(!Self::bonded_to(&sender, &target).is_zero(), Error::<T>::NotNominated);
+                AutoCompound::<T>::insert(&sender, &target, true);
+            } else {
+                AutoCompound::<T>::remove(&sender, &target);
+            }
+
+            Self::deposit_event(Event::<T>::AutoCompoundUpdated(sender, target, enabled));
+        }
+
+        /// Create a nomination pool nominating `validators`.
+        #[weight = T::WeightInfo::create_pool(validators.len() as u32)]
+        fn create_pool(origin, validators: Vec<<T::Lookup as StaticLookup>::Source>) {
+            let sender = ensure_signed(origin)?;
+
+            ensure!(
+                !validators.is_empty() && validators.len() <= MAXIMUM_POOL_VALIDATORS as usize,
+                Error::<T>::InvalidPoolValidators
+            );
+            let mut pool_validators = Vec::with_capacity(validators.len());
+            for validator in validators {
+                let validator = T::Lookup::lookup(validator)?;
+                ensure!(Self::is_validator(&validator), Error::<T>::NotValidator);
+                ensure!(!pool_validators.contains(&validator), Error::<T>::InvalidPoolValidators);
+                pool_validators.push(validator);
+            }
+
+            Self::apply_create_pool(sender, pool_validators);
+        }
+
+        /// Bond the `value` of the origin account's balance via the nomination pool.
+        #[weight = T::WeightInfo::bond_to_pool()]
+        fn bond_to_pool(origin, #[compact] pool_id: PoolId, #[compact] value: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+            ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+            Self::apply_bond_to_pool(&sender, pool_id, value)?;
+        }
+
+        /// Unbond the `value` of points from the nomination pool.
+        ///
+        /// The unbonded balances can be withdrawn after the bonding duration.
+        #[weight = T::WeightInfo::unbond_from_pool()]
+        fn unbond_from_pool(origin, #[compact] pool_id: PoolId, #[compact] value: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+            ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+            Self::apply_unbond_from_pool(&sender, pool_id, value)?;
+        }
+
+        /// Withdraw all the due unbonded balances from the nomination pool.
+        #[weight = T::WeightInfo::withdraw_from_pool()]
+        fn withdraw_from_pool(origin, #[compact] pool_id: PoolId) {
+            let sender = ensure_signed(origin)?;
+            Self::apply_withdraw_from_pool(&sender, pool_id)?;
+        }
+
+        /// Claim the dividend of the origin account from the nomination pool.
+        ///
+        /// The pool claims the dividends from all its validators first.
+        #[weight = T::WeightInfo::claim_from_pool(MAXIMUM_POOL_VALIDATORS)]
+        fn claim_from_pool(origin, #[compact] pool_id: PoolId) {
+            let sender = ensure_signed(origin)?;
+            Self::apply_claim_from_pool(&sender, pool_id)?;
+        }
+
         /// Declare the desire to validate for the origin account.
         #[weight = T::WeightInfo::validate()]
         fn validate(origin) {
@@ -486,6 +842,91 @@ decl_module! {
             }
         }
 
+        /// Set the commission of the origin validator.
+        ///
+        /// The commission is the proportion of the session reward paid to the validator
+        /// directly, and it can be changed once per `CommissionChangeInterval`.
+        #[weight = T::WeightInfo::set_commission()]
+        fn set_commission(origin, new: Perbill) {
+            let sender = ensure_signed(origin)?;
+            ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+            ensure!(new <= Self::maximum_commission(), Error::<T>::CommissionTooHigh);
+
+            let current_block = <frame_system::Module<T>>::block_number();
+            Validators::<T>::try_mutate(&sender, |validator| -> DispatchResult {
+                if let Some(last_change) = validator.last_commission_change {
+                    ensure!(
+                        current_block >= last_change.saturating_add(Self::commission_change_interval()),
+                        Error::<T>::NoMoreCommissionChange
+                    );
+                }
+                validator.commission = new;
+                validator.last_commission_change = Some(current_block);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::CommissionUpdated(sender, new));
+        }
+
+        /// Rename the referral id of the origin validator at the cost of `ReferralRenameFee`.
+        ///
+        /// The old referral id keeps pointing at the validator, which can be claimed
+        /// back by the validator later. Only the latest old referral id is reserved,
+        /// the one reserved by the previous rename is released.
+        #[weight = T::WeightInfo::rename_referral().saturating_add(T::DbWeight::get().reads_writes(2, 2))]
+        fn rename_referral(origin, new_id: ReferralId) {
+            let sender = ensure_signed(origin)?;
+            ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+
+            let current_block = <frame_system::Module<T>>::block_number();
+            if let Some(last_rename) = Self::last_referral_rename_of(&sender) {
+                ensure!(
+                    current_block >= last_rename.saturating_add(Self::referral_rename_interval()),
+                    Error::<T>::NoMoreReferralRename
+                );
+            }
+
+            let is_reclaimed = Self::renamed_validator_for(&new_id).as_ref() == Some(&sender);
+            if !is_reclaimed {
+                Self::check_referral_id(&new_id)?;
+            }
+
+            let fee = Self::referral_rename_fee();
+            if !fee.is_zero() {
+                Self::transfer(&sender, &T::TreasuryAccount::treasury_account(), fee)?;
+            }
+
+            Self::apply_rename_referral(&sender, new_id, current_block);
+        }
+
+        /// Submit the Phragmén election solution for the new era, only by the offchain workers.
+        ///
+        /// The solution is verified and queued if it's better than the queued one.
+        ///
+        /// The submitter must be a current validator, `validator_index` is its index in
+        /// `ElectionAuthorities` and the whole payload is signed by its session key,
+        /// which is checked in `validate_unsigned`.
+        #[weight = T::WeightInfo::submit_election_solution(winners.len() as u32, assignments.len() as u32)]
+        fn submit_election_solution(
+            origin,
+            era: EraIndex,
+            winners: Vec<T::AccountId>,
+            assignments: Vec<StakeAssignment<T::AccountId, BalanceOf<T>>>,
+            score: ElectionScore,
+            validator_index: u32,
+            // The signature is verified in `validate_unsigned`.
+            _signature: <T::ElectionAuthorityId as RuntimeAppPublic>::Signature
+        ) {
+            ensure_none(origin)?;
+
+            let computed_score = Self::check_election_solution(era, &winners, &assignments)?;
+            ensure!(computed_score == score, Error::<T>::InvalidElectionSolution);
+            Self::ensure_better_election_score(era, &score)?;
+
+            QueuedElectionOutcome::<T>::put(ElectionOutcome { era, winners, score });
+            Self::deposit_event(Event::<T>::ElectionSolutionQueued(era, score));
+        }
+
         #[weight = T::WeightInfo::set_validator_count()]
         fn set_validator_count(origin, #[compact] new: u32) {
             ensure_root(origin)?;
@@ -522,6 +963,93 @@ decl_module! {
             SessionsPerEra::put(new);
         }
 
+        /// Set a new emission schedule of PCX, taking effect from the next session.
+        #[weight = T::WeightInfo::set_emission_schedule()]
+        fn set_emission_schedule(origin, new: EmissionSchedule<BalanceOf<T>>) {
+            ensure_root(origin)?;
+            ensure!(new.is_valid(), Error::<T>::InvalidEmissionSchedule);
+            EmissionScheduleOf::<T>::put(new.clone());
+            Self::deposit_event(Event::<T>::EmissionScheduleUpdated(new));
+        }
+
+        #[weight = T::WeightInfo::set_maximum_commission()]
+        fn set_maximum_commission(origin, new: Perbill) {
+            ensure_root(origin)?;
+            MaximumCommission::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_commission_change_interval()]
+        fn set_commission_change_interval(origin, #[compact] new: T::BlockNumber) {
+            ensure_root(origin)?;
+            CommissionChangeInterval::<T>::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_election_strategy()]
+        fn set_election_strategy(origin, new: ElectionStrategy) {
+            ensure_root(origin)?;
+            ElectionStrategyOf::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_minimum_election_score()]
+        fn set_minimum_election_score(origin, new: ElectionScore) {
+            ensure_root(origin)?;
+            MinimumElectionScore::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_referral_rename_interval()]
+        fn set_referral_rename_interval(origin, #[compact] new: T::BlockNumber) {
+            ensure_root(origin)?;
+            ReferralRenameInterval::<T>::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_referral_rename_fee()]
+        fn set_referral_rename_fee(origin, #[compact] new: BalanceOf<T>) {
+            ensure_root(origin)?;
+            ReferralRenameFee::<T>::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_slashing_mode()]
+        fn set_slashing_mode(origin, new: SlashingMode) {
+            ensure_root(origin)?;
+            SlashingModeOf::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_slash_defer_duration()]
+        fn set_slash_defer_duration(origin, #[compact] new: EraIndex) {
+            ensure_root(origin)?;
+            SlashDeferDuration::put(new);
+        }
+
+        /// Cancel the deferred slashes of `era` given the indices in `UnappliedSlashes`.
+        #[weight = T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32)]
+        fn cancel_deferred_slash(origin, era: EraIndex, slash_indices: Vec<u32>) {
+            ensure_root(origin)?;
+            ensure!(!slash_indices.is_empty(), Error::<T>::InvalidSlashIndex);
+            ensure!(
+                slash_indices.windows(2).all(|pair| pair[0] < pair[1]),
+                Error::<T>::InvalidSlashIndex
+            );
+
+            UnappliedSlashes::<T>::try_mutate(era, |slashes| -> DispatchResult {
+                let last = *slash_indices.last().expect("slash_indices is not empty; qed");
+                ensure!((last as usize) < slashes.len(), Error::<T>::InvalidSlashIndex);
+                // Remove from the back so that the rest indices stay valid.
+                for &index in slash_indices.iter().rev() {
+                    let cancelled = slashes.remove(index as usize);
+                    Self::note_pending_slash_removed(&cancelled.validator);
+                }
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::<T>::DeferredSlashCancelled(era, slash_indices));
+        }
+
+        #[weight = T::WeightInfo::set_maximum_auto_compound_per_block()]
+        fn set_maximum_auto_compound_per_block(origin, #[compact] new: u32) {
+            ensure_root(origin)?;
+            MaximumAutoCompoundPerBlock::put(new);
+        }
+
         #[weight = 10_000_000]
         fn set_immortals(origin, new: Vec<T::AccountId>) {
             ensure_root(origin)?;
@@ -569,6 +1097,58 @@ decl_module! {
     }
 }
 
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+        if let Call::submit_election_solution(
+            era,
+            winners,
+            assignments,
+            score,
+            validator_index,
+            signature,
+        ) = call
+        {
+            let authorities = T::ElectionAuthorities::authorities();
+            let authority = authorities
+                .get(*validator_index as usize)
+                .ok_or(InvalidTransaction::BadProof)?;
+            let payload = (era, winners, assignments, score, validator_index).encode();
+            if !authority.verify(&payload, signature) {
+                return InvalidTransaction::BadProof.into();
+            }
+
+            let computed_score = Self::check_election_solution(*era, winners, assignments)
+                .map_err(|_| InvalidTransaction::Call)?;
+            if computed_score != *score {
+                return InvalidTransaction::BadProof.into();
+            }
+            Self::ensure_better_election_score(*era, score)
+                .map_err(|_| InvalidTransaction::Stale)?;
+
+            // A better solution replaces the worse one in the pool.
+            let priority =
+                ELECTION_SOLUTION_UNSIGNED_PRIORITY.saturating_add(score[0].saturated_into());
+            ValidTransaction::with_tag_prefix("XStakingOffchainElection")
+                .priority(priority)
+                .and_provides(era)
+                .longevity(T::SessionDuration::get().saturated_into::<u64>())
+                .propagate(true)
+                .build()
+        } else {
+            InvalidTransaction::Call.into()
+        }
+    }
+}
+
+/// Means for getting the session keys of the current validators.
+pub trait ElectionAuthorities<AuthorityId> {
+    /// Returns the session keys of the current validators, in the same order as
+    /// the session validators.
+    fn authorities() -> Vec<AuthorityId>;
+}
+
 /// Means for interacting with a specialized version of the `session` trait.
 ///
 /// This is needed because `Staking` sets the `ValidatorIdOf` of the `pallet_session::Trait`
@@ -582,6 +1162,9 @@ pub trait SessionInterface<AccountId>: frame_system::Trait {
 
     /// Get the validators from session.
     fn validators() -> Vec<AccountId>;
+
+    /// Get the current session index.
+    fn current_index() -> SessionIndex;
 }
 
 impl<T: Trait> SessionInterface<<T as frame_system::Trait>::AccountId> for T
@@ -601,6 +1184,10 @@ where
     fn validators() -> Vec<<T as frame_system::Trait>::AccountId> {
         <pallet_session::Module<T>>::validators()
     }
+
+    fn current_index() -> SessionIndex {
+        <pallet_session::Module<T>>::current_index()
+    }
 }
 
 impl<T: Trait> xpallet_support::traits::Validator<T::AccountId> for Module<T> {
@@ -611,6 +1198,10 @@ impl<T: Trait> xpallet_support::traits::Validator<T::AccountId> for Module<T> {
     fn validator_for(name: &[u8]) -> Option<T::AccountId> {
         Self::validator_for(name)
     }
+
+    fn renamed_validator_for(name: &[u8]) -> Option<T::AccountId> {
+        Self::renamed_validator_for(name)
+    }
 }
 
 impl<T: Trait> Module<T> {
@@ -637,6 +1228,7 @@ impl<T: Trait> Module<T> {
                 Nominations::<T>::mutate(who, who, |nominator| {
                     nominator.nomination = *self_bonded;
                 });
+                NominatorsOf::<T>::insert(who, who, true);
             }
             Self::apply_register(who, referral_id.to_vec());
             // These validators will be chilled on the network startup.
@@ -661,6 +1253,7 @@ impl<T: Trait> Module<T> {
             Nominations::<T>::mutate(sender, target, |nominator| {
                 nominator.nomination = value;
             });
+            NominatorsOf::<T>::insert(target, sender, true);
         }
         Ok(())
     }
@@ -704,6 +1297,12 @@ impl<T: Trait> Module<T> {
         });
     }
 
+    /// Returns true if `validator` has some deferred slashes yet to be applied.
+    #[inline]
+    pub fn has_pending_slash(validator: &T::AccountId) -> bool {
+        PendingSlashCount::<T>::contains_key(validator)
+    }
+
     /// Returns true if the account `who` is a validator.
     #[inline]
     pub fn is_validator(who: &T::AccountId) -> bool {
@@ -716,6 +1315,12 @@ impl<T: Trait> Module<T> {
         ValidatorFor::<T>::get(referral_id)
     }
 
+    /// Returns the validator account that has renamed the given referral id.
+    #[inline]
+    pub fn renamed_validator_for(referral_id: &[u8]) -> Option<T::AccountId> {
+        RenamedReferralIds::<T>::get(referral_id)
+    }
+
     /// Return true if the validator `who` is chilled.
     #[inline]
     pub fn is_chilled(who: &T::AccountId) -> bool {
@@ -844,7 +1449,8 @@ impl<T: Trait> Module<T> {
             Error::<T>::XssCheckFailed
         );
         ensure!(
-            Self::validator_for(referral_id).is_none(),
+            Self::validator_for(referral_id).is_none()
+                && Self::renamed_validator_for(referral_id).is_none(),
             Error::<T>::OccupiedReferralIdentity
         );
         Ok(())
@@ -940,6 +1546,7 @@ impl<T: Trait> Module<T> {
                 < Self::maximum_unbonded_chunk_size() as usize,
             Error::<T>::NoMoreUnbondChunks
         );
+        ensure!(!Self::has_pending_slash(target), Error::<T>::PendingSlash);
         Ok(())
     }
 
@@ -952,6 +1559,18 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// `rebond_unbonded` turns the locked type `BondedWithdrawal` back into `Bonded`.
+    fn rebond_unbonded_reserve(who: &T::AccountId, value: BalanceOf<T>) {
+        Locks::<T>::mutate(who, |locks| {
+            let withdrawal = locks.entry(LockedType::BondedWithdrawal).or_default();
+            *withdrawal = withdrawal.saturating_sub(value);
+            if withdrawal.is_zero() {
+                locks.remove(&LockedType::BondedWithdrawal);
+            }
+            *locks.entry(LockedType::Bonded).or_default() += value;
+        });
+    }
+
     /// Returns the total locked balances in Staking.
     fn total_locked_of(who: &T::AccountId) -> BalanceOf<T> {
         Self::locks(who)
@@ -985,6 +1604,29 @@ impl<T: Trait> Module<T> {
         Self::set_validator_vote_weight(target, target_weight, current_block, delta);
     }
 
+    fn apply_rename_referral(
+        who: &T::AccountId,
+        new_id: ReferralId,
+        current_block: T::BlockNumber,
+    ) {
+        let old_id = Validators::<T>::mutate(who, |validator| {
+            sp_std::mem::replace(&mut validator.referral_id, new_id.clone())
+        });
+        ValidatorFor::<T>::remove(&old_id);
+        ValidatorFor::<T>::insert(&new_id, who.clone());
+        // Release the referral id reserved by the previous rename, it's either
+        // reclaimed as `new_id` or no longer worth keeping.
+        if let Some(reserved_id) = ReservedReferralIdOf::<T>::take(who) {
+            if Self::renamed_validator_for(&reserved_id).as_ref() == Some(who) {
+                RenamedReferralIds::<T>::remove(&reserved_id);
+            }
+        }
+        RenamedReferralIds::<T>::insert(&old_id, who.clone());
+        ReservedReferralIdOf::<T>::insert(who, old_id.clone());
+        LastReferralRenameOf::<T>::insert(who, current_block);
+        Self::deposit_event(Event::<T>::ReferralRenamed(who.clone(), old_id, new_id));
+    }
+
     fn apply_register(who: &T::AccountId, referral_id: ReferralId) {
         let current_block = <frame_system::Module<T>>::block_number();
         ValidatorFor::<T>::insert(&referral_id, who.clone());
@@ -993,6 +1635,7 @@ impl<T: Trait> Module<T> {
             ValidatorProfile {
                 registered_at: current_block,
                 referral_id,
+                commission: DEFAULT_COMMISSION,
                 ..Default::default()
             },
         );
@@ -1033,6 +1676,35 @@ impl<T: Trait> Module<T> {
         ));
     }
 
+    fn apply_rebond_unbonded(who: &T::AccountId, target: &T::AccountId, value: BalanceOf<T>) {
+        Nominations::<T>::mutate(who, target, |nominator| {
+            // Take the value from the newest chunks first.
+            let chunks = &mut nominator.unbonded_chunks;
+            chunks.sort_by_key(|chunk| chunk.locked_until);
+            let mut remaining = value;
+            while let Some(newest) = chunks.last_mut() {
+                if newest.value > remaining {
+                    newest.value -= remaining;
+                    break;
+                }
+                remaining -= newest.value;
+                chunks.pop();
+                if remaining.is_zero() {
+                    break;
+                }
+            }
+        });
+
+        Self::rebond_unbonded_reserve(who, value);
+        Self::update_vote_weight(who, target, Delta::Add(value));
+
+        Self::deposit_event(Event::<T>::RebondedUnbonded(
+            who.clone(),
+            target.clone(),
+            value,
+        ));
+    }
+
     fn mutate_unbonded_chunks(
         who: &T::AccountId,
         target: &T::AccountId,
@@ -1085,6 +1757,42 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Unlocks all the due unbonded chunks in `nominations` of `who`.
+    ///
+    /// Returns the total unlocked balance.
+    fn apply_unlock_all_due(
+        who: &T::AccountId,
+        nominations: Vec<(
+            T::AccountId,
+            NominatorLedger<BalanceOf<T>, VoteWeight, T::BlockNumber>,
+        )>,
+    ) -> BalanceOf<T> {
+        let current_block = <frame_system::Module<T>>::block_number();
+        let mut total_unlocked: BalanceOf<T> = Zero::zero();
+        for (target, ledger) in nominations {
+            let (due, pending): (Vec<_>, Vec<_>) = ledger
+                .unbonded_chunks
+                .into_iter()
+                .partition(|chunk| current_block > chunk.locked_until);
+            // The unbonded balances stay at stake until the pending slashes are applied.
+            if due.is_empty() || Self::has_pending_slash(&target) {
+                continue;
+            }
+            total_unlocked = due
+                .iter()
+                .fold(total_unlocked, |acc, chunk| acc.saturating_add(chunk.value));
+            Nominations::<T>::mutate(who, &target, |nominator| {
+                nominator.unbonded_chunks = pending;
+            });
+        }
+
+        if !total_unlocked.is_zero() {
+            Self::apply_unlock_unbonded_withdrawal(who, total_unlocked);
+        }
+
+        total_unlocked
+    }
+
     fn apply_unlock_unbonded_withdrawal(who: &T::AccountId, value: BalanceOf<T>) {
         let new_bonded = Self::total_locked_of(who) - value;
         Self::set_lock(who, new_bonded);
@@ -0,0 +1,79 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    traits::Get, weights::Weight, IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap,
+    StorageMap, StorageValue,
+};
+use sp_runtime::RuntimeDebug;
+
+use chainx_primitives::ReferralId;
+use xp_logging::info;
+
+use crate::constants::DEFAULT_COMMISSION;
+use crate::types::{Releases, ValidatorProfile};
+use crate::{
+    Nominations, NominatorsOf, PendingSlashCount, StorageVersion, Trait, UnappliedSlashes,
+    Validators,
+};
+
+/// `ValidatorProfile` before the commission was introduced.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+struct ValidatorProfileV1<BlockNumber> {
+    registered_at: BlockNumber,
+    is_chilled: bool,
+    last_chilled: Option<BlockNumber>,
+    referral_id: ReferralId,
+}
+
+/// Apply all of the migrations due to `StorageVersion`.
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    match StorageVersion::get() {
+        Releases::V3_0_0 => 0,
+        Releases::V2_0_0 => migrate_to_v3::<T>(),
+        Releases::V1_0_0 => migrate_to_v2::<T>().saturating_add(migrate_to_v3::<T>()),
+    }
+}
+
+/// Initialize the commission of the existing validators with the default value.
+pub fn migrate_to_v2<T: Trait>() -> Weight {
+    Validators::<T>::translate::<ValidatorProfileV1<T::BlockNumber>, _>(|_, old| {
+        Some(ValidatorProfile {
+            registered_at: old.registered_at,
+            is_chilled: old.is_chilled,
+            last_chilled: old.last_chilled,
+            referral_id: old.referral_id,
+            commission: DEFAULT_COMMISSION,
+            last_commission_change: None,
+        })
+    });
+    let count = Validators::<T>::iter().count() as Weight;
+    StorageVersion::put(Releases::V2_0_0);
+    info!(
+        "[migrate_to_v2] initialized the commission of {} validators",
+        count
+    );
+    T::DbWeight::get().reads_writes(count * 2 + 1, count + 1)
+}
+
+/// Build the index of the nominators of each validator and count the deferred slashes.
+pub fn migrate_to_v3<T: Trait>() -> Weight {
+    let mut nominations = 0u64;
+    for (nominator, validator, _) in Nominations::<T>::iter() {
+        NominatorsOf::<T>::insert(validator, nominator, true);
+        nominations += 1;
+    }
+    let mut slashes = 0u64;
+    for (_, unapplied) in UnappliedSlashes::<T>::iter() {
+        for slash in unapplied {
+            PendingSlashCount::<T>::mutate(slash.validator, |count| *count += 1);
+            slashes += 1;
+        }
+    }
+    StorageVersion::put(Releases::V3_0_0);
+    info!(
+        "[migrate_to_v3] indexed {} nominations and {} deferred slashes",
+        nominations, slashes
+    );
+    T::DbWeight::get().reads_writes(nominations + slashes + 1, nominations + slashes + 1)
+}
@@ -5,7 +5,7 @@ use crate::{Module, Trait};
 use frame_support::{impl_outer_event, impl_outer_origin, parameter_types, weights::Weight};
 use sp_core::H256;
 use sp_runtime::{
-    testing::{Header, UintAuthorityId},
+    testing::{Header, TestXt, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
     Perbill,
 };
@@ -181,6 +181,13 @@ impl pallet_session::Trait for Test {
     type WeightInfo = ();
 }
 
+pub type Extrinsic = TestXt<Call<Test>, ()>;
+
+impl frame_system::offchain::SendTransactionTypes<Call<Test>> for Test {
+    type OverarchingCall = Call<Test>;
+    type Extrinsic = Extrinsic;
+}
+
 pub struct DummyTreasuryAccount;
 
 impl TreasuryAccount<AccountId> for DummyTreasuryAccount {
@@ -217,9 +224,20 @@ impl Trait for Test {
     type SessionInterface = Self;
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
+    type ElectionAuthorityId = UintAuthorityId;
+    type ElectionAuthorities = Self;
     type WeightInfo = ();
 }
 
+impl ElectionAuthorities<UintAuthorityId> for Test {
+    fn authorities() -> Vec<UintAuthorityId> {
+        Session::validators()
+            .into_iter()
+            .map(UintAuthorityId::from)
+            .collect()
+    }
+}
+
 thread_local! {
     static SESSION: RefCell<(Vec<AccountId>, HashSet<AccountId>)> = RefCell::new(Default::default());
     static SESSION_PER_ERA: RefCell<SessionIndex> = RefCell::new(3);
@@ -0,0 +1,488 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Nomination pools.
+//!
+//! The members of a pool deposit PCX into the pool account, which bonds them to the
+//! validators of the pool. The pool members split the dividends claimed by the pool
+//! according to their vote weight in the pool, the same way the nominators split the
+//! reward pot of a validator.
+
+use sp_std::{marker::PhantomData, prelude::*};
+
+use frame_support::{
+    ensure,
+    storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap, StorageValue},
+    traits::{Currency, ExistenceRequirement},
+};
+use sp_arithmetic::{helpers_128bit::multiply_by_rational, traits::BaseArithmetic};
+use sp_runtime::{
+    traits::{AccountIdConversion, SaturatedConversion, Saturating, Zero},
+    DispatchResult, ModuleId,
+};
+
+use xp_mining_common::{
+    generic_weight_factors, BaseMiningWeight, Claim, ComputeMiningWeight, Delta, WeightFactors,
+    WeightType,
+};
+
+use crate::{
+    constants::MAXIMUM_POOL_MEMBERS, types::*, BalanceOf, Error, Event, Module, NextPoolId,
+    PoolIdOf, PoolLedgers, PoolMemberCount, PoolMembers, Pools, Trait,
+};
+
+/// Module id for deriving the accounts of nomination pools.
+const POOL_MODULE_ID: ModuleId = ModuleId(*b"pcx/pool");
+
+/// Module id for deriving the reward accounts of nomination pools.
+const POOL_REWARD_MODULE_ID: ModuleId = ModuleId(*b"pcx/prwd");
+
+impl<Balance, BlockNumber> BaseMiningWeight<Balance, BlockNumber>
+    for PoolLedger<Balance, VoteWeight, BlockNumber>
+where
+    Balance: Default + BaseArithmetic + Copy,
+    BlockNumber: Default + BaseArithmetic + Copy,
+{
+    fn amount(&self) -> Balance {
+        self.total_points
+    }
+
+    fn set_amount(&mut self, new: Balance) {
+        self.total_points = new;
+    }
+
+    fn last_acum_weight(&self) -> WeightType {
+        self.last_total_weight
+    }
+
+    fn set_last_acum_weight(&mut self, latest_weight: WeightType) {
+        self.last_total_weight = latest_weight;
+    }
+
+    fn last_acum_weight_update(&self) -> BlockNumber {
+        self.last_total_weight_update
+    }
+
+    fn set_last_acum_weight_update(&mut self, current_block: BlockNumber) {
+        self.last_total_weight_update = current_block;
+    }
+}
+
+impl<Balance, BlockNumber> BaseMiningWeight<Balance, BlockNumber>
+    for PoolMember<Balance, VoteWeight, BlockNumber>
+where
+    Balance: Default + BaseArithmetic + Copy,
+    BlockNumber: Default + BaseArithmetic + Copy,
+{
+    fn amount(&self) -> Balance {
+        self.points
+    }
+
+    fn set_amount(&mut self, new: Balance) {
+        self.points = new;
+    }
+
+    fn last_acum_weight(&self) -> WeightType {
+        self.last_weight
+    }
+
+    fn set_last_acum_weight(&mut self, latest_weight: WeightType) {
+        self.last_weight = latest_weight;
+    }
+
+    fn last_acum_weight_update(&self) -> BlockNumber {
+        self.last_weight_update
+    }
+
+    fn set_last_acum_weight_update(&mut self, current_block: BlockNumber) {
+        self.last_weight_update = current_block;
+    }
+}
+
+/// Mining weight computation of the pool members, the claimee is the pool.
+pub struct PoolMining<T>(PhantomData<T>);
+
+impl<T: Trait> ComputeMiningWeight<T::AccountId, T::BlockNumber> for PoolMining<T> {
+    type Claimee = PoolId;
+    type Error = Error<T>;
+
+    fn claimer_weight_factors(
+        who: &T::AccountId,
+        target: &Self::Claimee,
+        current_block: T::BlockNumber,
+    ) -> WeightFactors {
+        let member = PoolMembers::<T>::get(target, who);
+        generic_weight_factors::<BalanceOf<T>, T::BlockNumber, _>(member, current_block)
+    }
+
+    fn claimee_weight_factors(
+        target: &Self::Claimee,
+        current_block: T::BlockNumber,
+    ) -> WeightFactors {
+        let pool_ledger = PoolLedgers::<T>::get(target);
+        generic_weight_factors::<BalanceOf<T>, T::BlockNumber, _>(pool_ledger, current_block)
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Returns the account that bonds the funds of pool members.
+    pub fn pool_account(pool_id: PoolId) -> T::AccountId {
+        POOL_MODULE_ID.into_sub_account(pool_id)
+    }
+
+    /// Returns the account that holds the dividends claimed by the pool.
+    pub fn pool_reward_account(pool_id: PoolId) -> T::AccountId {
+        POOL_REWARD_MODULE_ID.into_sub_account(pool_id)
+    }
+
+    pub(crate) fn apply_create_pool(owner: T::AccountId, validators: Vec<T::AccountId>) -> PoolId {
+        let pool_id = NextPoolId::mutate(|id| {
+            let pool_id = *id;
+            *id += 1;
+            pool_id
+        });
+        PoolIdOf::<T>::insert(Self::pool_account(pool_id), pool_id);
+        Pools::<T>::insert(
+            pool_id,
+            NominationPool {
+                owner: owner.clone(),
+                validators,
+                created_at: <frame_system::Module<T>>::block_number(),
+            },
+        );
+        Self::deposit_event(Event::<T>::PoolCreated(pool_id, owner));
+        pool_id
+    }
+
+    /// Settles and updates the vote weight of `member` and the pool given the points delta.
+    fn update_pool_weight(member: &T::AccountId, pool_id: PoolId, delta: Delta<BalanceOf<T>>) {
+        let current_block = <frame_system::Module<T>>::block_number();
+
+        let member_weight = <PoolMining<T> as ComputeMiningWeight<
+            T::AccountId,
+            T::BlockNumber,
+        >>::settle_claimer_weight(member, &pool_id, current_block);
+        let pool_weight = <PoolMining<T> as ComputeMiningWeight<
+            T::AccountId,
+            T::BlockNumber,
+        >>::settle_claimee_weight(&pool_id, current_block);
+
+        Self::set_pool_member_weight(member, pool_id, member_weight, current_block, delta);
+        Self::set_pool_weight(pool_id, pool_weight, current_block, delta);
+    }
+
+    fn set_pool_member_weight(
+        member: &T::AccountId,
+        pool_id: PoolId,
+        new_weight: WeightType,
+        current_block: T::BlockNumber,
+        delta: Delta<BalanceOf<T>>,
+    ) {
+        PoolMembers::<T>::mutate(pool_id, member, |member| {
+            member.points = delta.calculate(member.points);
+            member.last_weight = new_weight;
+            member.last_weight_update = current_block;
+        });
+    }
+
+    fn set_pool_weight(
+        pool_id: PoolId,
+        new_weight: WeightType,
+        current_block: T::BlockNumber,
+        delta: Delta<BalanceOf<T>>,
+    ) {
+        PoolLedgers::<T>::mutate(pool_id, |pool_ledger| {
+            pool_ledger.total_points = delta.calculate(pool_ledger.total_points);
+            pool_ledger.last_total_weight = new_weight;
+            pool_ledger.last_total_weight_update = current_block;
+        });
+    }
+
+    /// Removes `member` from the pool once it has no points, unbonded balances
+    /// or vote weight left in the pool.
+    fn try_reap_pool_member(member: &T::AccountId, pool_id: PoolId) {
+        if !PoolMembers::<T>::contains_key(pool_id, member) {
+            return;
+        }
+        let pool_member = Self::pool_members(pool_id, member);
+        if pool_member.points.is_zero()
+            && pool_member.unbonded_chunks.is_empty()
+            && pool_member.last_weight == 0
+        {
+            PoolMembers::<T>::remove(pool_id, member);
+            PoolMemberCount::mutate(pool_id, |count| *count = count.saturating_sub(1));
+        }
+    }
+
+    /// Reduces the points of the pool members proportionally by the `value` slashed from
+    /// the pool account, so that the total points still equal the pool nominations.
+    ///
+    /// There are at most `MAXIMUM_POOL_MEMBERS` members to be iterated, returns the
+    /// number of members iterated.
+    pub(crate) fn slash_pool_points(pool_id: PoolId, value: BalanceOf<T>) -> u32 {
+        let total_points = Self::pool_ledgers(pool_id).total_points;
+        let value = value.min(total_points);
+        if value.is_zero() {
+            return 0;
+        }
+
+        let (total_points, value) = (
+            total_points.saturated_into::<u128>(),
+            value.saturated_into::<u128>(),
+        );
+        let mut cuts = PoolMembers::<T>::iter_prefix(pool_id)
+            .map(|(member, pool_member)| {
+                let points = pool_member.points.saturated_into::<u128>();
+                let cut = multiply_by_rational(points, value, total_points).unwrap_or(points);
+                (member, points, cut)
+            })
+            .collect::<Vec<_>>();
+
+        // Spread the rounding remainder over the members one unit each.
+        let mut remainder =
+            value.saturating_sub(cuts.iter().fold(0u128, |acc, (_, _, cut)| acc + cut));
+        for (_, points, cut) in cuts.iter_mut() {
+            if remainder.is_zero() {
+                break;
+            }
+            if *cut < *points {
+                *cut += 1;
+                remainder -= 1;
+            }
+        }
+
+        let member_count = cuts.len() as u32;
+        for (member, _, cut) in cuts {
+            if cut > 0 {
+                Self::update_pool_weight(&member, pool_id, Delta::Sub(cut.saturated_into()));
+            }
+        }
+        member_count
+    }
+
+    /// Returns the pool validator with the least pool votes that is able to accept `value`.
+    fn pick_pool_validator_to_bond(
+        pool: &NominationPool<T::AccountId, T::BlockNumber>,
+        pool_account: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> Option<T::AccountId> {
+        pool.validators
+            .iter()
+            .filter(|v| {
+                Self::is_validator(v)
+                    && Self::check_validator_acceptable_votes_limit(v, value).is_ok()
+            })
+            .min_by_key(|v| Self::bonded_to(pool_account, v))
+            .cloned()
+    }
+
+    pub(crate) fn apply_bond_to_pool(
+        member: &T::AccountId,
+        pool_id: PoolId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let pool_account = Self::pool_account(pool_id);
+
+        let is_new_member = !PoolMembers::<T>::contains_key(pool_id, member);
+        if is_new_member {
+            ensure!(
+                Self::pool_member_count(pool_id) < MAXIMUM_POOL_MEMBERS,
+                Error::<T>::TooManyPoolMembers
+            );
+        }
+
+        let validator = Self::pick_pool_validator_to_bond(&pool, &pool_account, value)
+            .ok_or(Error::<T>::NoMoreAcceptableVotes)?;
+
+        Self::transfer(member, &pool_account, value)?;
+        Self::apply_bond(&pool_account, &validator, value)?;
+        Self::update_pool_weight(member, pool_id, Delta::Add(value));
+        if is_new_member {
+            PoolMemberCount::mutate(pool_id, |count| *count += 1);
+        }
+
+        Self::deposit_event(Event::<T>::PoolBonded(pool_id, member.clone(), value));
+        Ok(())
+    }
+
+    pub(crate) fn apply_unbond_from_pool(
+        member: &T::AccountId,
+        pool_id: PoolId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let pool_member = Self::pool_members(pool_id, member);
+        ensure!(
+            value <= pool_member.points,
+            Error::<T>::InvalidUnbondBalance
+        );
+        ensure!(
+            pool_member.unbonded_chunks.len() < Self::maximum_unbonded_chunk_size() as usize,
+            Error::<T>::NoMoreUnbondChunks
+        );
+        // The unbonded balances of pool are not at stake, keep them in the pool
+        // until the deferred slashes of its validators are applied.
+        ensure!(
+            !pool.validators.iter().any(Self::has_pending_slash),
+            Error::<T>::PendingSlash
+        );
+
+        let pool_account = Self::pool_account(pool_id);
+
+        // Unbond from the validators with the most pool votes first.
+        let mut bonded = pool
+            .validators
+            .iter()
+            .map(|v| (v.clone(), Self::bonded_to(&pool_account, v)))
+            .filter(|(_, bonded)| !bonded.is_zero())
+            .collect::<Vec<_>>();
+        bonded.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let mut remaining = value;
+        let mut to_unbond = Vec::with_capacity(bonded.len());
+        for (validator, bonded) in bonded {
+            if remaining.is_zero() {
+                break;
+            }
+            let amount = remaining.min(bonded);
+            to_unbond.push((validator, amount));
+            remaining -= amount;
+        }
+        // Otherwise the member would be owed the balances that are not unbonded at all.
+        ensure!(remaining.is_zero(), Error::<T>::InvalidUnbondBalance);
+
+        Self::unbond_reserve(&pool_account, value)?;
+        for (validator, amount) in to_unbond {
+            Self::update_vote_weight(&pool_account, &validator, Delta::Sub(amount));
+        }
+
+        let locked_until = <frame_system::Module<T>>::block_number() + Self::bonding_duration();
+        PoolMembers::<T>::mutate(pool_id, member, |pool_member| {
+            if let Some(chunk) = pool_member
+                .unbonded_chunks
+                .iter_mut()
+                .find(|x| x.locked_until == locked_until)
+            {
+                chunk.value += value;
+            } else {
+                pool_member.unbonded_chunks.push(Unbonded {
+                    value,
+                    locked_until,
+                });
+            }
+        });
+        Self::update_pool_weight(member, pool_id, Delta::Sub(value));
+
+        Self::deposit_event(Event::<T>::PoolUnbonded(pool_id, member.clone(), value));
+        Ok(())
+    }
+
+    /// Pays all the due unbonded balances of `member` back from the pool account.
+    pub(crate) fn apply_withdraw_from_pool(
+        member: &T::AccountId,
+        pool_id: PoolId,
+    ) -> DispatchResult {
+        let current_block = <frame_system::Module<T>>::block_number();
+        let mut pool_member = Self::pool_members(pool_id, member);
+        ensure!(
+            !pool_member.unbonded_chunks.is_empty(),
+            Error::<T>::EmptyUnbondedChunks
+        );
+
+        let value = pool_member
+            .unbonded_chunks
+            .iter()
+            .filter(|chunk| current_block > chunk.locked_until)
+            .fold(Zero::zero(), |acc: BalanceOf<T>, chunk| acc + chunk.value);
+        ensure!(!value.is_zero(), Error::<T>::UnbondedWithdrawalNotYetDue);
+
+        let pool_account = Self::pool_account(pool_id);
+        Self::apply_unlock_unbonded_withdrawal(&pool_account, value);
+        T::Currency::transfer(
+            &pool_account,
+            member,
+            value,
+            ExistenceRequirement::AllowDeath,
+        )?;
+
+        pool_member
+            .unbonded_chunks
+            .retain(|chunk| current_block <= chunk.locked_until);
+        PoolMembers::<T>::insert(pool_id, member, pool_member);
+        Self::try_reap_pool_member(member, pool_id);
+
+        Self::deposit_event(Event::<T>::PoolWithdrawn(pool_id, member.clone(), value));
+        Ok(())
+    }
+
+    /// Claims the dividends of the pool from all its validators into the pool reward account.
+    fn harvest_pool(pool_id: PoolId, pool: &NominationPool<T::AccountId, T::BlockNumber>) {
+        let pool_account = Self::pool_account(pool_id);
+        let free_before = Self::free_balance(&pool_account);
+        for validator in pool.validators.iter() {
+            // The pool may have nothing to claim from this validator.
+            let _ = <Self as Claim<T::AccountId>>::claim(&pool_account, validator);
+        }
+        let harvested = Self::free_balance(&pool_account).saturating_sub(free_before);
+        if !harvested.is_zero() {
+            let _ = Self::transfer(
+                &pool_account,
+                &Self::pool_reward_account(pool_id),
+                harvested,
+            );
+        }
+    }
+
+    /// Returns the dividend of `member` in the pool `pool_id` at `block_number`,
+    /// excluding the dividends not yet claimed by the pool.
+    pub fn compute_pool_dividend_at(
+        member: &T::AccountId,
+        pool_id: PoolId,
+        block_number: T::BlockNumber,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let reward_pot_balance = Self::free_balance(&Self::pool_reward_account(pool_id));
+        <PoolMining<T> as ComputeMiningWeight<T::AccountId, T::BlockNumber>>::compute_dividend(
+            member,
+            &pool_id,
+            block_number,
+            reward_pot_balance,
+        )
+        .map(|(dividend, _, _)| dividend)
+    }
+
+    pub(crate) fn apply_claim_from_pool(member: &T::AccountId, pool_id: PoolId) -> DispatchResult {
+        let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        Self::harvest_pool(pool_id, &pool);
+
+        let current_block = <frame_system::Module<T>>::block_number();
+        let reward_account = Self::pool_reward_account(pool_id);
+        let reward_pot_balance = Self::free_balance(&reward_account);
+
+        let (dividend, member_weight, pool_weight) =
+            <PoolMining<T> as ComputeMiningWeight<T::AccountId, T::BlockNumber>>::compute_dividend(
+                member,
+                &pool_id,
+                current_block,
+                reward_pot_balance,
+            )?;
+
+        Self::transfer(&reward_account, member, dividend)
+            .map_err(|_| Error::<T>::AllocateDividendFailed)?;
+
+        Self::set_pool_member_weight(member, pool_id, 0, current_block, Delta::Zero);
+        Self::set_pool_weight(
+            pool_id,
+            pool_weight - member_weight,
+            current_block,
+            Delta::Zero,
+        );
+        Self::try_reap_pool_member(member, pool_id);
+
+        Self::deposit_event(Event::<T>::PoolDividendClaimed(
+            pool_id,
+            member.clone(),
+            dividend,
+        ));
+        Ok(())
+    }
+}
@@ -7,27 +7,26 @@ use xp_mining_staking::SessionIndex;
 mod proposal09;
 
 impl<T: Trait> Module<T> {
-    /// Simple u32 power of 2 function - simply uses a bit shift
-    #[inline]
-    fn pow2(n: u32) -> BalanceOf<T> {
-        (1_u32 << n).saturated_into()
-    }
-
     /// Returns true if the time for first halving cycle has arrived.
     #[inline]
     fn first_halving_epoch_arrived(current_index: SessionIndex) -> bool {
         current_index > T::MigrationSessionOffset::get()
     }
 
+    /// Returns the emission schedule in use.
+    ///
+    /// Falls back to the Bitcoin-like halving of ChainX 1.0 if none has been set.
+    pub fn emission_schedule() -> EmissionSchedule<BalanceOf<T>> {
+        Self::emission_schedule_of().unwrap_or_else(|| EmissionSchedule::Halving {
+            initial_reward: INITIAL_REWARD.saturated_into(),
+            sessions_per_round: SESSIONS_PER_ROUND,
+            halving_offset: T::MigrationSessionOffset::get(),
+        })
+    }
+
     /// Returns the total reward for the session, assuming it ends with this block.
     pub(crate) fn this_session_reward(current_index: SessionIndex) -> BalanceOf<T> {
-        let halving_epoch = if Self::first_halving_epoch_arrived(current_index) {
-            (current_index - T::MigrationSessionOffset::get() - 1) / SESSIONS_PER_ROUND + 1
-        } else {
-            0
-        };
-
-        INITIAL_REWARD.saturated_into::<BalanceOf<T>>() / Self::pow2(halving_epoch)
+        Self::emission_schedule().session_reward(current_index, T::Currency::total_issuance())
     }
 
     /// Issue new fresh PCX.
@@ -41,16 +40,26 @@ impl<T: Trait> Module<T> {
     ///
     /// Add the reward to their balance, and their reward pot, pro-rata.
     fn apply_reward_validator(who: &T::AccountId, reward: BalanceOf<T>) {
-        // Validator themselves can only directly gain 10%, the rest 90% is for the reward pot.
-        let off_the_table = reward.saturated_into::<BalanceOf<T>>() / 10u32.saturated_into();
+        // Validator themselves can only directly gain the commission, which is capped
+        // by `MaximumCommission` in case the cap has been lowered after it was set.
+        let commission = Self::validators(who)
+            .commission
+            .min(Self::maximum_commission());
+        let off_the_table = commission.mul_floor(reward);
         Self::mint(who, off_the_table);
         debug!("💸 Mint validator({:?}):{:?}", who, off_the_table);
 
-        // Issue the rest 90% to validator's reward pot.
+        // Issue the rest to validator's reward pot.
         let to_reward_pot = reward - off_the_table;
         let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(who);
         Self::mint(&reward_pot, to_reward_pot);
         debug!("💸 Mint reward_pot({:?}):{:?}", reward_pot, to_reward_pot);
+
+        Self::note_validator_history(who, |record| {
+            record.session_rewards = record.session_rewards.saturating_add(reward);
+            record.reward_pot_inflow = record.reward_pot_inflow.saturating_add(to_reward_pot);
+            record.active_sessions = record.active_sessions.saturating_add(1);
+        });
     }
 
     /// Reward the intention and slash the validators that went offline in last session.
@@ -63,11 +72,16 @@ impl<T: Trait> Module<T> {
     }
 
     /// 20% reward of each session is for the vesting schedule in the first halving epoch.
+    ///
+    /// The vesting only belongs to the halving schedule inherited from ChainX 1.0, the
+    /// other schedules are not affected by `MigrationSessionOffset`.
     pub(crate) fn try_vesting(
         current_index: SessionIndex,
         this_session_reward: BalanceOf<T>,
     ) -> BalanceOf<T> {
-        if !Self::first_halving_epoch_arrived(current_index) {
+        if !Self::first_halving_epoch_arrived(current_index)
+            && Self::emission_schedule().is_halving()
+        {
             let to_vesting = this_session_reward / 5u32.saturated_into::<BalanceOf<T>>();
             let vesting_account = Self::vesting_account();
             Self::mint(&vesting_account, to_vesting);
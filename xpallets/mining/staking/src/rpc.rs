@@ -6,14 +6,18 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use frame_support::storage::{IterableStorageDoubleMap, StorageDoubleMap, StorageMap};
-use sp_runtime::RuntimeDebug;
+use frame_support::{
+    storage::{IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap},
+    traits::Currency,
+};
+use sp_runtime::{traits::Saturating, RuntimeDebug};
 
 use xp_mining_common::RewardPotAccountFor;
+use xp_mining_staking::SessionIndex;
 
 use crate::{
-    types::*, BalanceOf, LastRebondOf, Module, Nominations, SessionInterface, Trait,
-    ValidatorLedgers, Validators,
+    constants::MAX_EMISSION_PROJECTION_SESSIONS, types::*, BalanceOf, EraIndex, LastRebondOf,
+    Module, Nominations, SessionInterface, Trait, UnappliedSlashes, ValidatorLedgers, Validators,
 };
 
 /// Total information about a validator.
@@ -97,4 +101,30 @@ impl<T: Trait> Module<T> {
         let last_rebond = LastRebondOf::<T>::get(&who);
         NominatorInfo { last_rebond }
     }
+
+    /// Returns the total amount of PCX minted in each of the next `sessions` sessions
+    /// according to the current emission schedule.
+    ///
+    /// At most `MAX_EMISSION_PROJECTION_SESSIONS` sessions will be projected.
+    pub fn emission_projection(sessions: u32) -> Vec<(SessionIndex, BalanceOf<T>)> {
+        let schedule = Self::emission_schedule();
+        let first_session = T::SessionInterface::current_index() + 1;
+        let mut total_issuance = T::Currency::total_issuance();
+        (first_session..)
+            .take(sessions.min(MAX_EMISSION_PROJECTION_SESSIONS) as usize)
+            .map(|session_index| {
+                let reward = schedule.session_reward(session_index, total_issuance);
+                total_issuance = total_issuance.saturating_add(reward);
+                (session_index, reward)
+            })
+            .collect()
+    }
+    /// Returns all the unapplied slashes ordered by the era they will be applied in.
+    pub fn unapplied_slashes() -> Vec<(EraIndex, Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>)> {
+        let mut unapplied = UnappliedSlashes::<T>::iter()
+            .filter(|(_, slashes)| !slashes.is_empty())
+            .collect::<Vec<_>>();
+        unapplied.sort_by_key(|(era, _)| *era);
+        unapplied
+    }
 }
@@ -43,6 +43,8 @@ impl<T: Trait> Module<T> {
             }
         };
 
+        let slashing_mode = Self::slashing_mode();
+
         offenders
             .into_iter()
             .flat_map(|(offender, slash_fraction)| {
@@ -53,20 +55,20 @@ impl<T: Trait> Module<T> {
                     .map(|reward| reward + base_slash)
                     .unwrap_or(base_slash)
                     .max(minimum_penalty);
-                match slasher.try_slash(&offender, penalty) {
-                    SlashOutcome::Slashed(_) => {
+                let (pot_slashed, mut should_chill) = match slasher.try_slash(&offender, penalty) {
+                    SlashOutcome::Slashed(actual_slashed) => {
                         debug!(
                             "Slash the offender:{:?} for penalty {:?} by the given slash_fraction:{:?} successfully",
                             offender, penalty, slash_fraction
                         );
-                        None
+                        (actual_slashed, false)
                     }
                     SlashOutcome::InsufficientSlash(actual_slashed) => {
                         debug!(
                             "Insufficient reward pot balance of {:?}, actual slashed:{:?}",
                             offender, actual_slashed
                         );
-                        chill_offender_safe(offender)
+                        (actual_slashed, true)
                     }
                     SlashOutcome::SlashFailed(e) => {
                         debug!("Slash the offender {:?} for {:?} somehow failed: {:?}", offender, penalty, e);
@@ -75,10 +77,225 @@ impl<T: Trait> Module<T> {
                         //
                         // TODO: Reconsider this once https://github.com/paritytech/substrate/pull/7127
                         // is merged.
-                        chill_offender_safe(offender)
+                        (Zero::zero(), true)
+                    }
+                };
+                Self::note_validator_history(&offender, |record| {
+                    record.slashed = record.slashed.saturating_add(pot_slashed);
+                    record.offline_sessions = record.offline_sessions.saturating_add(1);
+                });
+
+                if slashing_mode == SlashingMode::NominatorProportional
+                    && !slash_fraction.is_zero()
+                    && Self::slash_nominations_of(&offender, slash_fraction)
+                {
+                    should_chill = true;
+                }
+
+                if !should_chill {
+                    return None;
+                }
+                let chilled = chill_offender_safe(offender);
+                if slashing_mode == SlashingMode::NominatorProportional {
+                    if let Some(ref chilled) = chilled {
+                        Self::start_new_slashing_span(chilled);
                     }
                 }
+                chilled
             })
             .collect()
     }
+
+    /// Records the slash of the nominations to `offender` by `slash_fraction`.
+    ///
+    /// Only the part exceeding the maximum slash fraction in the current slashing span
+    /// is slashed. Returns true if the nominations are going to be slashed.
+    fn slash_nominations_of(offender: &T::AccountId, slash_fraction: Perbill) -> bool {
+        let extra_fraction = SlashingSpans::<T>::mutate(offender, |span| {
+            if slash_fraction <= span.max_slash_fraction {
+                None
+            } else {
+                let extra_fraction = slash_fraction.saturating_sub(span.max_slash_fraction);
+                span.max_slash_fraction = slash_fraction;
+                Some(extra_fraction)
+            }
+        });
+        let extra_fraction = match extra_fraction {
+            Some(extra_fraction) => extra_fraction,
+            None => return false,
+        };
+
+        let own = extra_fraction.mul(Self::exposure_of(offender, offender));
+        let nominators = NominatorsOf::<T>::iter_prefix(offender)
+            .map(|(nominator, _)| nominator)
+            .filter(|nominator| nominator != offender)
+            .collect::<Vec<_>>();
+        // The session hook has no weight return, register the cost of going through
+        // the nominators as the mandatory extra weight.
+        let nominator_count = nominators.len() as Weight;
+        frame_system::Module::<T>::register_extra_weight_unchecked(
+            T::DbWeight::get().reads_writes(3 * nominator_count + 2, 2),
+            DispatchClass::Mandatory,
+        );
+        let others = nominators
+            .into_iter()
+            .map(|nominator| {
+                let value = extra_fraction.mul(Self::exposure_of(&nominator, offender));
+                (nominator, value)
+            })
+            .filter(|(_, value)| !value.is_zero())
+            .collect();
+        let active_era = Self::active_era().map(|info| info.index).unwrap_or(0);
+        let unapplied = UnappliedSlash {
+            validator: offender.clone(),
+            own,
+            others,
+            offence_era: active_era,
+        };
+
+        let defer_duration = Self::slash_defer_duration();
+        if defer_duration.is_zero() {
+            Self::apply_slash(unapplied);
+        } else {
+            let apply_era = active_era.saturating_add(defer_duration);
+            UnappliedSlashes::<T>::mutate(apply_era, |slashes| slashes.push(unapplied));
+            PendingSlashCount::<T>::mutate(offender, |count| *count += 1);
+            Self::deposit_event(Event::<T>::SlashDeferred(apply_era, offender.clone()));
+        }
+        true
+    }
+
+    /// Ends the current slashing span of `who` and starts a new one from the current era.
+    fn start_new_slashing_span(who: &T::AccountId) {
+        let start = Self::current_era().unwrap_or(0);
+        SlashingSpans::<T>::mutate(who, |span| {
+            *span = SlashingSpan {
+                index: span.index + 1,
+                start,
+                max_slash_fraction: Perbill::zero(),
+            };
+        });
+    }
+
+    /// Returns the balance of `nominator` at stake with `validator`.
+    ///
+    /// The unbonded balances of a nomination pool are owed to its members, so they
+    /// are never at stake.
+    fn exposure_of(nominator: &T::AccountId, validator: &T::AccountId) -> BalanceOf<T> {
+        let ledger = Nominations::<T>::get(nominator, validator);
+        if Self::pool_id_of(nominator).is_some() {
+            ledger.nomination
+        } else {
+            ledger
+                .unbonded_chunks
+                .iter()
+                .fold(ledger.nomination, |acc, chunk| acc + chunk.value)
+        }
+    }
+
+    /// Applies all the unapplied slashes of `era`.
+    pub(crate) fn apply_unapplied_slashes(era: EraIndex) {
+        for unapplied in UnappliedSlashes::<T>::take(era) {
+            Self::note_pending_slash_removed(&unapplied.validator);
+            Self::apply_slash(unapplied);
+        }
+    }
+
+    /// Notes one of the deferred slashes of `validator` has been applied or cancelled.
+    pub(crate) fn note_pending_slash_removed(validator: &T::AccountId) {
+        PendingSlashCount::<T>::mutate_exists(validator, |count| {
+            *count = count.and_then(|c| c.checked_sub(1)).filter(|c| *c > 0);
+        });
+    }
+
+    fn apply_slash(unapplied: UnappliedSlash<T::AccountId, BalanceOf<T>>) {
+        let UnappliedSlash {
+            validator,
+            own,
+            others,
+            offence_era,
+        } = unapplied;
+        // The slashes are applied in the session hook which has no weight return,
+        // register the cost of slashing each nomination as the mandatory extra weight.
+        let nomination_count = others.len() as Weight + 1;
+        frame_system::Module::<T>::register_extra_weight_unchecked(
+            T::DbWeight::get().reads_writes(8 * nomination_count, 6 * nomination_count),
+            DispatchClass::Mandatory,
+        );
+        let total_slashed = others.into_iter().fold(
+            Self::slash_nomination(&validator, &validator, own),
+            |acc, (nominator, value)| {
+                acc.saturating_add(Self::slash_nomination(&nominator, &validator, value))
+            },
+        );
+        debug!(
+            "Slashed the nominations to {:?} for {:?} in total",
+            validator, total_slashed
+        );
+        // The deferred slashes are recorded against the era of the offence.
+        Self::note_validator_history_at(offence_era, &validator, |record| {
+            record.slashed = record.slashed.saturating_add(total_slashed)
+        });
+        Self::deposit_event(Event::<T>::NominationsSlashed(validator, total_slashed));
+    }
+
+    /// Slashes at most `value` of the nomination from `nominator` to `validator`, the active
+    /// nomination goes first, then the unbonded balances.
+    ///
+    /// Returns the actual slashed balance, which is moved to the treasury.
+    fn slash_nomination(
+        nominator: &T::AccountId,
+        validator: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let value = value.min(Self::exposure_of(nominator, validator));
+        if value.is_zero() {
+            return Zero::zero();
+        }
+
+        let from_active = value.min(Self::bonded_to(nominator, validator));
+        if !from_active.is_zero() {
+            Self::update_vote_weight(nominator, validator, Delta::Sub(from_active));
+            Locks::<T>::mutate(nominator, |locks| {
+                let bonded = locks.entry(LockedType::Bonded).or_default();
+                *bonded = bonded.saturating_sub(from_active);
+            });
+        }
+
+        let from_unbonded = value - from_active;
+        if !from_unbonded.is_zero() {
+            Nominations::<T>::mutate(nominator, validator, |ledger| {
+                let mut remaining = from_unbonded;
+                for chunk in ledger.unbonded_chunks.iter_mut() {
+                    let slashed = remaining.min(chunk.value);
+                    chunk.value -= slashed;
+                    remaining -= slashed;
+                }
+                ledger
+                    .unbonded_chunks
+                    .retain(|chunk| !chunk.value.is_zero());
+            });
+            Locks::<T>::mutate(nominator, |locks| {
+                let withdrawal = locks.entry(LockedType::BondedWithdrawal).or_default();
+                *withdrawal = withdrawal.saturating_sub(from_unbonded);
+                if withdrawal.is_zero() {
+                    locks.remove(&LockedType::BondedWithdrawal);
+                }
+            });
+        }
+
+        Self::set_lock(nominator, Self::total_locked_of(nominator));
+        let (imbalance, _) = T::Currency::slash(nominator, value);
+        T::Currency::resolve_creating(&T::TreasuryAccount::treasury_account(), imbalance);
+
+        if let Some(pool_id) = Self::pool_id_of(nominator) {
+            let member_count = Self::slash_pool_points(pool_id, from_active) as Weight;
+            frame_system::Module::<T>::register_extra_weight_unchecked(
+                T::DbWeight::get().reads_writes(2 * member_count + 1, 2 * member_count + 1),
+                DispatchClass::Mandatory,
+            );
+        }
+
+        value
+    }
 }
@@ -1,8 +1,11 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use super::*;
+use crate::election::evaluate_score;
 use crate::mock::*;
+use codec::{Decode, Encode};
 use frame_support::{assert_err, assert_ok, traits::OnInitialize};
+use sp_runtime::testing::{TestSignature, UintAuthorityId};
 
 fn t_issue_pcx(to: AccountId, value: Balance) {
     XStaking::mint(&to, value);
@@ -884,5 +887,1255 @@ fn migration_session_offset_should_work() {
                 );
             }
         }
+
+        // only the halving schedule is vested in the first halving epoch
+        assert_ok!(XStaking::set_emission_schedule(
+            Origin::root(),
+            EmissionSchedule::LinearDecay {
+                initial_reward: 100,
+                decay_per_session: 10,
+                min_reward: 50,
+                start_session: 0,
+            }
+        ));
+        assert_eq!(
+            XStaking::try_vesting(MigrationSessionOffset::get(), 100),
+            100
+        );
+    });
+}
+
+#[test]
+fn set_emission_schedule_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(
+            XStaking::emission_schedule(),
+            EmissionSchedule::Halving {
+                initial_reward: INITIAL_REWARD as Balance,
+                sessions_per_round: SESSIONS_PER_ROUND,
+                halving_offset: MigrationSessionOffset::get(),
+            }
+        );
+
+        assert_err!(
+            XStaking::set_emission_schedule(
+                Origin::root(),
+                EmissionSchedule::Halving {
+                    initial_reward: 100,
+                    sessions_per_round: 0,
+                    halving_offset: 0,
+                }
+            ),
+            Error::<Test>::InvalidEmissionSchedule
+        );
+        assert_err!(
+            XStaking::set_emission_schedule(
+                Origin::root(),
+                EmissionSchedule::LinearDecay {
+                    initial_reward: 100,
+                    decay_per_session: 10,
+                    min_reward: 101,
+                    start_session: 0,
+                }
+            ),
+            Error::<Test>::InvalidEmissionSchedule
+        );
+        assert_err!(
+            XStaking::set_emission_schedule(
+                Origin::root(),
+                EmissionSchedule::LinearDecay {
+                    initial_reward: 100,
+                    decay_per_session: 0,
+                    min_reward: 75,
+                    start_session: 0,
+                }
+            ),
+            Error::<Test>::InvalidEmissionSchedule
+        );
+        assert_err!(
+            XStaking::set_emission_schedule(
+                Origin::root(),
+                EmissionSchedule::Halving {
+                    initial_reward: MAX_SESSION_REWARD as Balance + 1,
+                    sessions_per_round: 1,
+                    halving_offset: 0,
+                }
+            ),
+            Error::<Test>::InvalidEmissionSchedule
+        );
+        assert_err!(
+            XStaking::set_emission_schedule(
+                Origin::root(),
+                EmissionSchedule::FixedInflation {
+                    annual_inflation: Perbill::from_percent(11),
+                    sessions_per_year: MIN_SESSIONS_PER_YEAR,
+                }
+            ),
+            Error::<Test>::InvalidEmissionSchedule
+        );
+        assert_err!(
+            XStaking::set_emission_schedule(
+                Origin::root(),
+                EmissionSchedule::FixedInflation {
+                    annual_inflation: Perbill::from_percent(10),
+                    sessions_per_year: MIN_SESSIONS_PER_YEAR - 1,
+                }
+            ),
+            Error::<Test>::InvalidEmissionSchedule
+        );
+
+        assert_ok!(XStaking::set_emission_schedule(
+            Origin::root(),
+            EmissionSchedule::LinearDecay {
+                initial_reward: 100,
+                decay_per_session: 10,
+                min_reward: 75,
+                start_session: 10,
+            }
+        ));
+        assert_eq!(XStaking::this_session_reward(10), 100);
+        assert_eq!(XStaking::this_session_reward(11), 90);
+        assert_eq!(XStaking::this_session_reward(12), 80);
+        assert_eq!(XStaking::this_session_reward(13), 75);
+
+        assert_ok!(XStaking::set_emission_schedule(
+            Origin::root(),
+            EmissionSchedule::Halving {
+                initial_reward: 100,
+                sessions_per_round: 2,
+                halving_offset: 1,
+            }
+        ));
+        assert_eq!(XStaking::this_session_reward(1), 100);
+        assert_eq!(XStaking::this_session_reward(2), 50);
+        assert_eq!(XStaking::this_session_reward(3), 50);
+        assert_eq!(XStaking::this_session_reward(4), 25);
+        assert_eq!(XStaking::this_session_reward(1000), 0);
+    });
+}
+
+#[test]
+fn emission_projection_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_start_session(1);
+
+        assert_ok!(XStaking::set_emission_schedule(
+            Origin::root(),
+            EmissionSchedule::Halving {
+                initial_reward: 100,
+                sessions_per_round: 2,
+                halving_offset: 2,
+            }
+        ));
+        assert_eq!(
+            XStaking::emission_projection(4),
+            vec![(2, 100), (3, 50), (4, 50), (5, 25)]
+        );
+
+        let total_issuance = Balances::total_issuance();
+        assert_ok!(XStaking::set_emission_schedule(
+            Origin::root(),
+            EmissionSchedule::FixedInflation {
+                annual_inflation: Perbill::from_percent(10),
+                sessions_per_year: 10_000,
+            }
+        ));
+        let first = total_issuance / 100_000;
+        let second = (total_issuance + first) / 100_000;
+        assert_eq!(
+            XStaking::emission_projection(2),
+            vec![(2, first), (3, second)]
+        );
+        assert_eq!(XStaking::this_session_reward(2), first);
+
+        // The newly minted reward follows the new schedule.
+        t_start_session(2);
+        assert_eq!(Balances::total_issuance(), total_issuance + first);
+
+        assert_eq!(
+            XStaking::emission_projection(u32::max_value()).len() as u32,
+            MAX_EMISSION_PROJECTION_SESSIONS
+        );
+    });
+}
+
+#[test]
+fn set_commission_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_eq!(XStaking::validators(1).commission, DEFAULT_COMMISSION);
+
+        assert_err!(
+            XStaking::set_commission(Origin::signed(5), Perbill::from_percent(20)),
+            Error::<Test>::NotValidator
+        );
+
+        assert_ok!(XStaking::set_maximum_commission(
+            Origin::root(),
+            Perbill::from_percent(30)
+        ));
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(31)),
+            Error::<Test>::CommissionTooHigh
+        );
+
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(1),
+            Perbill::from_percent(20)
+        ));
+        let profile = XStaking::validators(1);
+        assert_eq!(profile.commission, Perbill::from_percent(20));
+        assert_eq!(profile.last_commission_change, Some(1));
+
+        assert_ok!(XStaking::set_commission_change_interval(Origin::root(), 10));
+        t_system_block_number_inc(9);
+        assert_err!(
+            XStaking::set_commission(Origin::signed(1), Perbill::from_percent(5)),
+            Error::<Test>::NoMoreCommissionChange
+        );
+        t_system_block_number_inc(1);
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(1),
+            Perbill::from_percent(5)
+        ));
+        assert_eq!(XStaking::validators(1).commission, Perbill::from_percent(5));
+    });
+}
+
+#[test]
+fn commission_should_work_on_reward() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(1),
+            Perbill::from_percent(50)
+        ));
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(2),
+            Perbill::from_percent(40)
+        ));
+        // The commission exceeding the maximum is capped.
+        assert_ok!(XStaking::set_maximum_commission(
+            Origin::root(),
+            Perbill::from_percent(40)
+        ));
+
+        t_start_session(1);
+
+        // See `staking_reward_should_work` for the details of reward distribution.
+        let staking_mining_reward = 3_168_000_000u128;
+        let total_staked = 100;
+        let test_validator_reward =
+            |validator: AccountId, initial_free: Balance, staked: Balance, commission: u128| {
+                let val_total_reward = staking_mining_reward * staked / total_staked;
+                let to_validator = val_total_reward * commission / 100;
+                assert_eq!(
+                    Balances::free_balance(&validator),
+                    initial_free + to_validator
+                );
+                assert_eq!(
+                    t_reward_pot_balance(validator),
+                    val_total_reward - to_validator
+                );
+            };
+
+        test_validator_reward(1, 100, 10, 40);
+        test_validator_reward(2, 200, 20, 40);
+        test_validator_reward(3, 300, 30, 10);
+        test_validator_reward(4, 400, 40, 10);
+    });
+}
+
+#[test]
+fn migrate_validator_profile_should_work() {
+    use frame_support::{storage::unhashed, traits::OnRuntimeUpgrade, StorageMap, StorageValue};
+
+    ExtBuilder::default().build_and_execute(|| {
+        // (registered_at, is_chilled, last_chilled, referral_id)
+        let old_profile = (10u64, true, Some(5u64), b"old".to_vec());
+        unhashed::put_raw(
+            &Validators::<Test>::hashed_key_for(&10),
+            &old_profile.encode(),
+        );
+        StorageVersion::put(Releases::V1_0_0);
+
+        XStaking::on_runtime_upgrade();
+
+        assert_eq!(
+            XStaking::validators(10),
+            ValidatorProfile {
+                registered_at: 10,
+                is_chilled: true,
+                last_chilled: Some(5),
+                referral_id: b"old".to_vec(),
+                commission: DEFAULT_COMMISSION,
+                last_commission_change: None,
+            }
+        );
+        assert_eq!(StorageVersion::get(), Releases::V3_0_0);
+    });
+}
+
+#[test]
+fn migrate_to_v3_should_index_nominators_and_pending_slashes() {
+    use frame_support::{traits::OnRuntimeUpgrade, StorageDoubleMap, StorageValue};
+
+    ExtBuilder::default().build_and_execute(|| {
+        t_issue_pcx(1111, 100);
+        assert_ok!(t_bond(1111, 2, 10));
+        NominatorsOf::<Test>::remove_prefix(2);
+        UnappliedSlashes::<Test>::insert(
+            5,
+            vec![UnappliedSlash {
+                validator: 2,
+                own: 1,
+                others: vec![],
+                offence_era: 0,
+            }],
+        );
+        StorageVersion::put(Releases::V2_0_0);
+
+        XStaking::on_runtime_upgrade();
+
+        assert!(XStaking::nominators_of(2, 1111));
+        assert!(XStaking::nominators_of(2, 2));
+        assert_eq!(XStaking::pending_slash_count(2), 1);
+        assert_eq!(StorageVersion::get(), Releases::V3_0_0);
+    });
+}
+
+#[test]
+fn auto_compound_should_work() {
+    use frame_support::StorageValue;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_err!(
+            XStaking::set_auto_compound(Origin::signed(t_1), 1, true),
+            Error::<Test>::NotNominated
+        );
+        assert_ok!(t_bond(t_1, 1, 10));
+
+        assert_err!(
+            XStaking::set_auto_compound(Origin::signed(t_1), 5, true),
+            Error::<Test>::NotValidator
+        );
+        assert_ok!(XStaking::set_auto_compound(Origin::signed(t_1), 1, true));
+        assert!(XStaking::auto_compound(t_1, 1));
+
+        let claim_and_get_dividend = || {
+            let free_before = Balances::free_balance(t_1);
+            assert_ok!(XStaking::claim(Origin::signed(t_1), 1));
+            Balances::free_balance(t_1) - free_before
+        };
+
+        // The dividend exceeds the acceptable votes of validator 1, no compounding.
+        t_start_session(1);
+        let dividend = claim_and_get_dividend();
+        assert!(dividend > 0);
+        assert_eq!(XStaking::bonded_to(&t_1, &1), 10);
+
+        UpperBoundFactorOfAcceptableVotes::put(u32::max_value());
+
+        t_start_session(2);
+        let dividend = claim_and_get_dividend();
+        assert_eq!(XStaking::bonded_to(&t_1, &1), 10 + dividend);
+        assert_bonded_locks(t_1, 10 + dividend);
+        let compounded_event = MetaEvent::staking(RawEvent::Compounded(t_1, 1, dividend));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == compounded_event));
+
+        // The auto-compounding nominations are claimed at the beginning of new era.
+        let bonded = XStaking::bonded_to(&t_1, &1);
+        t_start_session(6);
+        assert!(XStaking::auto_compound_pending());
+        XStaking::on_initialize(System::block_number());
+        assert!(!XStaking::auto_compound_pending());
+        assert_eq!(XStaking::auto_compound_cursor(), None);
+        assert!(XStaking::bonded_to(&t_1, &1) > bonded);
+
+        assert_ok!(XStaking::set_auto_compound(Origin::signed(t_1), 1, false));
+        assert!(!XStaking::auto_compound(t_1, 1));
+
+        // The auto-compounding is disabled once the whole nomination is unbonded.
+        assert_ok!(XStaking::set_auto_compound(Origin::signed(t_1), 1, true));
+        let bonded = XStaking::bonded_to(&t_1, &1);
+        assert_ok!(XStaking::unbond(Origin::signed(t_1), 1, bonded));
+        assert!(!XStaking::auto_compound(t_1, 1));
+    });
+}
+
+#[test]
+fn auto_compound_should_resume_across_blocks() {
+    use frame_support::StorageValue;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        for &who in &[t_1, t_2] {
+            t_issue_pcx(who, 100);
+            assert_ok!(t_bond(who, 1, 10));
+            assert_ok!(XStaking::set_auto_compound(Origin::signed(who), 1, true));
+        }
+        UpperBoundFactorOfAcceptableVotes::put(u32::max_value());
+        MaximumAutoCompoundPerBlock::put(1);
+
+        t_start_session(3);
+        assert!(XStaking::auto_compound_pending());
+        XStaking::on_initialize(System::block_number());
+        assert!(!XStaking::auto_compound_pending());
+        assert!(XStaking::auto_compound_cursor().is_some());
+        let compounded = |who| XStaking::bonded_to(&who, &1) > 10;
+        assert!(compounded(t_1) ^ compounded(t_2));
+
+        // The ongoing round is kept when a new era begins.
+        t_start_session(6);
+        assert!(XStaking::auto_compound_pending());
+        assert!(XStaking::auto_compound_cursor().is_some());
+        XStaking::on_initialize(System::block_number());
+        assert!(compounded(t_1) && compounded(t_2));
+        assert_eq!(XStaking::auto_compound_cursor(), None);
+
+        // Then the new round starts over.
+        XStaking::on_initialize(System::block_number());
+        assert!(!XStaking::auto_compound_pending());
+        assert!(XStaking::auto_compound_cursor().is_some());
+        XStaking::on_initialize(System::block_number());
+        assert_eq!(XStaking::auto_compound_cursor(), None);
+
+        // Nothing to do without a pending round.
+        XStaking::on_initialize(System::block_number());
+        assert!(!XStaking::auto_compound_pending());
+        assert_eq!(XStaking::auto_compound_cursor(), None);
+    });
+}
+
+#[test]
+fn nomination_pool_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        t_issue_pcx(t_1, 100);
+        t_issue_pcx(t_2, 100);
+
+        assert_err!(
+            XStaking::create_pool(Origin::signed(t_1), vec![]),
+            Error::<Test>::InvalidPoolValidators
+        );
+        assert_err!(
+            XStaking::create_pool(Origin::signed(t_1), vec![1, 1]),
+            Error::<Test>::InvalidPoolValidators
+        );
+        assert_err!(
+            XStaking::create_pool(Origin::signed(t_1), vec![1, 5]),
+            Error::<Test>::NotValidator
+        );
+        assert_ok!(XStaking::create_pool(Origin::signed(t_1), vec![1, 2]));
+        let pool_id = 0;
+        let pool_account = XStaking::pool_account(pool_id);
+        assert_eq!(XStaking::pools(pool_id).unwrap().validators, vec![1, 2]);
+
+        assert_err!(
+            XStaking::bond_to_pool(Origin::signed(t_1), 1, 10),
+            Error::<Test>::PoolNotFound
+        );
+
+        // The pool bonds to the validator with the least pool votes.
+        assert_ok!(XStaking::bond_to_pool(Origin::signed(t_1), pool_id, 30));
+        assert_ok!(XStaking::bond_to_pool(Origin::signed(t_2), pool_id, 20));
+        assert_eq!(XStaking::bonded_to(&pool_account, &1), 30);
+        assert_eq!(XStaking::bonded_to(&pool_account, &2), 20);
+        assert_eq!(XStaking::pool_members(pool_id, t_1).points, 30);
+        assert_eq!(XStaking::pool_members(pool_id, t_2).points, 20);
+        assert_eq!(XStaking::pool_ledgers(pool_id).total_points, 50);
+        assert_eq!(Balances::free_balance(t_1), 70);
+        assert_bonded_locks(pool_account, 50);
+
+        t_start_session(1);
+        t_start_session(2);
+
+        let claim_from_pool = |who: AccountId| {
+            let free_before = Balances::free_balance(who);
+            assert_ok!(XStaking::claim_from_pool(Origin::signed(who), pool_id));
+            Balances::free_balance(who) - free_before
+        };
+
+        // The dividends are split by the vote weight of members, i.e., 30:20.
+        let dividend_1 = claim_from_pool(t_1);
+        let dividend_2 = claim_from_pool(t_2);
+        assert!(dividend_1 > 0);
+        assert_eq!(dividend_1, (dividend_1 + dividend_2) * 3 / 5);
+        assert_eq!(
+            Balances::free_balance(XStaking::pool_reward_account(pool_id)),
+            0
+        );
+        let claimed_event =
+            MetaEvent::staking(RawEvent::PoolDividendClaimed(pool_id, t_1, dividend_1));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == claimed_event));
+
+        assert_ok!(XStaking::set_bonding_duration(Origin::root(), 0));
+        assert_err!(
+            XStaking::unbond_from_pool(Origin::signed(t_1), pool_id, 31),
+            Error::<Test>::InvalidUnbondBalance
+        );
+        assert_ok!(XStaking::unbond_from_pool(Origin::signed(t_1), pool_id, 30));
+        assert_eq!(XStaking::bonded_to(&pool_account, &1), 0);
+        assert_eq!(XStaking::bonded_to(&pool_account, &2), 20);
+        assert_eq!(XStaking::pool_members(pool_id, t_1).points, 0);
+        assert_eq!(XStaking::pool_ledgers(pool_id).total_points, 20);
+        assert_bonded_locks(pool_account, 20);
+        assert_bonded_withdrawal_locks(pool_account, 30);
+
+        assert_err!(
+            XStaking::withdraw_from_pool(Origin::signed(t_1), pool_id),
+            Error::<Test>::UnbondedWithdrawalNotYetDue
+        );
+        t_system_block_number_inc(1);
+        assert_ok!(XStaking::withdraw_from_pool(Origin::signed(t_1), pool_id));
+        assert_eq!(Balances::free_balance(t_1), 100 + dividend_1);
+        assert!(XStaking::pool_members(pool_id, t_1)
+            .unbonded_chunks
+            .is_empty());
+        assert_eq!(Balances::free_balance(pool_account), 20);
+        assert_eq!(
+            XStaking::locks(pool_account).get(&LockedType::BondedWithdrawal),
+            None
+        );
+    });
+}
+
+#[test]
+fn pool_members_should_be_capped() {
+    use frame_support::{StorageDoubleMap, StorageValue};
+
+    ExtBuilder::default().build_and_execute(|| {
+        UpperBoundFactorOfAcceptableVotes::put(u32::max_value());
+        assert_ok!(XStaking::create_pool(Origin::signed(1), vec![1]));
+        let pool_id = 0;
+
+        let members = (0..MAXIMUM_POOL_MEMBERS as AccountId)
+            .map(|i| 20_000 + i)
+            .collect::<Vec<_>>();
+        for &member in &members {
+            t_issue_pcx(member, 10);
+            assert_ok!(XStaking::bond_to_pool(Origin::signed(member), pool_id, 10));
+        }
+        assert_eq!(XStaking::pool_member_count(pool_id), MAXIMUM_POOL_MEMBERS);
+
+        let newcomer = 9999;
+        t_issue_pcx(newcomer, 10);
+        assert_err!(
+            XStaking::bond_to_pool(Origin::signed(newcomer), pool_id, 10),
+            Error::<Test>::TooManyPoolMembers
+        );
+        // The existing members can still bond more.
+        t_issue_pcx(members[1], 10);
+        assert_ok!(XStaking::bond_to_pool(
+            Origin::signed(members[1]),
+            pool_id,
+            10
+        ));
+
+        // The member leaving the pool makes room for the newcomer.
+        assert_ok!(XStaking::set_bonding_duration(Origin::root(), 0));
+        assert_ok!(XStaking::unbond_from_pool(
+            Origin::signed(members[0]),
+            pool_id,
+            10
+        ));
+        t_system_block_number_inc(1);
+        assert_ok!(XStaking::withdraw_from_pool(
+            Origin::signed(members[0]),
+            pool_id
+        ));
+        assert!(!PoolMembers::<Test>::contains_key(pool_id, members[0]));
+        assert_eq!(
+            XStaking::pool_member_count(pool_id),
+            MAXIMUM_POOL_MEMBERS - 1
+        );
+        assert_ok!(XStaking::bond_to_pool(
+            Origin::signed(newcomer),
+            pool_id,
+            10
+        ));
+    });
+}
+
+#[test]
+fn seq_phragmen_should_work() {
+    use crate::election::phragmen::seq_phragmen;
+
+    let voters = vec![
+        (10, 10, vec![1, 2]),
+        (20, 20, vec![1, 3]),
+        (30, 30, vec![2, 3]),
+    ];
+    let result = seq_phragmen(2, vec![1u64, 2, 3], voters);
+    assert_eq!(result.winners, vec![3, 2]);
+    assert_eq!(
+        result.assignments,
+        vec![
+            (10, vec![(2, 10)]),
+            (20, vec![(3, 20)]),
+            (30, vec![(2, 15), (3, 15)]),
+        ]
+    );
+    assert_eq!(
+        evaluate_score(&result.winners, &result.assignments),
+        [25, 60, 25 * 25 + 35 * 35]
+    );
+}
+
+fn t_prepare_phragmen_election() {
+    // Sorted by the total nominations: 4(340), 3(180), 2(178), 1(10).
+    t_issue_pcx(5, 300);
+    t_issue_pcx(6, 158);
+    assert_ok!(t_bond(5, 3, 150));
+    assert_ok!(t_bond(5, 4, 150));
+    assert_ok!(t_bond(6, 2, 158));
+    assert_ok!(XStaking::set_validator_count(Origin::root(), 2));
+    assert_ok!(XStaking::set_election_strategy(
+        Origin::root(),
+        ElectionStrategy::SequentialPhragmen
+    ));
+}
+
+#[test]
+fn phragmen_election_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_prepare_phragmen_election();
+
+        // Validator 3 shares the backing of nominator 5 with validator 4.
+        t_start_session(1);
+        let (winners, assignments) = XStaking::compute_election_solution();
+        let score = evaluate_score(&winners, &assignments);
+        assert_ok!(XStaking::submit_election_solution(
+            Origin::none(),
+            1,
+            winners,
+            assignments,
+            score,
+            0,
+            TestSignature(1, vec![])
+        ));
+        t_start_session(3);
+        assert_eq!(Session::validators(), vec![4, 2]);
+
+        // Fall back to sorting by the total nominations without any solution.
+        t_start_session(6);
+        assert_eq!(Session::validators(), vec![4, 3]);
+    });
+}
+
+fn t_signed_election_solution(
+    winners: Vec<AccountId>,
+    assignments: Vec<StakeAssignment<AccountId, Balance>>,
+    score: ElectionScore,
+    validator_index: u32,
+    key: u64,
+) -> Call<Test> {
+    let payload = (1u32, &winners, &assignments, &score, validator_index).encode();
+    let signature = UintAuthorityId(key).sign(&payload).unwrap();
+    Call::submit_election_solution(1, winners, assignments, score, validator_index, signature)
+}
+
+#[test]
+fn submit_election_solution_should_work() {
+    use frame_support::unsigned::ValidateUnsigned;
+
+    ExtBuilder::default().build_and_execute(|| {
+        t_prepare_phragmen_election();
+
+        let submit = |winners: Vec<AccountId>, assignments, score| {
+            XStaking::submit_election_solution(
+                Origin::none(),
+                1,
+                winners,
+                assignments,
+                score,
+                0,
+                TestSignature(1, vec![]),
+            )
+        };
+
+        let (winners, assignments) = XStaking::compute_election_solution();
+        let score = evaluate_score(&winners, &assignments);
+        assert_eq!(winners, vec![4, 2]);
+        assert_eq!(score, [178, 518, 340 * 340 + 178 * 178]);
+
+        // The election window is not open yet.
+        assert_err!(
+            submit(winners.clone(), assignments.clone(), score),
+            Error::<Test>::ElectionSolutionNotAcceptable
+        );
+
+        t_start_session(1);
+        assert!(XStaking::is_current_session_final());
+
+        // Fake score.
+        assert_err!(
+            submit(winners.clone(), assignments.clone(), [200, 518, 0]),
+            Error::<Test>::InvalidElectionSolution
+        );
+        // Too few winners.
+        assert_err!(
+            submit(vec![4], vec![(5, vec![(4, 300)])], [340, 340, 340 * 340]),
+            Error::<Test>::InvalidElectionSolution
+        );
+        // Nominator 6 has never nominated validator 4.
+        assert_err!(
+            submit(
+                vec![4, 2],
+                vec![(6, vec![(4, 158), (2, 0)])],
+                [0, 158, 158 * 158]
+            ),
+            Error::<Test>::InvalidElectionSolution
+        );
+        // Nominator 5 can not assign more than its nominations.
+        assert_err!(
+            submit(
+                vec![4, 3],
+                vec![(5, vec![(4, 200), (3, 200)])],
+                [200, 400, 80000]
+            ),
+            Error::<Test>::InvalidElectionSolution
+        );
+        // A weak solution is queued until a better one is submitted.
+        assert_ok!(submit(
+            vec![4, 3],
+            vec![(5, vec![(4, 150), (3, 150)])],
+            [150, 300, 2 * 150 * 150]
+        ));
+
+        // Only the current validators can submit the solution signed by their session keys.
+        let validators = Session::validators();
+        assert_eq!(validators, vec![1, 2, 3, 4]);
+        let call = t_signed_election_solution(winners.clone(), assignments.clone(), score, 3, 5);
+        assert_eq!(
+            XStaking::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::BadProof.into()
+        );
+        let call = t_signed_election_solution(winners.clone(), assignments.clone(), score, 4, 5);
+        assert_eq!(
+            XStaking::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::BadProof.into()
+        );
+
+        // Better than the queued solution.
+        let call = t_signed_election_solution(winners.clone(), assignments.clone(), score, 3, 4);
+        assert!(XStaking::validate_unsigned(TransactionSource::External, &call).is_ok());
+        assert_ok!(submit(winners.clone(), assignments.clone(), score));
+        let queued_event = MetaEvent::staking(RawEvent::ElectionSolutionQueued(1, score));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == queued_event));
+        assert_eq!(
+            XStaking::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::Stale.into()
+        );
+        assert_err!(
+            submit(winners, assignments, score),
+            Error::<Test>::WeakElectionSolution
+        );
+
+        // A balanced solution has a greater minimal support.
+        let balanced_assignments = vec![
+            (3, vec![(3, 30)]),
+            (4, vec![(4, 40)]),
+            (5, vec![(4, 150), (3, 150)]),
+        ];
+        let balanced_score = [180, 370, 190 * 190 + 180 * 180];
+        assert_ok!(XStaking::set_minimum_election_score(
+            Origin::root(),
+            [190, 0, 0]
+        ));
+        assert_err!(
+            submit(vec![4, 3], balanced_assignments.clone(), balanced_score),
+            Error::<Test>::ElectionScoreTooLow
+        );
+        assert_ok!(XStaking::set_minimum_election_score(
+            Origin::root(),
+            [0, 0, 0]
+        ));
+        assert_ok!(submit(vec![4, 3], balanced_assignments, balanced_score));
+        assert_eq!(
+            XStaking::queued_election_outcome().unwrap().winners,
+            vec![4, 3]
+        );
+
+        t_start_session(3);
+        assert_eq!(Session::validators(), vec![4, 3]);
+        assert!(XStaking::queued_election_outcome().is_none());
+        assert!(!XStaking::is_current_session_final());
+    });
+}
+
+#[test]
+fn zero_score_election_solution_should_be_rejected() {
+    use frame_support::unsigned::ValidateUnsigned;
+
+    ExtBuilder::default().build_and_execute(|| {
+        t_prepare_phragmen_election();
+        t_start_session(1);
+        assert!(XStaking::queued_election_outcome().is_none());
+
+        // Valid winners without any assignment.
+        let call = t_signed_election_solution(vec![4, 2], vec![], [0, 0, 0], 3, 4);
+        assert_eq!(
+            XStaking::validate_unsigned(TransactionSource::External, &call),
+            InvalidTransaction::Stale.into()
+        );
+        assert_err!(
+            XStaking::submit_election_solution(
+                Origin::none(),
+                1,
+                vec![4, 2],
+                vec![],
+                [0, 0, 0],
+                3,
+                TestSignature(4, vec![])
+            ),
+            Error::<Test>::ElectionScoreTooLow
+        );
+        assert!(XStaking::queued_election_outcome().is_none());
+    });
+}
+
+#[test]
+fn offchain_election_should_work() {
+    use frame_support::{traits::OffchainWorker, unsigned::ValidateUnsigned};
+    use sp_core::offchain::{
+        testing::{TestOffchainExt, TestTransactionPoolExt},
+        OffchainExt, TransactionPoolExt,
+    };
+
+    let mut ext = ExtBuilder::default().build();
+    let (offchain, _state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    ext.register_extension(OffchainExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        t_prepare_phragmen_election();
+
+        // No solution out of the election window.
+        XStaking::offchain_worker(System::block_number());
+        assert!(pool_state.read().transactions.is_empty());
+
+        t_start_session(1);
+        // No solution without the session key of a current validator.
+        UintAuthorityId::set_all_keys(vec![5u64]);
+        XStaking::offchain_worker(System::block_number());
+        assert!(pool_state.read().transactions.is_empty());
+
+        UintAuthorityId::set_all_keys(vec![4u64]);
+        XStaking::offchain_worker(System::block_number());
+        // Only one solution per era.
+        XStaking::offchain_worker(System::block_number());
+        assert_eq!(pool_state.read().transactions.len(), 1);
+
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature, None);
+        let (winners, assignments) = XStaking::compute_election_solution();
+        let score = evaluate_score(&winners, &assignments);
+        assert_eq!(
+            tx.call,
+            t_signed_election_solution(winners, assignments, score, 3, 4)
+        );
+        assert!(XStaking::validate_unsigned(TransactionSource::External, &tx.call).is_ok());
+    });
+}
+
+#[test]
+fn unlock_all_due_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 123;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(t_bond(t_1, 2, 10));
+        assert_ok!(t_bond(t_1, 3, 10));
+
+        assert_ok!(t_unbond(t_1, 1, 5));
+        assert_ok!(t_unbond(t_1, 2, 3));
+        assert_ok!(t_unbond(t_1, 2, 2));
+        t_system_block_number_inc(DEFAULT_BONDING_DURATION);
+        assert_ok!(t_unbond(t_1, 3, 5));
+        t_system_block_number_inc(1);
+
+        assert_err!(
+            XStaking::unlock_all_due(Origin::signed(t_1), 2),
+            Error::<Test>::InvalidNominationsWitness
+        );
+        assert_ok!(XStaking::unlock_all_due(Origin::signed(t_1), 3));
+        let withdrawn_event = MetaEvent::staking(RawEvent::AllDueWithdrawn(t_1, 10));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == withdrawn_event));
+
+        assert!(XStaking::nominations(t_1, 1).unbonded_chunks.is_empty());
+        assert!(XStaking::nominations(t_1, 2).unbonded_chunks.is_empty());
+        assert_eq!(XStaking::nominations(t_1, 3).unbonded_chunks.len(), 1);
+        assert_bonded_locks(t_1, 15);
+        assert_bonded_withdrawal_locks(t_1, 5);
+
+        // Nothing is due now.
+        assert_err!(
+            XStaking::unlock_all_due(Origin::signed(t_1), 3),
+            Error::<Test>::UnbondedWithdrawalNotYetDue
+        );
+    });
+}
+
+#[test]
+fn rebond_unbonded_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 123;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 1, 50));
+
+        assert_ok!(t_unbond(t_1, 1, 10));
+        t_system_block_number_inc(1);
+        assert_ok!(t_unbond(t_1, 1, 20));
+        assert_bonded_locks(t_1, 20);
+        assert_bonded_withdrawal_locks(t_1, 30);
+
+        assert_err!(
+            XStaking::rebond_unbonded(Origin::signed(t_1), 1, 31),
+            Error::<Test>::InvalidRebondUnbondedBalance
+        );
+        assert_err!(
+            XStaking::rebond_unbonded(Origin::signed(t_1), 2, 10),
+            Error::<Test>::InvalidRebondUnbondedBalance
+        );
+
+        // The newest chunk is consumed first.
+        assert_ok!(XStaking::rebond_unbonded(Origin::signed(t_1), 1, 25));
+        let chunks = XStaking::nominations(t_1, 1).unbonded_chunks;
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].value, 5);
+        assert_eq!(chunks[0].locked_until, 1 + DEFAULT_BONDING_DURATION);
+        assert_eq!(XStaking::bonded_to(&t_1, &1), 45);
+        assert_eq!(XStaking::validator_ledgers(1).total_nomination, 55);
+        assert_bonded_locks(t_1, 45);
+        assert_bonded_withdrawal_locks(t_1, 5);
+
+        assert_ok!(XStaking::rebond_unbonded(Origin::signed(t_1), 1, 5));
+        assert!(XStaking::nominations(t_1, 1).unbonded_chunks.is_empty());
+        assert_eq!(
+            XStaking::locks(t_1).get(&LockedType::BondedWithdrawal),
+            None
+        );
+        assert_bonded_locks(t_1, 50);
+        assert_eq!(Balances::usable_balance(t_1), 50);
+    });
+}
+
+#[test]
+fn nominator_proportional_slash_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        let t_3 = 3333;
+        t_issue_pcx(t_1, 100);
+        t_issue_pcx(t_2, 100);
+        t_issue_pcx(t_3, 100);
+        assert_ok!(t_bond(t_1, 4, 60));
+        assert_ok!(t_unbond(t_1, 4, 10));
+
+        assert_ok!(XStaking::create_pool(Origin::signed(t_2), vec![4]));
+        let pool_id = 0;
+        let pool_account = XStaking::pool_account(pool_id);
+        assert_eq!(XStaking::pool_id_of(pool_account), Some(pool_id));
+        assert_ok!(XStaking::bond_to_pool(Origin::signed(t_2), pool_id, 40));
+        assert_ok!(XStaking::bond_to_pool(Origin::signed(t_3), pool_id, 20));
+
+        assert_ok!(XStaking::set_slashing_mode(
+            Origin::root(),
+            SlashingMode::NominatorProportional
+        ));
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 1));
+        // Keep the offender from being chilled so that the slashing span goes on.
+        assert_ok!(XStaking::set_minimum_validator_count(Origin::root(), 4));
+
+        let slash = |fraction: u32| {
+            let offenders = vec![(4, Perbill::from_percent(fraction))]
+                .into_iter()
+                .collect::<BTreeMap<_, _>>();
+            assert!(XStaking::slash_offenders_in_session(offenders, vec![]).is_empty());
+        };
+        let apply_era = XStaking::active_era().unwrap().index + 1;
+        let sorted_others = |index: usize| {
+            let mut others = XStaking::unapplied_slashes_of(apply_era)[index]
+                .others
+                .clone();
+            others.sort();
+            others
+        };
+
+        // The unbonded balances of nominator are at stake too, but not the ones of pool.
+        slash(10);
+        assert_eq!(XStaking::unapplied_slashes_of(apply_era)[0].own, 4);
+        assert_eq!(sorted_others(0), vec![(pool_account, 6), (t_1, 6)]);
+        let deferred_event = MetaEvent::staking(RawEvent::SlashDeferred(apply_era, 4));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == deferred_event));
+        assert!(XStaking::nominators_of(4, t_1));
+        assert!(XStaking::nominators_of(4, pool_account));
+        assert_eq!(XStaking::pending_slash_count(4), 1);
+
+        // The nominations at stake can not be moved away before the slash is applied.
+        assert_err!(t_rebond(t_1, 4, 2, 10), Error::<Test>::PendingSlash);
+        assert_err!(t_unbond(t_1, 4, 10), Error::<Test>::PendingSlash);
+        assert_err!(
+            XStaking::unbond_from_pool(Origin::signed(t_2), pool_id, 10),
+            Error::<Test>::PendingSlash
+        );
+
+        // Only the exceeding fraction in the same span is slashed.
+        slash(10);
+        assert_eq!(XStaking::unapplied_slashes_of(apply_era).len(), 1);
+        slash(15);
+        assert_eq!(XStaking::unapplied_slashes_of(apply_era)[1].own, 2);
+        assert_eq!(XStaking::pending_slash_count(4), 2);
+        assert_eq!(sorted_others(1), vec![(pool_account, 3), (t_1, 3)]);
+        assert_eq!(
+            XStaking::slashing_spans(4).max_slash_fraction,
+            Perbill::from_percent(15)
+        );
+        assert_eq!(XStaking::unapplied_slashes().len(), 1);
+
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), apply_era, vec![1, 1]),
+            Error::<Test>::InvalidSlashIndex
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), apply_era, vec![2]),
+            Error::<Test>::InvalidSlashIndex
+        );
+        assert_ok!(XStaking::cancel_deferred_slash(
+            Origin::root(),
+            apply_era,
+            vec![1]
+        ));
+        assert_eq!(XStaking::unapplied_slashes_of(apply_era).len(), 1);
+        assert_eq!(XStaking::pending_slash_count(4), 1);
+
+        t_start_session(3);
+        assert_eq!(XStaking::active_era().unwrap().index, apply_era);
+        assert!(XStaking::unapplied_slashes_of(apply_era).is_empty());
+        assert!(!XStaking::has_pending_slash(&4));
+        let slashed_event = MetaEvent::staking(RawEvent::NominationsSlashed(4, 16));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == slashed_event));
+        // The deferred slashes are recorded in the era of the offence.
+        assert_eq!(XStaking::validator_history_of(apply_era, 4).slashed, 0);
+        assert!(XStaking::validator_history_of(apply_era - 1, 4).slashed >= 16);
+
+        assert_eq!(XStaking::bonded_to(&4, &4), 36);
+        assert_eq!(XStaking::bonded_to(&t_1, &4), 44);
+        assert_bonded_locks(t_1, 44);
+        assert_bonded_withdrawal_locks(t_1, 10);
+        assert_eq!(Balances::free_balance(t_1), 94);
+
+        // The points of pool members are reduced proportionally.
+        assert_eq!(XStaking::bonded_to(&pool_account, &4), 54);
+        assert_eq!(XStaking::pool_ledgers(pool_id).total_points, 54);
+        assert_eq!(XStaking::pool_members(pool_id, t_2).points, 36);
+        assert_eq!(XStaking::pool_members(pool_id, t_3).points, 18);
+        assert_eq!(Balances::free_balance(pool_account), 54);
+
+        assert_ok!(t_rebond(t_1, 4, 2, 10));
+        assert_ok!(t_unbond(t_1, 4, 10));
+        assert_ok!(XStaking::unbond_from_pool(Origin::signed(t_2), pool_id, 10));
+    });
+}
+
+#[test]
+fn slashing_span_should_restart_once_chilled() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XStaking::set_slashing_mode(
+            Origin::root(),
+            SlashingMode::NominatorProportional
+        ));
+        assert_ok!(XStaking::set_slash_defer_duration(Origin::root(), 0));
+
+        let offenders = vec![(4, Perbill::from_percent(10))]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(
+            XStaking::slash_offenders_in_session(offenders, vec![]),
+            vec![4]
+        );
+        assert!(XStaking::is_chilled(&4));
+        // Applied immediately without the defer duration.
+        assert!(XStaking::unapplied_slashes().is_empty());
+        assert_eq!(XStaking::bonded_to(&4, &4), 36);
+        assert_eq!(
+            XStaking::slashing_spans(4),
+            SlashingSpan {
+                index: 1,
+                start: XStaking::current_era().unwrap_or(0),
+                max_slash_fraction: Perbill::zero(),
+            }
+        );
+    });
+}
+
+#[test]
+fn validator_history_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_start_session(1);
+        t_start_session(2);
+        t_start_session(3);
+        // The reward of a session is minted at the beginning of next session.
+        t_start_session(4);
+        let active_era = XStaking::active_era().unwrap().index;
+
+        let history = XStaking::validator_history(4, 100);
+        assert!(!history.is_empty());
+        assert!(history.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert_eq!(history.last().unwrap().0, active_era);
+        for (_, record) in history.iter() {
+            assert!(record.active_sessions > 0);
+            assert!(record.reward_pot_inflow > 0);
+            assert!(record.reward_pot_inflow < record.session_rewards);
+            assert_eq!(record.offline_sessions, 0);
+        }
+        assert_eq!(XStaking::validator_history(4, 1).len(), 1);
+        assert!(XStaking::validator_history(5, 100).is_empty());
+
+        // The offline sessions and slashes are recorded.
+        assert_ok!(XStaking::set_minimum_penalty(Origin::root(), 5));
+        let before = XStaking::validator_history_of(active_era, 1);
+        let offenders = vec![(1, Perbill::zero())]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        XStaking::slash_offenders_in_session(offenders, vec![]);
+        let after = XStaking::validator_history_of(active_era, 1);
+        assert_eq!(after.offline_sessions, before.offline_sessions + 1);
+        assert_eq!(after.slashed, before.slashed + 5);
+
+        // The records out of the history depth are pruned.
+        XStaking::prune_validator_history(active_era + VALIDATOR_HISTORY_DEPTH);
+        assert!(!ValidatorHistory::<Test>::contains_key(active_era, 4));
+    });
+}
+
+#[test]
+fn rename_referral_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XStaking::set_referral_rename_fee(Origin::root(), 5));
+        assert_ok!(XStaking::set_referral_rename_interval(Origin::root(), 10));
+
+        assert_err!(
+            XStaking::rename_referral(Origin::signed(123), b"referral1".to_vec()),
+            Error::<Test>::NotValidator
+        );
+        assert_err!(
+            XStaking::rename_referral(Origin::signed(1), b"2 ".to_vec()),
+            Error::<Test>::OccupiedReferralIdentity
+        );
+
+        let treasury_balance = Balances::free_balance(TREASURY_ACCOUNT);
+        assert_ok!(XStaking::rename_referral(
+            Origin::signed(1),
+            b"referral1".to_vec()
+        ));
+        assert_eq!(Balances::free_balance(1), 95);
+        assert_eq!(
+            Balances::free_balance(TREASURY_ACCOUNT),
+            treasury_balance + 5
+        );
+        assert_eq!(XStaking::validators(1).referral_id, b"referral1".to_vec());
+        assert_eq!(XStaking::validator_for(b"referral1"), Some(1));
+        assert_eq!(XStaking::validator_for(b"1 "), None);
+        // The old referral id still points at the validator and can't be claimed by others.
+        assert_eq!(XStaking::renamed_validator_for(b"1 "), Some(1));
+        assert_err!(
+            XStaking::register(Origin::signed(111), b"1 ".to_vec(), 0),
+            Error::<Test>::OccupiedReferralIdentity
+        );
+        let renamed_event = MetaEvent::staking(RawEvent::ReferralRenamed(
+            1,
+            b"1 ".to_vec(),
+            b"referral1".to_vec(),
+        ));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == renamed_event));
+
+        assert_err!(
+            XStaking::rename_referral(Origin::signed(1), b"1 ".to_vec()),
+            Error::<Test>::NoMoreReferralRename
+        );
+        t_system_block_number_inc(10);
+
+        // The validator is able to claim the old referral id back.
+        assert_ok!(XStaking::rename_referral(Origin::signed(1), b"1 ".to_vec()));
+        assert_eq!(XStaking::validator_for(b"1 "), Some(1));
+        assert_eq!(XStaking::renamed_validator_for(b"1 "), None);
+        assert_eq!(XStaking::renamed_validator_for(b"referral1"), Some(1));
+        t_system_block_number_inc(10);
+
+        // Only the latest old referral id is reserved, the previous one is released.
+        assert_ok!(XStaking::rename_referral(
+            Origin::signed(1),
+            b"referral2".to_vec()
+        ));
+        assert_eq!(XStaking::renamed_validator_for(b"referral1"), None);
+        assert_eq!(XStaking::renamed_validator_for(b"1 "), Some(1));
+        assert_eq!(XStaking::reserved_referral_id_of(1), Some(b"1 ".to_vec()));
+        assert_ok!(XStaking::register(
+            Origin::signed(111),
+            b"referral1".to_vec(),
+            0
+        ));
+    });
+}
+
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(t_bond(t_1, 2, 10));
+
+        t_start_session(1);
+        let current_block = System::block_number();
+        let dividend_1 = XStaking::compute_dividend_at(&t_1, &1, current_block).unwrap();
+        let dividend_2 = XStaking::compute_dividend_at(&t_1, &2, current_block).unwrap();
+        assert!(dividend_1 > 0 && dividend_2 > 0);
+
+        // The nomination without any vote weight yet is skipped.
+        assert_ok!(t_bond(t_1, 3, 10));
+        assert_err!(
+            XStaking::claim_all(Origin::signed(t_1), 2),
+            Error::<Test>::InvalidNominationsWitness
+        );
+
+        let free_before = Balances::free_balance(t_1);
+        assert_ok!(XStaking::claim_all(Origin::signed(t_1), 3));
+        assert_eq!(
+            Balances::free_balance(t_1),
+            free_before + dividend_1 + dividend_2
+        );
+        let claimed_event = MetaEvent::staking(RawEvent::AllClaimed(t_1, dividend_1 + dividend_2));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == claimed_event));
+        assert_eq!(
+            XStaking::compute_dividend_at(&t_1, &1, current_block).unwrap(),
+            0
+        );
+        assert_eq!(
+            XStaking::compute_dividend_at(&t_1, &2, current_block).unwrap(),
+            0
+        );
     });
 }
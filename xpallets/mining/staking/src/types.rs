@@ -7,15 +7,16 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use sp_runtime::{
-    traits::{SaturatedConversion, Saturating},
-    DispatchError, DispatchResult, RuntimeDebug,
+    traits::{AtLeast32BitUnsigned, SaturatedConversion, Saturating, Zero},
+    DispatchError, DispatchResult, Perbill, RuntimeDebug,
 };
 
 use chainx_primitives::{AssetId, ReferralId};
 use xp_logging::debug;
 use xp_mining_common::{RewardPotAccountFor, WeightType};
-use xp_mining_staking::MiningPower;
+use xp_mining_staking::{MiningPower, SessionIndex};
 
+use crate::constants::{MAX_ANNUAL_INFLATION, MAX_SESSION_REWARD, MIN_SESSIONS_PER_YEAR};
 use crate::{AssetMining, BalanceOf, EraIndex, Event, Module, Trait};
 
 pub type VoteWeight = WeightType;
@@ -108,6 +109,72 @@ pub struct ValidatorProfile<BlockNumber> {
     /// Referral identity that belongs to the validator.
     #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
     pub referral_id: ReferralId,
+    /// Proportion of the session reward directly paid to the validator,
+    /// the rest goes to the reward pot shared by all the nominators.
+    pub commission: Perbill,
+    /// Block number of last performed `set_commission` operation.
+    pub last_commission_change: Option<BlockNumber>,
+}
+
+/// The storage version of this pallet.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    /// `ValidatorProfile` without commission.
+    V1_0_0,
+    /// `commission` and `last_commission_change` are added to `ValidatorProfile`.
+    V2_0_0,
+    /// `NominatorsOf` and `PendingSlashCount` are added.
+    V3_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
+/// Identifier of a nomination pool.
+pub type PoolId = u32;
+
+/// Profile of a nomination pool.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NominationPool<AccountId, BlockNumber> {
+    /// Account that created this pool.
+    pub owner: AccountId,
+    /// Validators nominated by the pool.
+    pub validators: Vec<AccountId>,
+    /// Block number at which point the pool was created.
+    pub created_at: BlockNumber,
+}
+
+/// Vote weight properties of a nomination pool.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolLedger<Balance, VoteWeight, BlockNumber> {
+    /// Total points of all the pool members, 1 point per bonded PCX.
+    pub total_points: Balance,
+    /// Last calculated total vote weight of the pool members.
+    pub last_total_weight: VoteWeight,
+    /// Block number at which point `last_total_weight` just updated.
+    pub last_total_weight_update: BlockNumber,
+}
+
+/// Vote weight properties of a pool member.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolMember<Balance, VoteWeight, BlockNumber> {
+    /// Points of the member in the pool.
+    pub points: Balance,
+    /// Last calculated vote weight of the member.
+    pub last_weight: VoteWeight,
+    /// Block number at which point `last_weight` just updated.
+    pub last_weight_update: BlockNumber,
+    /// Unbonded entries.
+    pub unbonded_chunks: Vec<Unbonded<Balance, BlockNumber>>,
 }
 
 /// Information regarding the active era (era in used in session).
@@ -142,6 +209,114 @@ impl Default for Forcing {
     }
 }
 
+/// Strategy of electing the new validator set at the end of an era.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ElectionStrategy {
+    /// Choose the candidates with the most total nominations.
+    SortByNomination,
+    /// Sequential Phragmén over all the nominations, computed by the offchain workers
+    /// and verified on chain. Falls back to `SortByNomination` if no valid solution
+    /// has been submitted.
+    SequentialPhragmen,
+}
+
+impl Default for ElectionStrategy {
+    fn default() -> Self {
+        ElectionStrategy::SortByNomination
+    }
+}
+
+/// Score of an election solution, `[min_support, sum_support, sum_support_squared]`.
+///
+/// A solution is better if it has a greater minimal support, then a greater total
+/// support, then a smaller sum of squared supports.
+pub type ElectionScore = [u128; 3];
+
+/// How the stake of a nominator is distributed among the elected validators.
+pub type StakeAssignment<AccountId, Balance> = (AccountId, Vec<(AccountId, Balance)>);
+
+/// The verified election result waiting to be applied in the new era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ElectionOutcome<AccountId> {
+    /// Era the outcome is elected for.
+    pub era: EraIndex,
+    /// Elected validators.
+    pub winners: Vec<AccountId>,
+    /// Score of the solution.
+    pub score: ElectionScore,
+}
+
+/// Performance of a validator in an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorEraRecord<Balance> {
+    /// Total session rewards of the validator, including the commission.
+    pub session_rewards: Balance,
+    /// The part of session rewards issued to the reward pot.
+    pub reward_pot_inflow: Balance,
+    /// Total balances slashed from the reward pot and the nominations.
+    pub slashed: Balance,
+    /// Number of sessions the validator was rewarded as an active validator.
+    pub active_sessions: u32,
+    /// Number of sessions the validator was reported to be offline.
+    pub offline_sessions: u32,
+}
+
+/// Mode of slashing the offenders.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SlashingMode {
+    /// Only the reward pot of the offender is slashed.
+    RewardPotOnly,
+    /// Besides the reward pot, the self-bonded and nominated balances of the offender
+    /// are slashed proportionally by the slash fraction.
+    NominatorProportional,
+}
+
+impl Default for SlashingMode {
+    fn default() -> Self {
+        SlashingMode::RewardPotOnly
+    }
+}
+
+/// Index of a slashing span.
+pub type SpanIndex = u32;
+
+/// A slashing span of a validator.
+///
+/// The nominations are slashed at most once by the maximum slash fraction within a span,
+/// the span ends once the offender is chilled.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SlashingSpan {
+    /// Index of the span.
+    pub index: SpanIndex,
+    /// Era at which point the span started.
+    pub start: EraIndex,
+    /// The maximum fraction the nominations have been slashed in this span.
+    pub max_slash_fraction: Perbill,
+}
+
+/// A pending slash of the nominations to an offender.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct UnappliedSlash<AccountId, Balance> {
+    /// The offender.
+    pub validator: AccountId,
+    /// The amount to be slashed from the self-bonded balance of the offender.
+    pub own: Balance,
+    /// The amounts to be slashed from the nominators of the offender.
+    pub others: Vec<(AccountId, Balance)>,
+    /// The active era when the offence was reported.
+    pub offence_era: EraIndex,
+}
+
 /// Top level shares of various reward destinations.
 #[derive(Copy, Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -222,6 +397,116 @@ impl MiningDistribution {
     }
 }
 
+/// The curve of the newly minted PCX per session.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum EmissionSchedule<Balance> {
+    /// The session reward is cut in half periodically, the way Bitcoin does.
+    Halving {
+        /// Reward per session before the first halving.
+        initial_reward: Balance,
+        /// Number of sessions between two halvings.
+        sessions_per_round: SessionIndex,
+        /// The reward is halved for the first time after this session.
+        halving_offset: SessionIndex,
+    },
+    /// The session reward decreases by a fixed amount per session until reaching a floor.
+    LinearDecay {
+        /// Reward per session before the decay starts.
+        initial_reward: Balance,
+        /// Amount subtracted from the reward for each session after `start_session`.
+        decay_per_session: Balance,
+        /// The session reward never goes below this value.
+        min_reward: Balance,
+        /// The decay starts after this session.
+        start_session: SessionIndex,
+    },
+    /// A fixed annual inflation rate of the total issuance.
+    FixedInflation {
+        /// Ratio of the total issuance minted per year.
+        annual_inflation: Perbill,
+        /// Expected number of sessions per year.
+        sessions_per_year: SessionIndex,
+    },
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> EmissionSchedule<Balance> {
+    /// Returns true if the schedule parameters are sane.
+    ///
+    /// The caps make sure that a typo in governance can not mint without limit.
+    pub fn is_valid(&self) -> bool {
+        let max_session_reward: Balance = MAX_SESSION_REWARD.saturated_into();
+        match self {
+            Self::Halving {
+                initial_reward,
+                sessions_per_round,
+                ..
+            } => *sessions_per_round > 0 && *initial_reward <= max_session_reward,
+            Self::LinearDecay {
+                initial_reward,
+                decay_per_session,
+                min_reward,
+                ..
+            } => {
+                !decay_per_session.is_zero()
+                    && min_reward <= initial_reward
+                    && *initial_reward <= max_session_reward
+            }
+            Self::FixedInflation {
+                annual_inflation,
+                sessions_per_year,
+            } => {
+                *sessions_per_year >= MIN_SESSIONS_PER_YEAR
+                    && *annual_inflation <= MAX_ANNUAL_INFLATION
+            }
+        }
+    }
+
+    /// Returns true if this is the Bitcoin-like halving schedule.
+    pub fn is_halving(&self) -> bool {
+        match self {
+            Self::Halving { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the total reward of session `index` given the current `total_issuance`.
+    pub fn session_reward(&self, index: SessionIndex, total_issuance: Balance) -> Balance {
+        match *self {
+            Self::Halving {
+                initial_reward,
+                sessions_per_round,
+                halving_offset,
+            } => {
+                let halving_epoch = if index > halving_offset {
+                    (index - halving_offset - 1) / sessions_per_round + 1
+                } else {
+                    0
+                };
+                initial_reward
+                    .saturated_into::<u128>()
+                    .checked_shr(halving_epoch)
+                    .unwrap_or(0)
+                    .saturated_into()
+            }
+            Self::LinearDecay {
+                initial_reward,
+                decay_per_session,
+                min_reward,
+                start_session,
+            } => {
+                let elapsed: Balance = index.saturating_sub(start_session).into();
+                initial_reward
+                    .saturating_sub(decay_per_session.saturating_mul(elapsed))
+                    .max(min_reward)
+            }
+            Self::FixedInflation {
+                annual_inflation,
+                sessions_per_year,
+            } => annual_inflation * total_issuance / sessions_per_year.into(),
+        }
+    }
+}
+
 /// Result of performing a slash operation.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub enum SlashOutcome<Balance> {
@@ -44,6 +44,29 @@ pub trait WeightInfo {
     fn set_validator_bonding_duration() -> Weight;
     fn set_minimum_penalty() -> Weight;
     fn set_sessions_per_era() -> Weight;
+    fn set_emission_schedule() -> Weight;
+    fn set_commission() -> Weight;
+    fn set_maximum_commission() -> Weight;
+    fn set_commission_change_interval() -> Weight;
+    fn set_auto_compound() -> Weight;
+    fn set_maximum_auto_compound_per_block() -> Weight;
+    fn create_pool(v: u32) -> Weight;
+    fn bond_to_pool() -> Weight;
+    fn unbond_from_pool() -> Weight;
+    fn withdraw_from_pool() -> Weight;
+    fn claim_from_pool(v: u32) -> Weight;
+    fn submit_election_solution(v: u32, n: u32) -> Weight;
+    fn set_election_strategy() -> Weight;
+    fn set_minimum_election_score() -> Weight;
+    fn unlock_all_due(n: u32) -> Weight;
+    fn rebond_unbonded() -> Weight;
+    fn set_slashing_mode() -> Weight;
+    fn set_slash_defer_duration() -> Weight;
+    fn cancel_deferred_slash(s: u32) -> Weight;
+    fn rename_referral() -> Weight;
+    fn set_referral_rename_interval() -> Weight;
+    fn set_referral_rename_fee() -> Weight;
+    fn claim_all(n: u32) -> Weight;
 }
 
 /// Weights for xpallet_mining_staking using the Substrate node and recommended hardware.
@@ -107,6 +130,116 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_emission_schedule() -> Weight {
+        (5_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_commission() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_commission() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_commission_change_interval() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_auto_compound() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_auto_compound_per_block() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn create_pool(v: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((3_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn bond_to_pool() -> Weight {
+        (620_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(40 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn unbond_from_pool() -> Weight {
+        (650_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(40 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn withdraw_from_pool() -> Weight {
+        (300_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn claim_from_pool(v: u32) -> Weight {
+        (300_000_000 as Weight)
+            .saturating_add((760_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(v as Weight)))
+    }
+    fn submit_election_solution(v: u32, n: u32) -> Weight {
+        (120_000_000 as Weight)
+            .saturating_add((45_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add((38_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_election_strategy() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_minimum_election_score() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn unlock_all_due(n: u32) -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn rebond_unbonded() -> Weight {
+        (240_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn set_slashing_mode() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (38_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn rename_referral() -> Weight {
+        (112_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    fn set_referral_rename_interval() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_referral_rename_fee() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((148_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -169,4 +302,114 @@ impl WeightInfo for () {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_emission_schedule() -> Weight {
+        (5_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_commission() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_commission() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_commission_change_interval() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_auto_compound() -> Weight {
+        (25_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_auto_compound_per_block() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn create_pool(v: u32) -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add((3_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn bond_to_pool() -> Weight {
+        (620_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(40 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn unbond_from_pool() -> Weight {
+        (650_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(40 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn withdraw_from_pool() -> Weight {
+        (300_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn claim_from_pool(v: u32) -> Weight {
+        (300_000_000 as Weight)
+            .saturating_add((760_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(v as Weight)))
+    }
+    fn submit_election_solution(v: u32, n: u32) -> Weight {
+        (120_000_000 as Weight)
+            .saturating_add((45_000_000 as Weight).saturating_mul(v as Weight))
+            .saturating_add((38_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_election_strategy() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_minimum_election_score() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn unlock_all_due(n: u32) -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn rebond_unbonded() -> Weight {
+        (240_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn set_slashing_mode() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (38_000_000 as Weight)
+            .saturating_add((1_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn rename_referral() -> Weight {
+        (112_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    fn set_referral_rename_interval() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_referral_rename_fee() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (52_000_000 as Weight)
+            .saturating_add((148_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
 }
@@ -18,6 +18,9 @@ pub trait Validator<AccountId> {
     fn is_validator(who: &AccountId) -> bool;
 
     fn validator_for(name: &[u8]) -> Option<AccountId>;
+
+    /// Returns the validator that has renamed its referral `name` to another one.
+    fn renamed_validator_for(name: &[u8]) -> Option<AccountId>;
 }
 
 impl<AccountId> Validator<AccountId> for () {
@@ -28,6 +31,10 @@ impl<AccountId> Validator<AccountId> for () {
     fn validator_for(_: &[u8]) -> Option<AccountId> {
         None
     }
+
+    fn renamed_validator_for(_: &[u8]) -> Option<AccountId> {
+        None
+    }
 }
 
 /// This trait provides a simple way to get the treasury account.
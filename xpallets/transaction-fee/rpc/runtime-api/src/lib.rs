@@ -7,9 +7,12 @@
 use codec::Codec;
 use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
 
-pub use xpallet_transaction_fee::{FeeDetails, InclusionFee};
+pub use xpallet_transaction_fee::{AssetFee, FeeDetails, InclusionFee};
 
 sp_api::decl_runtime_apis! {
+    /// Version 2: `asset_fee` is added to `FeeDetails`.
+    /// Version 3: `sponsor` is added to `FeeDetails`.
+    #[api_version(3)]
     pub trait XTransactionFeeApi<Balance> where
         Balance: Codec + MaybeDisplay + MaybeFromStr,
     {
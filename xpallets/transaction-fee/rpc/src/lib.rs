@@ -9,7 +9,7 @@ use codec::{Codec, Decode};
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_runtime::{
@@ -20,7 +20,7 @@ use sp_runtime::{
 use pallet_transaction_payment_rpc::Error;
 
 use xp_rpc::RpcBalance;
-use xpallet_transaction_fee_rpc_runtime_api::{FeeDetails, InclusionFee};
+use xpallet_transaction_fee_rpc_runtime_api::{AssetFee, FeeDetails, InclusionFee};
 
 pub use xpallet_transaction_fee_rpc_runtime_api::XTransactionFeeApi as XTransactionFeeRuntimeApi;
 
@@ -66,6 +66,18 @@ where
 
         let uxt: Block::Extrinsic = Decode::decode(&mut &*encoded_xt).map_err(into_rpc_err)?;
 
+        // `FeeDetails` of the other versions can not be decoded.
+        let supported = api
+            .has_api::<dyn XTransactionFeeRuntimeApi<Block, Balance>>(&at)
+            .map_err(into_rpc_err)?;
+        if !supported {
+            return Err(RpcError {
+                code: ErrorCode::ServerError(Error::RuntimeError.into()),
+                message: "Unsupported version of the transaction fee runtime api.".into(),
+                data: None,
+            });
+        }
+
         api.query_fee_details(&at, uxt, encoded_len)
             .map(|fee_details| FeeDetails {
                 inclusion_fee: fee_details.inclusion_fee.map(|fee| InclusionFee {
@@ -76,6 +88,11 @@ where
                 tip: fee_details.tip.into(),
                 extra_fee: fee_details.extra_fee.into(),
                 final_fee: fee_details.final_fee.into(),
+                asset_fee: fee_details.asset_fee.map(|fee| AssetFee {
+                    asset_id: fee.asset_id,
+                    amount: fee.amount.into(),
+                }),
+                sponsor: fee_details.sponsor,
             })
             .map_err(into_rpc_err)
     }
@@ -0,0 +1,100 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::{
+    storage::{StorageDoubleMap, StorageMap},
+    traits::Currency,
+};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+use super::*;
+
+const ASSET_ID: AssetId = 1;
+const SEED: u32 = 0;
+
+fn fund_sponsor<T: Trait>() -> T::AccountId {
+    let sponsor: T::AccountId = whitelisted_caller();
+    <T as xpallet_assets::Trait>::Currency::make_free_balance_be(
+        &sponsor,
+        DepositBalanceOf::<T>::max_value() / 2u32.into(),
+    );
+    sponsor
+}
+
+fn max_call_filters(n: u32) -> Vec<CallFilter> {
+    (0..n)
+        .map(|_| CallFilter {
+            pallet: vec![b'a'; MAX_CALL_NAME_LEN],
+            call: Some(vec![b'a'; MAX_CALL_NAME_LEN]),
+        })
+        .collect()
+}
+
+benchmarks! {
+    _{ }
+
+    set_fee_asset_rate {
+        let rate = FeeAssetRate::Fixed(100_000);
+    }: _(RawOrigin::Root, ASSET_ID, Some(rate))
+    verify {
+        assert_eq!(FeeAssetRateOf::get(ASSET_ID), Some(rate));
+    }
+
+    sponsor {
+        let n in 0 .. MAX_CALL_FILTERS as u32;
+        let sponsor = fund_sponsor::<T>();
+        let call_filters = max_call_filters(n);
+    }: _(RawOrigin::Signed(sponsor.clone()), 100u32.into(), 10u32.into(), call_filters)
+    verify {
+        assert_eq!(
+            SponsorshipOf::<T>::get(&sponsor).map(|s| s.call_filters.len()),
+            Some(n as usize)
+        );
+    }
+
+    toggle_sponsored {
+        let sponsor = fund_sponsor::<T>();
+        Module::<T>::sponsor(
+            RawOrigin::Signed(sponsor.clone()).into(),
+            100u32.into(),
+            10u32.into(),
+            Vec::new(),
+        )?;
+        let who: T::AccountId = account("who", 0, SEED);
+        let who_lookup = T::Lookup::unlookup(who.clone());
+    }: _(RawOrigin::Signed(sponsor.clone()), who_lookup, true)
+    verify {
+        assert!(SponsoredAccounts::<T>::get(&sponsor, &who));
+    }
+
+    unsponsor {
+        let sponsor = fund_sponsor::<T>();
+        Module::<T>::sponsor(
+            RawOrigin::Signed(sponsor.clone()).into(),
+            100u32.into(),
+            10u32.into(),
+            max_call_filters(MAX_CALL_FILTERS as u32),
+        )?;
+    }: _(RawOrigin::Signed(sponsor.clone()))
+    verify {
+        assert!(SponsorshipOf::<T>::get(&sponsor).is_none());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{ExtBuilder, Test};
+    use frame_support::assert_ok;
+
+    #[test]
+    fn test_benchmarks() {
+        ExtBuilder::default().build().execute_with(|| {
+            assert_ok!(test_benchmark_set_fee_asset_rate::<Test>());
+            assert_ok!(test_benchmark_sponsor::<Test>());
+            assert_ok!(test_benchmark_toggle_sponsored::<Test>());
+            assert_ok!(test_benchmark_unsponsor::<Test>());
+        });
+    }
+}
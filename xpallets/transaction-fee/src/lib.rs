@@ -5,36 +5,233 @@
 //! This module is a complement to pallet-transaction-payment module, unlike
 //! pallet-transaction-payment which merely returns the value of final fee, it
 //! exposes all the details of calculated transation fee in a struct `FeeDetails`.
+//! It also maintains the whitelist of assets that can be used to pay the fee, and the
+//! sponsorships under which the fee of the signer is paid by a sponsor.
 //!
 //! The future improvement is to make this feature native to Substrate's transaction-payment
 //! module so that we don't have to copy and paste the core logic of fee calculation.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+mod payment;
+#[cfg(test)]
+mod tests;
 mod types;
+pub mod weights;
 
 use sp_std::prelude::*;
 
 use frame_support::{
-    decl_event, decl_module,
-    traits::Get,
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::CallMetadata,
+    ensure,
+    traits::{Get, ReservableCurrency},
     weights::{
         DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, Weight, WeightToFeePolynomial,
     },
 };
+use frame_system::{ensure_root, ensure_signed};
 use sp_runtime::{
-    traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, Saturating},
+    traits::{
+        DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SaturatedConversion, Saturating,
+        StaticLookup,
+    },
     FixedPointNumber, FixedPointOperand,
 };
 
-pub use self::types::{FeeDetails, InclusionFee};
+use chainx_primitives::AssetId;
+use xpallet_assets::{AssetErr, AssetType};
+
+pub use self::payment::ChargeAssetTxPayment;
+pub use self::types::{AssetFee, CallFilter, FeeAssetRate, FeeDetails, InclusionFee, Sponsorship};
+pub use self::weights::WeightInfo;
 
 type BalanceOf<T> = <<T as pallet_transaction_payment::Trait>::OnChargeTransaction as pallet_transaction_payment::OnChargeTransaction<T>>::Balance;
+type DepositBalanceOf<T> = xpallet_assets::BalanceOf<T>;
+
+/// Maximum number of the call filters of a sponsorship.
+pub const MAX_CALL_FILTERS: usize = 16;
+
+/// Maximum length of the pallet name and the call name in a call filter.
+pub const MAX_CALL_NAME_LEN: usize = 32;
+
+/// Provides the price of assets measured by PCX.
+pub trait PriceFeed {
+    /// Returns the amount of PCX per unit of `asset_id`, both in the smallest unit.
+    fn native_price_of(asset_id: AssetId) -> Option<u128>;
+}
+
+impl PriceFeed for () {
+    fn native_price_of(_: AssetId) -> Option<u128> {
+        None
+    }
+}
+
+/// Provides the receivers of the transaction fee.
+pub trait FeeReceivers<AccountId> {
+    /// Returns the block author and its reward pot.
+    fn author_and_reward_pot() -> (AccountId, AccountId);
+}
+
+pub trait Trait: pallet_transaction_payment::Trait + xpallet_assets::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The price source of the fee assets following the time-weighted average price
+    /// of the spot market, which can hardly be manipulated by a single trade.
+    type PriceFeed: PriceFeed;
+
+    /// The receivers of the transaction fee paid in the whitelisted assets.
+    type FeeReceivers: FeeReceivers<Self::AccountId>;
+
+    /// The deposit reserved from the sponsor for a sponsorship.
+    type SponsorshipDeposit: Get<DepositBalanceOf<Self>>;
+
+    /// The deposit reserved from the sponsor for each call filter of a sponsorship.
+    type CallFilterDeposit: Get<DepositBalanceOf<Self>>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XTransactionFee {
+        /// The whitelisted assets that can be used to pay the transaction fee.
+        pub FeeAssetRateOf get(fn fee_asset_rate_of):
+            map hasher(twox_64_concat) AssetId => Option<FeeAssetRate>;
 
-pub trait Trait: pallet_transaction_payment::Trait {}
+        /// The fee sponsorship of each sponsor.
+        pub SponsorshipOf get(fn sponsorship_of):
+            map hasher(blake2_128_concat) T::AccountId => Option<Sponsorship<BalanceOf<T>>>;
+
+        /// The accounts whitelisted by the sponsor, all of whose calls are sponsored.
+        pub SponsoredAccounts get(fn is_sponsored):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId
+            => bool;
+
+        /// The deposit reserved for the sponsorship of each sponsor.
+        pub SponsorshipDepositOf get(fn sponsorship_deposit_of):
+            map hasher(blake2_128_concat) T::AccountId => DepositBalanceOf<T>;
+    }
+}
+
+decl_error! {
+    /// Error for the XTransactionFee Module
+    pub enum Error for Module<T: Trait> {
+        /// The sponsor has no sponsorship.
+        NoSponsorship,
+        /// The call of the account is not sponsored.
+        NotSponsored,
+        /// The fee exceeds the maximum fee per transaction of the sponsorship.
+        ExceedMaxFeePerTx,
+        /// The remaining budget of the sponsorship is insufficient.
+        InsufficientBudget,
+        /// Too many call filters.
+        TooManyCallFilters,
+        /// The pallet name or the call name of a call filter is too long.
+        CallFilterTooLong,
+        /// The asset is not whitelisted, has no price or can not be moved.
+        InvalidFeeAsset,
+        /// The fee asset of the payer is frozen.
+        FeeAssetFrozen,
+        /// The usable balance of the fee asset is insufficient.
+        InsufficientFeeAsset,
+    }
+}
 
 decl_module! {
-    pub struct Module<T: Trait> for enum Call where origin: T::Origin {}
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Sets the conversion rate of a fee asset, `None` removes it from the whitelist.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_fee_asset_rate()]
+        fn set_fee_asset_rate(origin, #[compact] asset_id: AssetId, rate: Option<FeeAssetRate>) {
+            ensure_root(origin)?;
+            match rate {
+                Some(rate) => FeeAssetRateOf::insert(asset_id, rate),
+                None => FeeAssetRateOf::remove(asset_id),
+            }
+        }
+
+        /// Sponsors the fee of the calls of the whitelisted accounts matching `call_filters`,
+        /// all the calls of them are sponsored if `call_filters` is empty.
+        ///
+        /// The previous sponsorship of the sender will be replaced. A deposit is reserved
+        /// according to the number of call filters.
+        #[weight = <T as Trait>::WeightInfo::sponsor(call_filters.len() as u32)]
+        fn sponsor(
+            origin,
+            budget: BalanceOf<T>,
+            max_fee_per_tx: BalanceOf<T>,
+            call_filters: Vec<CallFilter>
+        ) {
+            let sponsor = ensure_signed(origin)?;
+            ensure!(call_filters.len() <= MAX_CALL_FILTERS, Error::<T>::TooManyCallFilters);
+            ensure!(
+                call_filters.iter().all(|f| f.pallet.len() <= MAX_CALL_NAME_LEN
+                    && f.call.as_ref().map_or(true, |call| call.len() <= MAX_CALL_NAME_LEN)),
+                Error::<T>::CallFilterTooLong
+            );
+
+            let deposit = T::SponsorshipDeposit::get().saturating_add(
+                T::CallFilterDeposit::get().saturating_mul((call_filters.len() as u32).into())
+            );
+            let old_deposit = Self::sponsorship_deposit_of(&sponsor);
+            if deposit > old_deposit {
+                <T as xpallet_assets::Trait>::Currency::reserve(&sponsor, deposit - old_deposit)?;
+            } else {
+                <T as xpallet_assets::Trait>::Currency::unreserve(&sponsor, old_deposit - deposit);
+            }
+            SponsorshipDepositOf::<T>::insert(&sponsor, deposit);
+
+            SponsorshipOf::<T>::insert(&sponsor, Sponsorship {
+                budget,
+                max_fee_per_tx,
+                call_filters,
+            });
+            Self::deposit_event(Event::<T>::SponsorshipSet(sponsor, budget));
+        }
+
+        /// Adds `who` to or removes it from the whitelist of the sender's sponsorship.
+        #[weight = <T as Trait>::WeightInfo::toggle_sponsored()]
+        fn toggle_sponsored(
+            origin,
+            who: <T::Lookup as StaticLookup>::Source,
+            should_sponsor: bool
+        ) {
+            let sponsor = ensure_signed(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            ensure!(SponsorshipOf::<T>::contains_key(&sponsor), Error::<T>::NoSponsorship);
+            if should_sponsor {
+                SponsoredAccounts::<T>::insert(&sponsor, &who, true);
+            } else {
+                SponsoredAccounts::<T>::remove(&sponsor, &who);
+            }
+        }
+
+        /// Stops sponsoring the fee and unreserves the deposit.
+        ///
+        /// The whitelist is kept and takes effect again once the sender sponsors again.
+        #[weight = <T as Trait>::WeightInfo::unsponsor()]
+        fn unsponsor(origin) {
+            let sponsor = ensure_signed(origin)?;
+            ensure!(SponsorshipOf::<T>::contains_key(&sponsor), Error::<T>::NoSponsorship);
+            SponsorshipOf::<T>::remove(&sponsor);
+            <T as xpallet_assets::Trait>::Currency::unreserve(
+                &sponsor,
+                SponsorshipDepositOf::<T>::take(&sponsor),
+            );
+            Self::deposit_event(Event::<T>::SponsorshipRemoved(sponsor));
+        }
+    }
 }
 
 decl_event!(
@@ -47,6 +244,15 @@ decl_event!(
         /// Transaction fee was paid to the block author and its reward pot in 1:9.
         /// [author, author_fee, reward_pot, reward_pot_fee]
         FeePaid(AccountId, Balance, AccountId, Balance),
+        /// Transaction fee was paid in a whitelisted asset to the block author and its reward pot in 1:9.
+        /// [asset_id, author, author_fee, reward_pot, reward_pot_fee]
+        AssetFeePaid(AssetId, AccountId, Balance, AccountId, Balance),
+        /// A sponsor set the fee sponsorship. [sponsor, budget]
+        SponsorshipSet(AccountId, Balance),
+        /// A sponsor stopped sponsoring the fee. [sponsor]
+        SponsorshipRemoved(AccountId),
+        /// The transaction fee of an account was paid by the sponsor. [sponsor, who, fee]
+        FeeSponsored(AccountId, AccountId, Balance),
     }
 );
 
@@ -139,6 +345,8 @@ where
                 tip,
                 extra_fee: 0u32.into(),
                 final_fee: total,
+                asset_fee: None,
+                sponsor: None,
             }
         } else {
             FeeDetails {
@@ -146,6 +354,8 @@ where
                 tip,
                 extra_fee: 0u32.into(),
                 final_fee: tip,
+                asset_fee: None,
+                sponsor: None,
             }
         }
     }
@@ -157,3 +367,215 @@ where
         T::WeightToFee::calc(&capped_weight)
     }
 }
+
+impl<T: Trait> Module<T> {
+    /// Converts the native `fee` into the amount of the whitelisted `asset_id`, rounding up.
+    ///
+    /// Returns `None` if `asset_id` is not whitelisted or its price is unavailable.
+    pub fn native_fee_in_asset(asset_id: AssetId, fee: BalanceOf<T>) -> Option<BalanceOf<T>> {
+        let native_price = match Self::fee_asset_rate_of(asset_id)? {
+            FeeAssetRate::Spot => T::PriceFeed::native_price_of(asset_id)?,
+            FeeAssetRate::Fixed(native_price) => native_price,
+        };
+        if native_price == 0 {
+            return None;
+        }
+        let fee = fee.saturated_into::<u128>();
+        let amount = fee / native_price + if fee % native_price == 0 { 0 } else { 1 };
+        Some(amount.saturated_into())
+    }
+
+    /// Ensures that `who` is able to pay the native `fee` in `asset_id`, without moving
+    /// any balance.
+    ///
+    /// Returns the fee measured by `asset_id`.
+    pub fn ensure_can_pay_asset_fee(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        fee: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let amount = Self::native_fee_in_asset(asset_id, fee).ok_or(Error::<T>::InvalidFeeAsset)?;
+        ensure!(
+            xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(&asset_id).is_ok()
+                && xpallet_assets::Module::<T>::can_move(&asset_id).is_ok(),
+            Error::<T>::InvalidFeeAsset
+        );
+        ensure!(
+            !xpallet_assets::Module::<T>::is_frozen(who, asset_id),
+            Error::<T>::FeeAssetFrozen
+        );
+        let usable = xpallet_assets::Module::<T>::usable_balance(who, &asset_id);
+        ensure!(
+            usable.saturated_into::<u128>() >= amount.saturated_into::<u128>(),
+            Error::<T>::InsufficientFeeAsset
+        );
+        Ok(amount)
+    }
+
+    /// Reserves the native `fee` in `asset_id` from `who` until `settle_asset_fee`.
+    ///
+    /// Returns the reserved fee measured by `asset_id`.
+    pub fn reserve_asset_fee(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        fee: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let amount = Self::ensure_can_pay_asset_fee(who, asset_id, fee)?;
+        Self::move_asset_fee(
+            asset_id,
+            who,
+            AssetType::Usable,
+            who,
+            AssetType::Reserved,
+            amount,
+        )?;
+        Ok(amount)
+    }
+
+    /// Charges the actual native fee from the `reserved` asset fee of `who` and refunds the rest.
+    ///
+    /// The reserved fee is charged in full if `asset_id` has been removed from the whitelist.
+    pub fn settle_asset_fee(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        reserved: BalanceOf<T>,
+        actual_fee: BalanceOf<T>,
+    ) -> Result<(), Error<T>> {
+        let actual_asset_fee = Self::native_fee_in_asset(asset_id, actual_fee)
+            .map_or(reserved, |fee| fee.min(reserved));
+        Self::move_asset_fee(
+            asset_id,
+            who,
+            AssetType::Reserved,
+            who,
+            AssetType::Usable,
+            reserved - actual_asset_fee,
+        )?;
+        Self::deal_with_asset_fee(who, asset_id, actual_asset_fee)
+    }
+
+    /// Moves the `fee` reserved by `who` in `asset_id` to the receivers of the transaction fee.
+    fn deal_with_asset_fee(
+        who: &T::AccountId,
+        asset_id: AssetId,
+        fee: BalanceOf<T>,
+    ) -> Result<(), Error<T>> {
+        // for fees, 90% to the reward pot of author, 10% to author
+        let to_reward_pot = fee.saturating_mul(90u32.into()) / 100u32.into();
+        let to_author = fee - to_reward_pot;
+
+        let (author, reward_pot) = T::FeeReceivers::author_and_reward_pot();
+
+        for (receiver, value) in [(&author, to_author), (&reward_pot, to_reward_pot)].iter() {
+            Self::move_asset_fee(
+                asset_id,
+                who,
+                AssetType::Reserved,
+                receiver,
+                AssetType::Usable,
+                *value,
+            )?;
+        }
+        Self::deposit_event(Event::<T>::AssetFeePaid(
+            asset_id,
+            author,
+            to_author,
+            reward_pot,
+            to_reward_pot,
+        ));
+        Ok(())
+    }
+
+    fn move_asset_fee(
+        asset_id: AssetId,
+        from: &T::AccountId,
+        from_type: AssetType,
+        to: &T::AccountId,
+        to_type: AssetType,
+        value: BalanceOf<T>,
+    ) -> Result<(), Error<T>> {
+        xpallet_assets::Module::<T>::move_balance(
+            &asset_id,
+            from,
+            from_type,
+            to,
+            to_type,
+            value.saturated_into::<u128>().saturated_into(),
+        )
+        .map_err(|err| match err {
+            AssetErr::NotEnough => Error::<T>::InsufficientFeeAsset,
+            AssetErr::Frozen => Error::<T>::FeeAssetFrozen,
+            _ => Error::<T>::InvalidFeeAsset,
+        })
+    }
+
+    /// Ensures that `sponsor` is willing to pay `fee` for the call of `who` described by `call`.
+    pub fn ensure_sponsored(
+        sponsor: &T::AccountId,
+        who: &T::AccountId,
+        call: &CallMetadata,
+        fee: BalanceOf<T>,
+    ) -> Result<Sponsorship<BalanceOf<T>>, Error<T>> {
+        let sponsorship = Self::sponsorship_of(sponsor).ok_or(Error::<T>::NoSponsorship)?;
+        ensure!(
+            Self::is_sponsored(sponsor, who)
+                && (sponsorship.call_filters.is_empty()
+                    || sponsorship.call_filters.iter().any(|f| f.matches(call))),
+            Error::<T>::NotSponsored
+        );
+        ensure!(
+            fee <= sponsorship.max_fee_per_tx,
+            Error::<T>::ExceedMaxFeePerTx
+        );
+        ensure!(fee <= sponsorship.budget, Error::<T>::InsufficientBudget);
+        Ok(sponsorship)
+    }
+
+    /// Takes `fee` from the budget of `sponsor` for the call of `who`.
+    ///
+    /// The unused part is returned by `settle_sponsored_fee` after the dispatch.
+    pub fn withdraw_sponsor_budget(
+        sponsor: &T::AccountId,
+        who: &T::AccountId,
+        call: &CallMetadata,
+        fee: BalanceOf<T>,
+    ) -> Result<(), Error<T>> {
+        let mut sponsorship = Self::ensure_sponsored(sponsor, who, call, fee)?;
+        sponsorship.budget -= fee;
+        SponsorshipOf::<T>::insert(sponsor, sponsorship);
+        Ok(())
+    }
+
+    /// Returns the unused part of the `withdrawn` fee to the budget of `sponsor`.
+    pub fn settle_sponsored_fee(
+        sponsor: &T::AccountId,
+        who: &T::AccountId,
+        withdrawn: BalanceOf<T>,
+        actual_fee: BalanceOf<T>,
+    ) {
+        let actual_fee = actual_fee.min(withdrawn);
+        SponsorshipOf::<T>::mutate(sponsor, |maybe_sponsorship| {
+            if let Some(sponsorship) = maybe_sponsorship {
+                sponsorship.budget = sponsorship.budget.saturating_add(withdrawn - actual_fee);
+            }
+        });
+        Self::deposit_event(Event::<T>::FeeSponsored(
+            sponsor.clone(),
+            who.clone(),
+            actual_fee,
+        ));
+    }
+
+    /// Attaches the fee measured by `asset_id` to the fee `details`.
+    pub fn with_asset_fee(
+        details: FeeDetails<BalanceOf<T>>,
+        asset_id: AssetId,
+    ) -> FeeDetails<BalanceOf<T>> {
+        let native_fee = details.final_fee.saturating_sub(details.extra_fee);
+        FeeDetails {
+            asset_fee: Self::native_fee_in_asset(asset_id, native_fee)
+                .map(|amount| AssetFee { asset_id, amount }),
+            ..details
+        }
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+
+use frame_support::{
+    impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types, sp_io,
+    weights::{IdentityFee, Weight},
+};
+
+use chainx_primitives::AssetId;
+pub use xp_protocol::X_BTC;
+use xpallet_assets::{AssetInfo, AssetRestrictions, Chain};
+
+use crate::*;
+
+/// The AccountId alias in this test module.
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+impl_outer_dispatch! {
+    pub enum Call for Test where origin: Origin {
+        pallet_balances::Balances,
+    }
+}
+
+use frame_system as system;
+mod transaction_fee {
+    // Re-export needed for `impl_outer_event!`.
+    pub use super::super::*;
+}
+
+impl_outer_event! {
+    pub enum MetaEvent for Test {
+        system<T>,
+        pallet_balances<T>,
+        xpallet_assets_registrar,
+        xpallet_assets<T>,
+        transaction_fee<T>,
+    }
+}
+
+// For testing the pallet, we construct most of a mock runtime. This means
+// first constructing a configuration type (`Test`) which `impl`s each of the
+// configuration traits of pallets we want to use.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = MetaEvent;
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = MetaEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const TransactionByteFee: Balance = 1;
+}
+
+impl pallet_transaction_payment::Trait for Test {
+    type OnChargeTransaction = pallet_transaction_payment::CurrencyAdapter<Balances, ()>;
+    type TransactionByteFee = TransactionByteFee;
+    type WeightToFee = IdentityFee<Balance>;
+    type FeeMultiplierUpdate = ();
+}
+
+parameter_types! {
+    pub const ChainXAssetId: AssetId = 0;
+    pub const RegistrationDeposit: Balance = 100;
+}
+
+impl xpallet_assets_registrar::Trait for Test {
+    type Event = MetaEvent;
+    type NativeAssetId = ChainXAssetId;
+    type RegistrarHandler = ();
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
+    type WeightInfo = ();
+}
+
+impl xpallet_assets::Trait for Test {
+    type Event = MetaEvent;
+    type Currency = Balances;
+    type Amount = i128;
+    type TreasuryAccount = ();
+    type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
+    type OnAssetChanged = ();
+    type MaxVestingDelay = ();
+    type WeightInfo = ();
+}
+
+thread_local! {
+    pub static NATIVE_PRICE: RefCell<Option<u128>> = RefCell::new(None);
+}
+
+pub struct DummyPriceFeed;
+impl PriceFeed for DummyPriceFeed {
+    fn native_price_of(_: AssetId) -> Option<u128> {
+        NATIVE_PRICE.with(|v| *v.borrow())
+    }
+}
+
+pub struct DummyFeeReceivers;
+impl FeeReceivers<AccountId> for DummyFeeReceivers {
+    fn author_and_reward_pot() -> (AccountId, AccountId) {
+        (AUTHOR, REWARD_POT)
+    }
+}
+
+parameter_types! {
+    pub const SponsorshipDeposit: Balance = 10;
+    pub const CallFilterDeposit: Balance = 1;
+}
+
+impl Trait for Test {
+    type Event = MetaEvent;
+    type PriceFeed = DummyPriceFeed;
+    type FeeReceivers = DummyFeeReceivers;
+    type SponsorshipDeposit = SponsorshipDeposit;
+    type CallFilterDeposit = CallFilterDeposit;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type XAssets = xpallet_assets::Module<Test>;
+pub type XTransactionFee = Module<Test>;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const AUTHOR: AccountId = 100;
+pub const REWARD_POT: AccountId = 101;
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self
+    }
+}
+
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        let _ = pallet_balances::GenesisConfig::<Test> {
+            balances: vec![(ALICE, 1000), (BOB, 1000), (CHARLIE, 1000)],
+        }
+        .assimilate_storage(&mut storage);
+
+        let btc = AssetInfo::new::<Test>(
+            b"X-BTC".to_vec(),
+            b"X-BTC".to_vec(),
+            Chain::Bitcoin,
+            8,
+            b"ChainX's cross-chain Bitcoin".to_vec(),
+        )
+        .unwrap();
+        let _ = xpallet_assets_registrar::GenesisConfig {
+            assets: vec![(X_BTC, btc, true, true)],
+        }
+        .assimilate_storage::<Test>(&mut storage);
+
+        let mut endowed = BTreeMap::new();
+        endowed.insert(X_BTC, vec![(ALICE, 100), (BOB, 100)]);
+        let _ = xpallet_assets::GenesisConfig::<Test> {
+            assets_restrictions: vec![(X_BTC, AssetRestrictions::empty())],
+            endowed,
+        }
+        .assimilate_storage(&mut storage);
+
+        let mut ext = sp_io::TestExternalities::new(storage);
+        ext.execute_with(|| System::set_block_number(1));
+        ext
+    }
+
+    pub fn build_and_execute(self, test: impl FnOnce()) {
+        let mut ext = self.build();
+        ext.execute_with(test);
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+
+use frame_support::{
+    dispatch::GetCallMetadata,
+    weights::{DispatchInfo, PostDispatchInfo},
+};
+use sp_runtime::{
+    traits::{
+        DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SaturatedConversion, SignedExtension,
+        Zero,
+    },
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError,
+        ValidTransaction,
+    },
+    DispatchResult, FixedPointOperand,
+};
+
+use pallet_transaction_payment::ChargeTransactionPayment;
+
+use chainx_primitives::AssetId;
+
+use crate::{BalanceOf, Module, Trait};
+
+type TransactionPayment<T> = pallet_transaction_payment::Module<T>;
+
+/// The asset fee reserved from the payer before the dispatch.
+pub struct ReservedAssetFee<T: Trait> {
+    who: T::AccountId,
+    asset_id: AssetId,
+    tip: BalanceOf<T>,
+    amount: BalanceOf<T>,
+}
+
+/// The fee of `who` taken from the budget of `sponsor` before the dispatch.
+pub struct SponsoredFee<T: Trait> {
+    sponsor: T::AccountId,
+    who: T::AccountId,
+    tip: BalanceOf<T>,
+    amount: BalanceOf<T>,
+}
+
+/// A struct for charging the transaction fee in PCX or in a whitelisted asset.
+///
+/// This takes the place of `pallet_transaction_payment::ChargeTransactionPayment`, the fee
+/// is charged in PCX as usual if `fee_asset` is `None`, otherwise the fee is converted into
+/// `fee_asset` at the rate of `FeeAssetRateOf`.
+///
+/// The fee is charged from `sponsor` instead of the signer if `sponsor` is specified, which
+/// fails unless the signer's call is sponsored. The sponsored transaction can not have a tip,
+/// otherwise the signer could spend the budget of the sponsor at will.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct ChargeAssetTxPayment<T: Trait> {
+    #[codec(compact)]
+    tip: BalanceOf<T>,
+    fee_asset: Option<AssetId>,
+    sponsor: Option<T::AccountId>,
+}
+
+impl<T: Trait + Send + Sync> ChargeAssetTxPayment<T>
+where
+    T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetCallMetadata,
+    BalanceOf<T>: Send + Sync + FixedPointOperand,
+{
+    pub fn new(
+        tip: BalanceOf<T>,
+        fee_asset: Option<AssetId>,
+        sponsor: Option<T::AccountId>,
+    ) -> Self {
+        Self {
+            tip,
+            fee_asset,
+            sponsor,
+        }
+    }
+
+    /// Returns the asset chosen to pay the transaction fee, `None` for PCX.
+    pub fn fee_asset(&self) -> Option<AssetId> {
+        self.fee_asset
+    }
+
+    /// Returns the sponsor if it's willing to pay `fee` for the `call` of `who`.
+    pub fn sponsor_of(
+        &self,
+        who: &T::AccountId,
+        call: &T::Call,
+        fee: BalanceOf<T>,
+    ) -> Option<T::AccountId> {
+        let sponsor = self.sponsor.as_ref()?;
+        if !self.tip.is_zero() {
+            return None;
+        }
+        Module::<T>::ensure_sponsored(sponsor, who, &call.get_call_metadata(), fee).ok()?;
+        Some(sponsor.clone())
+    }
+
+    fn native(&self) -> ChargeTransactionPayment<T> {
+        ChargeTransactionPayment::from(self.tip)
+    }
+
+    /// Returns the account paying the fee of `who`, without touching the sponsor's budget.
+    fn payer<'a>(
+        &'a self,
+        who: &'a T::AccountId,
+        call: &T::Call,
+        fee: BalanceOf<T>,
+    ) -> Result<&'a T::AccountId, TransactionValidityError> {
+        match &self.sponsor {
+            Some(sponsor) => {
+                if !self.tip.is_zero() {
+                    return Err(InvalidTransaction::Payment.into());
+                }
+                Module::<T>::ensure_sponsored(sponsor, who, &call.get_call_metadata(), fee)
+                    .map_err(|_| InvalidTransaction::Payment)?;
+                Ok(sponsor)
+            }
+            None => Ok(who),
+        }
+    }
+
+    /// Takes the fee of `who` from the budget of the sponsor if any.
+    fn withdraw_sponsor_budget(
+        &self,
+        who: &T::AccountId,
+        call: &T::Call,
+        fee: BalanceOf<T>,
+    ) -> Result<Option<SponsoredFee<T>>, TransactionValidityError> {
+        let sponsor = match &self.sponsor {
+            Some(sponsor) => sponsor,
+            None => return Ok(None),
+        };
+        if !self.tip.is_zero() {
+            return Err(InvalidTransaction::Payment.into());
+        }
+        Module::<T>::withdraw_sponsor_budget(sponsor, who, &call.get_call_metadata(), fee)
+            .map_err(|_| InvalidTransaction::Payment)?;
+        Ok(Some(SponsoredFee {
+            sponsor: sponsor.clone(),
+            who: who.clone(),
+            tip: self.tip,
+            amount: fee,
+        }))
+    }
+}
+
+impl<T: Trait> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(
+            f,
+            "ChargeAssetTxPayment<{:?}, {:?}, {:?}>",
+            self.tip, self.fee_asset, self.sponsor
+        )
+    }
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for ChargeAssetTxPayment<T>
+where
+    T::Call: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo> + GetCallMetadata,
+    BalanceOf<T>: Send + Sync + FixedPointOperand,
+{
+    const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+    type AccountId = T::AccountId;
+    type Call = T::Call;
+    type AdditionalSigned = ();
+    type Pre = (
+        <ChargeTransactionPayment<T> as SignedExtension>::Pre,
+        Option<ReservedAssetFee<T>>,
+        Option<SponsoredFee<T>>,
+    );
+
+    fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        let fee = TransactionPayment::<T>::compute_fee(len as u32, info, self.tip);
+        let payer = self.payer(who, call, fee)?;
+        match self.fee_asset {
+            Some(asset_id) => {
+                Module::<T>::ensure_can_pay_asset_fee(payer, asset_id, fee)
+                    .map_err(|_| InvalidTransaction::Payment)?;
+                Ok(ValidTransaction {
+                    priority: fee.saturated_into::<TransactionPriority>(),
+                    ..Default::default()
+                })
+            }
+            None => self.native().validate(payer, call, info, len),
+        }
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> sp_std::result::Result<Self::Pre, TransactionValidityError> {
+        let fee = TransactionPayment::<T>::compute_fee(len as u32, info, self.tip);
+        let sponsored = self.withdraw_sponsor_budget(who, call, fee)?;
+        let payer = sponsored
+            .as_ref()
+            .map_or(who, |sponsored| &sponsored.sponsor);
+        match self.fee_asset {
+            Some(asset_id) => {
+                let amount = Module::<T>::reserve_asset_fee(payer, asset_id, fee)
+                    .map_err(|_| InvalidTransaction::Payment)?;
+                let reserved = ReservedAssetFee {
+                    who: payer.clone(),
+                    asset_id,
+                    tip: self.tip,
+                    amount,
+                };
+                Ok((Default::default(), Some(reserved), sponsored))
+            }
+            None => {
+                let native_pre = self.native().pre_dispatch(payer, call, info, len)?;
+                Ok((native_pre, None, sponsored))
+            }
+        }
+    }
+
+    fn post_dispatch(
+        pre: Self::Pre,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> sp_std::result::Result<(), TransactionValidityError> {
+        let (native_pre, reserved, sponsored) = pre;
+        match reserved {
+            Some(ReservedAssetFee {
+                who,
+                asset_id,
+                tip,
+                amount,
+            }) => {
+                let actual_fee =
+                    TransactionPayment::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+                Module::<T>::settle_asset_fee(&who, asset_id, amount, actual_fee)
+                    .map_err(|_| InvalidTransaction::Payment)?;
+            }
+            None => ChargeTransactionPayment::<T>::post_dispatch(
+                native_pre, info, post_info, len, result,
+            )?,
+        }
+
+        if let Some(SponsoredFee {
+            sponsor,
+            who,
+            tip,
+            amount,
+        }) = sponsored
+        {
+            let actual_fee =
+                TransactionPayment::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+            Module::<T>::settle_sponsored_fee(&sponsor, &who, amount, actual_fee);
+        }
+
+        Ok(())
+    }
+}
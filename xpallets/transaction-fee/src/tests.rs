@@ -0,0 +1,421 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{
+    assert_err, assert_noop, assert_ok,
+    dispatch::GetCallMetadata,
+    weights::{DispatchInfo, PostDispatchInfo},
+    StorageDoubleMap,
+};
+use sp_runtime::{
+    traits::SignedExtension, transaction_validity::InvalidTransaction, DispatchError,
+};
+
+use xpallet_assets::AssetType;
+
+use crate::mock::Call;
+use crate::mock::*;
+use crate::*;
+
+const CALL: &<Test as frame_system::Trait>::Call =
+    &Call::Balances(pallet_balances::Call::transfer(BOB, 69));
+
+/// With `IdentityFee` and no base weight, the fee is `len + weight + tip`.
+fn info_from_weight(w: Weight) -> DispatchInfo {
+    DispatchInfo {
+        weight: w,
+        ..Default::default()
+    }
+}
+
+fn post_info_from_weight(w: Weight) -> PostDispatchInfo {
+    PostDispatchInfo {
+        actual_weight: Some(w),
+        pays_fee: Default::default(),
+    }
+}
+
+fn t_set_fee_asset_rate(rate: Option<FeeAssetRate>) {
+    assert_ok!(XTransactionFee::set_fee_asset_rate(
+        Origin::root(),
+        X_BTC,
+        rate
+    ));
+}
+
+fn t_usable(who: AccountId) -> Balance {
+    XAssets::usable_balance(&who, &X_BTC)
+}
+
+fn t_reserved(who: AccountId) -> Balance {
+    XAssets::asset_balance_of(&who, &X_BTC, AssetType::Reserved)
+}
+
+fn t_reserve_asset_fee(who: AccountId, fee: Balance) -> Result<Balance, DispatchError> {
+    XTransactionFee::reserve_asset_fee(&who, X_BTC, fee).map_err(Into::into)
+}
+
+#[test]
+fn set_fee_asset_rate_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XTransactionFee::set_fee_asset_rate(
+                Origin::signed(ALICE),
+                X_BTC,
+                Some(FeeAssetRate::Spot)
+            ),
+            DispatchError::BadOrigin
+        );
+
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(2)));
+        assert_eq!(
+            XTransactionFee::fee_asset_rate_of(X_BTC),
+            Some(FeeAssetRate::Fixed(2))
+        );
+
+        t_set_fee_asset_rate(None);
+        assert_eq!(XTransactionFee::fee_asset_rate_of(X_BTC), None);
+    });
+}
+
+#[test]
+fn native_fee_in_asset_should_round_up() {
+    ExtBuilder::default().build_and_execute(|| {
+        // Not whitelisted.
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 15), None);
+
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(2)));
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 15), Some(8));
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 14), Some(7));
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 0), Some(0));
+
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(0)));
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 15), None);
+
+        // No time-weighted average price yet.
+        t_set_fee_asset_rate(Some(FeeAssetRate::Spot));
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 15), None);
+
+        NATIVE_PRICE.with(|v| *v.borrow_mut() = Some(3));
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 15), Some(5));
+        assert_eq!(XTransactionFee::native_fee_in_asset(X_BTC, 16), Some(6));
+    });
+}
+
+#[test]
+fn validate_should_not_move_asset_fee() {
+    ExtBuilder::default().build_and_execute(|| {
+        let info = info_from_weight(5);
+        let ext = ChargeAssetTxPayment::<Test>::new(0, Some(X_BTC), None);
+
+        // Not whitelisted.
+        assert_eq!(
+            ext.validate(&ALICE, CALL, &info, 10),
+            Err(InvalidTransaction::Payment.into())
+        );
+
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(2)));
+        let valid = ext.validate(&ALICE, CALL, &info, 10).unwrap();
+        assert_eq!(valid.priority, 15);
+        assert_eq!(t_usable(ALICE), 100);
+        assert_eq!(t_reserved(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+    });
+}
+
+#[test]
+fn invalid_asset_fee_should_be_rejected() {
+    ExtBuilder::default().build_and_execute(|| {
+        let info = info_from_weight(5);
+        let ext = ChargeAssetTxPayment::<Test>::new(0, Some(X_BTC), None);
+
+        // Not whitelisted.
+        assert_err!(
+            t_reserve_asset_fee(ALICE, 15),
+            Error::<Test>::InvalidFeeAsset
+        );
+
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(2)));
+
+        // Insufficient usable balance: 7 < 8.
+        assert_ok!(XAssets::move_usable_balance(&X_BTC, &ALICE, &CHARLIE, 93));
+        assert_err!(
+            t_reserve_asset_fee(ALICE, 15),
+            Error::<Test>::InsufficientFeeAsset
+        );
+        assert_eq!(
+            ext.validate(&ALICE, CALL, &info, 10),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert!(ext.clone().pre_dispatch(&ALICE, CALL, &info, 10).is_err());
+        assert_ok!(t_reserve_asset_fee(ALICE, 14));
+        assert_eq!(t_reserved(ALICE), 7);
+
+        // Frozen.
+        xpallet_assets::FrozenAccounts::<Test>::insert(&BOB, X_BTC, true);
+        assert_err!(t_reserve_asset_fee(BOB, 15), Error::<Test>::FeeAssetFrozen);
+        assert_eq!(
+            ext.validate(&BOB, CALL, &info, 10),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert!(ext.pre_dispatch(&BOB, CALL, &info, 10).is_err());
+        assert_eq!(t_usable(BOB), 100);
+        assert_eq!(t_reserved(BOB), 0);
+    });
+}
+
+#[test]
+fn asset_fee_should_be_reserved_and_refunded() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(2)));
+
+        let info = info_from_weight(5);
+        let pre = ChargeAssetTxPayment::<Test>::new(0, Some(X_BTC), None)
+            .pre_dispatch(&ALICE, CALL, &info, 10)
+            .unwrap();
+        // 15 PCX, i.e., 7.5 X-BTC rounded up to 8.
+        assert_eq!(t_usable(ALICE), 92);
+        assert_eq!(t_reserved(ALICE), 8);
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+
+        assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+            pre,
+            &info,
+            &post_info_from_weight(1),
+            10,
+            &Ok(())
+        ));
+        // The actual fee is 11 PCX, i.e., 5.5 X-BTC rounded up to 6, the rest 2 is refunded.
+        assert_eq!(t_usable(ALICE), 94);
+        assert_eq!(t_reserved(ALICE), 0);
+        // 90% to the reward pot rounded down, the rest to the author.
+        assert_eq!(t_usable(REWARD_POT), 5);
+        assert_eq!(t_usable(AUTHOR), 1);
+        assert!(System::events().iter().any(|record| record.event
+            == MetaEvent::transaction_fee(RawEvent::AssetFeePaid(
+                X_BTC, AUTHOR, 1, REWARD_POT, 5
+            ))));
+    });
+}
+
+#[test]
+fn asset_fee_should_be_charged_in_full_once_unwhitelisted() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(2)));
+
+        let info = info_from_weight(5);
+        let pre = ChargeAssetTxPayment::<Test>::new(0, Some(X_BTC), None)
+            .pre_dispatch(&ALICE, CALL, &info, 10)
+            .unwrap();
+
+        t_set_fee_asset_rate(None);
+        assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+            pre,
+            &info,
+            &post_info_from_weight(1),
+            10,
+            &Ok(())
+        ));
+        assert_eq!(t_usable(ALICE), 92);
+        assert_eq!(t_reserved(ALICE), 0);
+        assert_eq!(t_usable(REWARD_POT), 7);
+        assert_eq!(t_usable(AUTHOR), 1);
+    });
+}
+
+#[test]
+fn native_fee_should_be_charged_without_fee_asset() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_set_fee_asset_rate(Some(FeeAssetRate::Fixed(2)));
+
+        let info = info_from_weight(5);
+        let pre = ChargeAssetTxPayment::<Test>::new(0, None, None)
+            .pre_dispatch(&ALICE, CALL, &info, 10)
+            .unwrap();
+        assert_eq!(Balances::free_balance(ALICE), 1000 - 15);
+
+        assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+            pre,
+            &info,
+            &post_info_from_weight(1),
+            10,
+            &Ok(())
+        ));
+        assert_eq!(Balances::free_balance(ALICE), 1000 - 11);
+        assert_eq!(t_usable(ALICE), 100);
+    });
+}
+
+fn t_call_filter(pallet: &[u8], call: Option<&[u8]>) -> CallFilter {
+    CallFilter {
+        pallet: pallet.to_vec(),
+        call: call.map(|call| call.to_vec()),
+    }
+}
+
+fn t_ensure_sponsored(who: AccountId, call: &Call, fee: Balance) -> Result<(), DispatchError> {
+    XTransactionFee::ensure_sponsored(&ALICE, &who, &call.get_call_metadata(), fee)
+        .map(|_| ())
+        .map_err(Into::into)
+}
+
+#[test]
+fn sponsor_should_reserve_deposit_and_bound_call_filters() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_noop!(
+            XTransactionFee::sponsor(
+                Origin::signed(ALICE),
+                100,
+                20,
+                vec![t_call_filter(b"Balances", None); MAX_CALL_FILTERS + 1]
+            ),
+            Error::<Test>::TooManyCallFilters
+        );
+        assert_noop!(
+            XTransactionFee::sponsor(
+                Origin::signed(ALICE),
+                100,
+                20,
+                vec![t_call_filter(
+                    b"Balances",
+                    Some(&[b'a'; MAX_CALL_NAME_LEN + 1][..])
+                )]
+            ),
+            Error::<Test>::CallFilterTooLong
+        );
+        assert_noop!(
+            XTransactionFee::toggle_sponsored(Origin::signed(ALICE), BOB, true),
+            Error::<Test>::NoSponsorship
+        );
+
+        let call_filters = vec![
+            t_call_filter(b"Balances", Some(&b"transfer"[..])),
+            t_call_filter(b"XAssets", None),
+        ];
+        assert_ok!(XTransactionFee::sponsor(
+            Origin::signed(ALICE),
+            100,
+            20,
+            call_filters
+        ));
+        assert_eq!(Balances::reserved_balance(ALICE), 10 + 2);
+        assert_eq!(XTransactionFee::sponsorship_deposit_of(ALICE), 12);
+
+        // Replacing the sponsorship adjusts the deposit.
+        assert_ok!(XTransactionFee::sponsor(
+            Origin::signed(ALICE),
+            100,
+            20,
+            vec![]
+        ));
+        assert_eq!(Balances::reserved_balance(ALICE), 10);
+
+        assert_ok!(XTransactionFee::unsponsor(Origin::signed(ALICE)));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(XTransactionFee::sponsorship_deposit_of(ALICE), 0);
+        assert_eq!(XTransactionFee::sponsorship_of(ALICE), None);
+    });
+}
+
+#[test]
+fn call_filters_should_only_apply_to_whitelisted_accounts() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XTransactionFee::sponsor(
+            Origin::signed(ALICE),
+            100,
+            20,
+            vec![t_call_filter(b"Balances", Some(&b"transfer"[..]))]
+        ));
+
+        // The call matches, but the account is not whitelisted.
+        assert_err!(
+            t_ensure_sponsored(BOB, CALL, 10),
+            Error::<Test>::NotSponsored
+        );
+
+        assert_ok!(XTransactionFee::toggle_sponsored(
+            Origin::signed(ALICE),
+            BOB,
+            true
+        ));
+        assert_ok!(t_ensure_sponsored(BOB, CALL, 10));
+
+        // The account is whitelisted, but the call does not match.
+        let other_call = Call::Balances(pallet_balances::Call::transfer_keep_alive(CHARLIE, 69));
+        assert_err!(
+            t_ensure_sponsored(BOB, &other_call, 10),
+            Error::<Test>::NotSponsored
+        );
+
+        assert_err!(
+            t_ensure_sponsored(BOB, CALL, 21),
+            Error::<Test>::ExceedMaxFeePerTx
+        );
+
+        assert_ok!(XTransactionFee::toggle_sponsored(
+            Origin::signed(ALICE),
+            BOB,
+            false
+        ));
+        assert_err!(
+            t_ensure_sponsored(BOB, CALL, 10),
+            Error::<Test>::NotSponsored
+        );
+    });
+}
+
+#[test]
+fn sponsored_fee_should_be_paid_by_sponsor() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XTransactionFee::sponsor(
+            Origin::signed(ALICE),
+            20,
+            15,
+            vec![]
+        ));
+        assert_ok!(XTransactionFee::toggle_sponsored(
+            Origin::signed(ALICE),
+            BOB,
+            true
+        ));
+        let budget = || XTransactionFee::sponsorship_of(ALICE).unwrap().budget;
+
+        let info = info_from_weight(5);
+        let ext = ChargeAssetTxPayment::<Test>::new(0, None, Some(ALICE));
+
+        // Neither the not whitelisted account nor the tip is sponsored.
+        assert_eq!(
+            ext.validate(&CHARLIE, CALL, &info, 10),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert_eq!(
+            ChargeAssetTxPayment::<Test>::new(1, None, Some(ALICE)).validate(&BOB, CALL, &info, 10),
+            Err(InvalidTransaction::Payment.into())
+        );
+
+        // The budget is untouched in the validation.
+        assert_ok!(ext.validate(&BOB, CALL, &info, 10));
+        assert_eq!(budget(), 20);
+
+        let free = Balances::free_balance(ALICE);
+        let pre = ext.clone().pre_dispatch(&BOB, CALL, &info, 10).unwrap();
+        assert_eq!(budget(), 5);
+        assert_eq!(Balances::free_balance(ALICE), free - 15);
+        assert_eq!(Balances::free_balance(BOB), 1000);
+
+        assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+            pre,
+            &info,
+            &post_info_from_weight(1),
+            10,
+            &Ok(())
+        ));
+        assert_eq!(budget(), 9);
+        assert_eq!(Balances::free_balance(ALICE), free - 11);
+        assert!(System::events().iter().any(|record| record.event
+            == MetaEvent::transaction_fee(RawEvent::FeeSponsored(ALICE, BOB, 11))));
+
+        // Insufficient budget.
+        assert!(ext.pre_dispatch(&BOB, CALL, &info, 10).is_err());
+        assert_eq!(budget(), 9);
+    });
+}
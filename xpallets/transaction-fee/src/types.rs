@@ -4,8 +4,13 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use sp_std::prelude::*;
+
+use frame_support::dispatch::CallMetadata;
 use sp_runtime::RuntimeDebug;
 
+use chainx_primitives::{AccountId, AssetId};
+
 /// The base fee and adjusted weight and length fees constitute the _inclusion fee,_ which is
 /// the minimum fee for a transaction to be included in a block.
 ///
@@ -30,6 +35,15 @@ pub struct InclusionFee<Balance> {
     pub adjusted_weight_fee: Balance,
 }
 
+/// The fee paid in a non-native asset, converted from the native fee.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetFee<Balance> {
+    pub asset_id: AssetId,
+    pub amount: Balance,
+}
+
 /// The `final_fee` is composed of:
 ///   - (Optional) `inclusion_fee`: Only the `Pays::Yes` transaction can have the inclusion fee.
 ///   - (Optional) `tip`: If included in the transaction, the tip will be added on top. Only
@@ -47,4 +61,62 @@ pub struct FeeDetails<Balance> {
     pub extra_fee: Balance,
     pub tip: Balance,
     pub final_fee: Balance,
+    /// The fee measured by the asset chosen to pay the fee, if it's not PCX.
+    ///
+    /// The extra fee is not included as it's always charged in PCX.
+    pub asset_fee: Option<AssetFee<Balance>>,
+    /// The account paying the fee on behalf of the signer, if any.
+    ///
+    /// The extra fee is not included as it's always charged from the signer.
+    pub sponsor: Option<AccountId>,
+}
+
+/// The rate at which the native fee is converted into a whitelisted fee asset.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum FeeAssetRate {
+    /// Follows the time-weighted average price of the spot trading pair against PCX in
+    /// last era, which is sampled for the assets whose mining power follows the price.
+    Spot,
+    /// A fixed amount of PCX per unit of the asset set by the governance.
+    Fixed(u128),
+}
+
+/// A filter of the sponsored calls, matching all the calls of `pallet` if `call` is `None`.
+///
+/// The names are the ones in the call metadata, e.g., `XAssets` and `transfer`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CallFilter {
+    pub pallet: Vec<u8>,
+    pub call: Option<Vec<u8>>,
+}
+
+impl CallFilter {
+    /// Returns true if the call described by `metadata` matches this filter.
+    pub fn matches(&self, metadata: &CallMetadata) -> bool {
+        self.pallet == metadata.pallet_name.as_bytes()
+            && self
+                .call
+                .as_ref()
+                .map_or(true, |call| call[..] == *metadata.function_name.as_bytes())
+    }
+}
+
+/// The fee sponsorship of a sponsor.
+///
+/// The fee of an account is sponsored if the account is whitelisted by the sponsor,
+/// and the call matches any of `call_filters` unless there is no call filter.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct Sponsorship<Balance> {
+    /// The remaining amount of fee the sponsor is willing to pay.
+    pub budget: Balance,
+    /// The maximum fee paid by the sponsor for a single transaction.
+    pub max_fee_per_tx: Balance,
+    /// The calls sponsored for the whitelisted accounts, all the calls if empty.
+    pub call_filters: Vec<CallFilter>,
 }